@@ -0,0 +1,452 @@
+//! Sub-commands for submitting blobs to a running node over RPC.
+//!
+//! Besides `upload-blob`, which does everything in one go, the `blob-tx` sub-commands split a blob
+//! transaction into building the signing payload, signing it and submitting the result, so that
+//! the uploader key can stay on a machine without network access.
+
+use crate::{
+	blob_rpc::blobs_of,
+	transaction::{additional_signed, sign_extrinsic, signed_extra, AdditionalSigned, Signer},
+};
+use blobchain_runtime::{self as runtime, Hash, Header, MaxBlobSize, Nonce};
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use jsonrpsee::{
+	core::client::{ClientT, SubscriptionClientT},
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use pallet_blobmanager::{BlobLimits, DefaultLimits};
+use sc_transaction_pool_api::TransactionStatus;
+use sp_core::{
+	crypto::{KeyTypeId, Ss58Codec},
+	sr25519, Bytes, Pair,
+};
+use sp_runtime::{generic::Era, traits::Header as _};
+use std::{
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+/// Default RPC endpoint of a locally running node.
+const DEFAULT_RPC_URL: &str = "ws://127.0.0.1:9944";
+
+/// Parameters selecting the key a blob transaction is signed with.
+#[derive(Debug, Clone, clap::Args)]
+pub struct SignerParams {
+	/// Secret URI of the uploader key, e.g. `//Alice` or a mnemonic phrase.
+	#[arg(long, required_unless_present = "public")]
+	pub suri: Option<String>,
+
+	/// SS58 address of an uploader key held in the keystore given by `--keystore-path`.
+	#[arg(long, conflicts_with = "suri", requires = "keystore_path")]
+	pub public: Option<String>,
+
+	/// Keystore directory holding the key given by `--public`.
+	#[arg(long)]
+	pub keystore_path: Option<PathBuf>,
+
+	/// Password of the keystore.
+	#[arg(long)]
+	pub password: Option<String>,
+
+	/// Key type the uploader key is stored under in the keystore.
+	#[arg(long, default_value = "acco")]
+	pub key_type: String,
+}
+
+impl SignerParams {
+	/// Builds the [`Signer`] described by the parameters.
+	pub fn signer(&self) -> sc_cli::Result<Signer> {
+		if let Some(suri) = &self.suri {
+			let pair = sr25519::Pair::from_string(suri, None)
+				.map_err(|e| format!("Invalid secret URI: {:?}", e))?;
+			return Ok(Signer::Pair(pair));
+		}
+
+		let public = self.public.as_ref().ok_or("Either --suri or --public must be given")?;
+		let public = sr25519::Public::from_ss58check(public)
+			.map_err(|e| format!("Invalid public key {}: {:?}", public, e))?;
+		let key_type = KeyTypeId::try_from(self.key_type.as_str())
+			.map_err(|_| format!("Invalid key type {}", self.key_type))?;
+		let path = self.keystore_path.as_ref().ok_or("--public requires --keystore-path")?;
+		let keystore = sc_keystore::LocalKeystore::open(
+			path,
+			self.password.clone().map(sp_core::crypto::SecretString::new),
+		)
+		.map_err(|e| format!("Failed to open keystore at {}: {}", path.display(), e))?;
+
+		Ok(Signer::Keystore { keystore: Arc::new(keystore), key_type, public })
+	}
+}
+
+/// The `upload-blob` command used to upload a file as a blob.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct UploadBlobCmd {
+	/// File to upload.
+	#[arg(value_name = "FILE")]
+	pub file: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub signer: SignerParams,
+
+	/// RPC endpoint of the node the blob is submitted to.
+	#[arg(long, default_value = DEFAULT_RPC_URL)]
+	pub url: String,
+}
+
+impl UploadBlobCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		block_on(self.upload())
+	}
+
+	async fn upload(&self) -> sc_cli::Result<()> {
+		let signer = self.signer.signer()?;
+		let client = connect(&self.url).await?;
+		let blob = read_blob(&self.file, limits(&client).await?.max_blob_size)?;
+
+		let genesis_hash = block_hash(&client, 0).await?;
+		let best = header(&client, None).await?;
+		let version = runtime_version(&client).await?;
+		let nonce = account_nonce(&client, &signer).await?;
+
+		let period = runtime::BlockHashCount::get()
+			.checked_next_power_of_two()
+			.map(|c| c / 2)
+			.unwrap_or(2) as u64;
+		let extra = signed_extra(nonce, Era::mortal(period, best.number.into()));
+		let additional = additional_signed(
+			version.spec_version,
+			version.transaction_version,
+			genesis_hash,
+			best.hash(),
+		);
+		let call = pallet_blobmanager::Call::upload_blob { blob }.into();
+		let extrinsic = sign_extrinsic(&signer, call, extra, additional)?;
+
+		submit_and_watch(&client, extrinsic.encode()).await
+	}
+}
+
+/// Sub-commands for building, signing and submitting a blob transaction in separate steps.
+#[derive(Debug, clap::Subcommand)]
+pub enum BlobTxSubcommand {
+	/// Build the unsigned signing payload of an `upload_blob` transaction.
+	Build(BuildBlobTxCmd),
+
+	/// Sign a payload produced by `build`.
+	Sign(SignBlobTxCmd),
+
+	/// Submit an extrinsic produced by `sign` to a running node.
+	Submit(SubmitBlobTxCmd),
+}
+
+impl BlobTxSubcommand {
+	/// Run the sub-command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			Self::Build(cmd) => cmd.run(),
+			Self::Sign(cmd) => cmd.run(),
+			Self::Submit(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// Everything needed to sign an `upload_blob` transaction, as written by `blob-tx build`.
+#[derive(Encode, Decode)]
+pub struct UnsignedBlobTx {
+	/// The `upload_blob` call.
+	pub call: runtime::RuntimeCall,
+	/// Signed extensions of the transaction.
+	pub extra: runtime::SignedExtra,
+	/// Data covered by the signature which isn't part of the extrinsic.
+	pub additional: AdditionalSigned,
+}
+
+/// The `blob-tx build` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct BuildBlobTxCmd {
+	/// File to upload.
+	#[arg(value_name = "FILE")]
+	pub file: PathBuf,
+
+	/// Nonce of the uploader account.
+	#[arg(long)]
+	pub nonce: Nonce,
+
+	/// Hash of the genesis block of the chain.
+	#[arg(long)]
+	pub genesis_hash: Hash,
+
+	/// Number of blocks the transaction stays valid for. The transaction is immortal if not
+	/// given.
+	#[arg(long, requires_all = ["era_block_number", "era_block_hash"])]
+	pub mortality: Option<u64>,
+
+	/// Number of the block the mortal era starts at.
+	#[arg(long)]
+	pub era_block_number: Option<u64>,
+
+	/// Hash of the block the mortal era starts at.
+	#[arg(long)]
+	pub era_block_hash: Option<Hash>,
+
+	/// Runtime spec version to sign for. Defaults to the version this node was built with.
+	#[arg(long, default_value_t = runtime::VERSION.spec_version)]
+	pub spec_version: u32,
+
+	/// Transaction version to sign for. Defaults to the version this node was built with.
+	#[arg(long, default_value_t = runtime::VERSION.transaction_version)]
+	pub transaction_version: u32,
+
+	/// Largest blob the chain currently accepts, as set with `set_limits`. Defaults to the
+	/// ceiling this node was built with.
+	#[arg(long, default_value_t = MaxBlobSize::get())]
+	pub max_blob_size: u32,
+
+	/// File the payload is written to.
+	#[arg(long, short)]
+	pub output: PathBuf,
+}
+
+impl BuildBlobTxCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let blob = read_blob(&self.file, self.max_blob_size)?;
+
+		let (era, era_hash) = match (self.mortality, self.era_block_number, self.era_block_hash) {
+			(Some(period), Some(number), Some(hash)) => (Era::mortal(period, number), hash),
+			_ => (Era::Immortal, self.genesis_hash),
+		};
+		let unsigned = UnsignedBlobTx {
+			call: pallet_blobmanager::Call::upload_blob { blob }.into(),
+			extra: signed_extra(self.nonce, era),
+			additional: additional_signed(
+				self.spec_version,
+				self.transaction_version,
+				self.genesis_hash,
+				era_hash,
+			),
+		};
+
+		write_hex(&self.output, &unsigned.encode())
+	}
+}
+
+/// The `blob-tx sign` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SignBlobTxCmd {
+	/// Payload written by `blob-tx build`.
+	#[arg(value_name = "PAYLOAD")]
+	pub payload: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub signer: SignerParams,
+
+	/// File the signed extrinsic is written to.
+	#[arg(long, short)]
+	pub output: PathBuf,
+}
+
+impl SignBlobTxCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let UnsignedBlobTx { call, extra, additional } =
+			UnsignedBlobTx::decode(&mut &read_hex(&self.payload)?[..])
+				.map_err(|e| format!("Invalid payload: {}", e))?;
+		let signer = self.signer.signer()?;
+		let extrinsic = sign_extrinsic(&signer, call, extra, additional)?;
+
+		write_hex(&self.output, &extrinsic.encode())
+	}
+}
+
+/// The `blob-tx submit` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SubmitBlobTxCmd {
+	/// Signed extrinsic written by `blob-tx sign`.
+	#[arg(value_name = "EXTRINSIC")]
+	pub extrinsic: PathBuf,
+
+	/// RPC endpoint of the node the extrinsic is submitted to.
+	#[arg(long, default_value = DEFAULT_RPC_URL)]
+	pub url: String,
+}
+
+impl SubmitBlobTxCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let extrinsic = read_hex(&self.extrinsic)?;
+		let blobs = blobs_of(
+			runtime::UncheckedExtrinsic::decode(&mut &extrinsic[..])
+				.map_err(|e| format!("Invalid extrinsic: {}", e))?,
+		);
+
+		block_on(async {
+			let client = connect(&self.url).await?;
+			let max_blob_size = limits(&client).await?.max_blob_size;
+			for blob in &blobs {
+				check_blob_size(blob, max_blob_size)?;
+			}
+			submit_and_watch(&client, extrinsic).await
+		})
+	}
+}
+
+/// Writes `data` to `path` as a hex string.
+fn write_hex(path: &Path, data: &[u8]) -> sc_cli::Result<()> {
+	std::fs::write(path, sp_core::bytes::to_hex(data, false))?;
+	println!("Written to {}", path.display());
+	Ok(())
+}
+
+/// Reads a hex string written by [`write_hex`] from `path`.
+fn read_hex(path: &Path) -> sc_cli::Result<Vec<u8>> {
+	let hex = std::fs::read_to_string(path)?;
+	sp_core::bytes::from_hex(hex.trim())
+		.map_err(|e| format!("Invalid hex in {}: {}", path.display(), e).into())
+}
+
+/// The subset of the node's runtime version needed to sign transactions.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeVersion {
+	/// Version of the runtime specification.
+	pub spec_version: u32,
+	/// Version of the extrinsic format.
+	pub transaction_version: u32,
+}
+
+/// Reads a blob from `path`, checking it against the size limit `max_blob_size`.
+pub fn read_blob(path: &Path, max_blob_size: u32) -> sc_cli::Result<Vec<u8>> {
+	let blob = std::fs::read(path)?;
+	check_blob_size(&blob, max_blob_size)?;
+
+	Ok(blob)
+}
+
+/// Checks `blob` against the size limit `max_blob_size`.
+fn check_blob_size(blob: &[u8], max_blob_size: u32) -> sc_cli::Result<()> {
+	if blob.len() > max_blob_size as usize {
+		return Err(format!(
+			"Blob is {} bytes, the runtime accepts at most {} bytes",
+			blob.len(),
+			max_blob_size
+		)
+		.into());
+	}
+
+	Ok(())
+}
+
+/// Runs `future` to completion on a new tokio runtime.
+pub fn block_on<F: std::future::Future<Output = sc_cli::Result<()>>>(
+	future: F,
+) -> sc_cli::Result<()> {
+	tokio::runtime::Builder::new_multi_thread()
+		.enable_all()
+		.build()?
+		.block_on(future)
+}
+
+/// Connects to the node's RPC server at `url`.
+pub async fn connect(url: &str) -> sc_cli::Result<WsClient> {
+	WsClientBuilder::default()
+		.build(url)
+		.await
+		.map_err(|e| format!("Failed to connect to {}: {}", url, e).into())
+}
+
+async fn block_hash(client: &WsClient, number: u32) -> sc_cli::Result<Hash> {
+	client
+		.request::<Option<Hash>, _>("chain_getBlockHash", rpc_params![number])
+		.await
+		.map_err(|e| format!("chain_getBlockHash failed: {}", e))?
+		.ok_or_else(|| format!("Block {} not found", number).into())
+}
+
+async fn header(client: &WsClient, hash: Option<Hash>) -> sc_cli::Result<Header> {
+	client
+		.request::<Option<Header>, _>("chain_getHeader", rpc_params![hash])
+		.await
+		.map_err(|e| format!("chain_getHeader failed: {}", e))?
+		.ok_or_else(|| "Header not found".into())
+}
+
+async fn runtime_version(client: &WsClient) -> sc_cli::Result<RuntimeVersion> {
+	client
+		.request("state_getRuntimeVersion", rpc_params![])
+		.await
+		.map_err(|e| format!("state_getRuntimeVersion failed: {}", e).into())
+}
+
+async fn account_nonce(client: &WsClient, signer: &Signer) -> sc_cli::Result<Nonce> {
+	client
+		.request("system_accountNextIndex", rpc_params![signer.account_id().to_ss58check()])
+		.await
+		.map_err(|e| format!("system_accountNextIndex failed: {}", e).into())
+}
+
+/// Reads the live blob limits at the node's best block.
+async fn limits(client: &WsClient) -> sc_cli::Result<BlobLimits> {
+	let key = pallet_blobmanager::Limits::<runtime::Runtime>::hashed_key();
+	let limits = client
+		.request::<Option<Bytes>, _>("state_getStorage", rpc_params![Bytes(key.to_vec())])
+		.await
+		.map_err(|e| format!("state_getStorage failed: {}", e))?
+		.map(|data| BlobLimits::decode(&mut &data[..]))
+		.transpose()
+		.map_err(|e| format!("Invalid blob limits: {}", e))?;
+
+	Ok(limits.unwrap_or_else(DefaultLimits::<runtime::Runtime>::get))
+}
+
+/// Submits an encoded extrinsic and reports its progress until it is finalized.
+pub async fn submit_and_watch(client: &WsClient, extrinsic: Vec<u8>) -> sc_cli::Result<()> {
+	let mut subscription = client
+		.subscribe::<TransactionStatus<Hash, Hash>, _>(
+			"author_submitAndWatchExtrinsic",
+			rpc_params![Bytes(extrinsic)],
+			"author_unwatchExtrinsic",
+		)
+		.await
+		.map_err(|e| format!("Failed to submit extrinsic: {}", e))?;
+
+	while let Some(status) = subscription.next().await {
+		let status = status.map_err(|e| format!("Invalid status notification: {}", e))?;
+		match status {
+			TransactionStatus::Future | TransactionStatus::Ready => {
+				println!("Extrinsic is in the transaction pool")
+			},
+			TransactionStatus::Broadcast(_) => println!("Extrinsic was broadcast to peers"),
+			TransactionStatus::InBlock((hash, index)) => {
+				let number = header(client, Some(hash)).await?.number;
+				println!("Included in block #{} ({:?}) at extrinsic index {}", number, hash, index);
+			},
+			TransactionStatus::Retracted(hash) => {
+				println!("Block {:?} was retracted, waiting for re-inclusion", hash)
+			},
+			TransactionStatus::Finalized((hash, index)) => {
+				let number = header(client, Some(hash)).await?.number;
+				println!(
+					"Finalized in block #{} ({:?}) at extrinsic index {}",
+					number, hash, index
+				);
+				return Ok(());
+			},
+			TransactionStatus::FinalityTimeout(hash) => {
+				return Err(format!("Finality timed out for block {:?}", hash).into())
+			},
+			TransactionStatus::Usurped(hash) => {
+				return Err(format!("Extrinsic was usurped by {:?}", hash).into())
+			},
+			TransactionStatus::Dropped => return Err("Extrinsic was dropped from the pool".into()),
+			TransactionStatus::Invalid => return Err("Extrinsic is invalid".into()),
+		}
+	}
+
+	Err("Status subscription ended before finalization".into())
+}
@@ -0,0 +1,206 @@
+//! Prometheus metrics of blob throughput.
+//!
+//! [`BlobMetrics::run`] follows block imports, finality and the transaction pool, and records
+//! how many blobs get included per block, how long blob transactions wait in the pool and how
+//! many blob bytes got finalized.
+//!
+//! The pool only notifies about ready transactions, so blob transactions waiting in its future
+//! queue, e.g. behind a nonce gap, are picked up at the next block import.
+
+use crate::{blob_proposer::is_blob_transaction, blob_store::blobs_at, service::FullClient};
+use blobchain_runtime::{opaque::Block, BlockNumber, Hash};
+use futures::StreamExt;
+use sc_client_api::{
+	BlockBackend, BlockImportNotification, BlockchainEvents, FinalityNotification,
+};
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TxHash};
+use sp_runtime::traits::Header as _;
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use substrate_prometheus_endpoint::{
+	register, Counter, Gauge, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
+
+/// Log target of the blob metrics.
+const LOG_TARGET: &str = "blob-metrics";
+
+/// Time after which a blob transaction that never got included stops being tracked.
+const PENDING_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Blob throughput metrics.
+pub struct BlobMetrics {
+	blobs_per_block: Histogram,
+	blob_bytes_per_block: Histogram,
+	pool_blob_transactions: Gauge<U64>,
+	inclusion_latency: Histogram,
+	finalized_blob_bytes: Counter<U64>,
+}
+
+impl BlobMetrics {
+	/// Registers the metrics with `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			blobs_per_block: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"blobchain_blobs_per_block",
+						"Number of blobs stored by each imported block",
+					)
+					.buckets((0..=10).map(f64::from).collect()),
+				)?,
+				registry,
+			)?,
+			blob_bytes_per_block: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"blobchain_blob_bytes_per_block",
+						"Total size of the blobs stored by each imported block",
+					)
+					.buckets(substrate_prometheus_endpoint::exponential_buckets(1024.0, 4.0, 10)?),
+				)?,
+				registry,
+			)?,
+			pool_blob_transactions: register(
+				Gauge::new(
+					"blobchain_pool_blob_transactions",
+					"Number of blob transactions waiting in the transaction pool",
+				)?,
+				registry,
+			)?,
+			inclusion_latency: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"blobchain_blob_inclusion_latency_seconds",
+						"Time from the submission of a blob transaction to the pool to its \
+						 inclusion in an imported block",
+					)
+					.buckets(substrate_prometheus_endpoint::exponential_buckets(1.0, 2.0, 12)?),
+				)?,
+				registry,
+			)?,
+			finalized_blob_bytes: register(
+				Counter::new(
+					"blobchain_finalized_blob_bytes_total",
+					"Total size of the blobs stored by finalized blocks",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Feeds the metrics from the notifications of `client` and `pool` until the node shuts
+	/// down.
+	pub async fn run<P>(self, client: Arc<FullClient>, pool: Arc<P>)
+	where
+		P: TransactionPool<Block = Block> + 'static,
+	{
+		let mut block_imports = client.import_notification_stream().fuse();
+		let mut finality_notifications = client.finality_notification_stream().fuse();
+		let mut pool_imports = pool.import_notification_stream().fuse();
+		let mut tracker = Tracker {
+			metrics: self,
+			client,
+			pool,
+			pending: HashMap::new(),
+			imported: HashMap::new(),
+		};
+
+		loop {
+			futures::select! {
+				notification = block_imports.next() => match notification {
+					Some(notification) => tracker.on_block_import(notification),
+					None => return,
+				},
+				notification = finality_notifications.next() => match notification {
+					Some(notification) => tracker.on_finality(notification),
+					None => return,
+				},
+				hash = pool_imports.next() => match hash {
+					Some(hash) => tracker.on_pool_import(hash),
+					None => return,
+				},
+				complete => return,
+			}
+		}
+	}
+}
+
+/// State of [`BlobMetrics::run`].
+struct Tracker<P: TransactionPool> {
+	metrics: BlobMetrics,
+	client: Arc<FullClient>,
+	pool: Arc<P>,
+	/// Submission times of the blob transactions in the pool, ready or future.
+	pending: HashMap<TxHash<P>, Instant>,
+	/// Number and blob bytes of imported, not yet finalized blocks.
+	imported: HashMap<Hash, (BlockNumber, u64)>,
+}
+
+impl<P: TransactionPool<Block = Block>> Tracker<P> {
+	fn on_pool_import(&mut self, hash: TxHash<P>) {
+		let tx = self.pool.ready_transaction(&hash);
+		if tx.is_some_and(|tx| is_blob_transaction(tx.data())) {
+			self.pending.insert(hash, Instant::now());
+			self.metrics.pool_blob_transactions.set(self.pending.len() as u64);
+		}
+	}
+
+	fn on_block_import(&mut self, notification: BlockImportNotification<Block>) {
+		let hash = notification.hash;
+		let number = *notification.header.number();
+
+		match blobs_at(&self.client, hash, number) {
+			Ok(blobs) => {
+				let bytes = blobs.iter().map(|blob| blob.len() as u64).sum::<u64>();
+				self.metrics.blobs_per_block.observe(blobs.len() as f64);
+				self.metrics.blob_bytes_per_block.observe(bytes as f64);
+				self.imported.insert(hash, (number, bytes));
+			},
+			Err(e) => log::debug!(target: LOG_TARGET, "Failed to read blobs of #{}: {}", number, e),
+		}
+
+		if !self.pending.is_empty() {
+			if let Ok(Some(body)) = self.client.block_body(hash) {
+				for extrinsic in &body {
+					if let Some(submitted) = self.pending.remove(&self.pool.hash_of(extrinsic)) {
+						self.metrics.inclusion_latency.observe(submitted.elapsed().as_secs_f64());
+					}
+				}
+			}
+		}
+
+		// Forget transactions that left the pool without being included.
+		let futures = self.track_futures();
+		let pool = &self.pool;
+		self.pending.retain(|hash, submitted| {
+			submitted.elapsed() < PENDING_TTL &&
+				(futures.contains(hash) || pool.ready_transaction(hash).is_some())
+		});
+		self.metrics.pool_blob_transactions.set(self.pending.len() as u64);
+	}
+
+	/// Starts tracking the blob transactions in the future queue of the pool, and returns the
+	/// hashes of all transactions in it.
+	fn track_futures(&mut self) -> HashSet<TxHash<P>> {
+		let futures = self.pool.futures();
+		for tx in futures.iter().filter(|tx| is_blob_transaction(tx.data())) {
+			self.pending.entry(tx.hash().clone()).or_insert_with(Instant::now);
+		}
+		futures.iter().map(|tx| tx.hash().clone()).collect()
+	}
+
+	fn on_finality(&mut self, notification: FinalityNotification<Block>) {
+		for hash in notification.tree_route.iter().chain(std::iter::once(&notification.hash)) {
+			if let Some((_, bytes)) = self.imported.remove(hash) {
+				self.metrics.finalized_blob_bytes.inc_by(bytes);
+			}
+		}
+
+		// Blocks at or below the finalized one that are still around are on dead forks.
+		let finalized = *notification.header.number();
+		self.imported.retain(|_, (number, _)| *number > finalized);
+	}
+}
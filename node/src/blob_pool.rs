@@ -0,0 +1,704 @@
+//! Transaction pool with a dedicated budget for blob transactions.
+//!
+//! Blob transactions of up to `MaxBlobSize` bytes each would otherwise compete with every other
+//! transaction for the limits of the pool itself. [`BlobLimitedPool`] tracks the blob
+//! transactions submitted to the pool it wraps, and once they exceed a count or a total byte
+//! budget, evicts the blob transactions with the lowest priority until they fit again. Other
+//! transactions are never evicted to make room for blobs.
+//!
+//! Evicted transactions are dropped from the pool without being banned, see [`EvictTransactions`],
+//! so they can be resubmitted once there is room again. The pool is only looked at once the
+//! running totals of the tracked blob transactions exceed the budget, and after each block, to
+//! forget the ones that left it.
+
+use crate::blob_proposer::{is_blob_transaction, ReadyIterator};
+use blobchain_runtime::opaque::Block;
+use codec::Encode;
+use futures::StreamExt;
+use sc_transaction_pool::{BasicPool, ChainApi, ValidatedTransaction};
+use sc_transaction_pool_api::{
+	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, InPoolTransaction,
+	LocalTransactionFor, LocalTransactionPool, MaintainedTransactionPool, PoolFuture, PoolStatus,
+	TransactionFor, TransactionPool, TransactionSource, TransactionStatus,
+	TransactionStatusStreamFor, TxHash,
+};
+use sp_runtime::{
+	traits::{Block as BlockT, NumberFor},
+	transaction_validity::TransactionPriority,
+};
+use std::{
+	collections::{HashMap, HashSet},
+	future::Future,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+};
+
+/// Log target of the blob pool.
+const LOG_TARGET: &str = "blob-pool";
+
+/// Budget of the blob transactions in the pool.
+#[derive(Debug, Clone, Copy)]
+pub struct BlobPoolLimits {
+	/// Maximum number of blob transactions.
+	pub max_count: usize,
+	/// Maximum total encoded size of blob transactions.
+	pub max_bytes: usize,
+}
+
+/// Removal of transactions from a pool without banning them.
+///
+/// [`TransactionPool::remove_invalid`] bans the removed transactions for the ban period of the
+/// pool, which is right for invalid transactions but not for valid ones evicted for room.
+pub trait EvictTransactions: TransactionPool {
+	/// Drops the transactions with hashes `hashes` from the pool, along with the transactions
+	/// depending on them, notifying their watchers.
+	fn evict(&self, hashes: &[TxHash<Self>]);
+}
+
+impl<PoolApi, B> EvictTransactions for BasicPool<PoolApi, B>
+where
+	B: BlockT,
+	PoolApi: ChainApi<Block = B> + 'static,
+{
+	fn evict(&self, hashes: &[TxHash<Self>]) {
+		// Resubmitting a transaction as unknown drops it, while `remove_invalid` would ban it. Its
+		// watchers are told it is invalid though, see `BlobLimitedPool::submit_and_watch`.
+		let dropped = hashes
+			.iter()
+			.map(|hash| {
+				let error = TxPoolError::ImmediatelyDropped.into();
+				(*hash, ValidatedTransaction::Unknown(*hash, error))
+			})
+			.collect();
+		self.pool().validated_pool().resubmit(dropped);
+	}
+}
+
+/// A blob transaction submitted to the pool.
+struct BlobTransaction {
+	/// Encoded size of the transaction.
+	len: usize,
+	/// Set once the transaction gets evicted, if it is watched.
+	evicted: Option<Arc<AtomicBool>>,
+}
+
+/// The blob transactions submitted to the pool, with their running totals.
+struct BlobTransactions<H> {
+	txs: HashMap<H, BlobTransaction>,
+	/// Total encoded size of `txs`.
+	bytes: usize,
+}
+
+impl<H> Default for BlobTransactions<H> {
+	fn default() -> Self {
+		Self { txs: HashMap::new(), bytes: 0 }
+	}
+}
+
+impl<H: std::hash::Hash + Eq> BlobTransactions<H> {
+	fn insert(&mut self, hash: H, tx: BlobTransaction) {
+		self.bytes += tx.len;
+		if let Some(replaced) = self.txs.insert(hash, tx) {
+			self.bytes -= replaced.len;
+		}
+	}
+
+	fn remove(&mut self, hash: &H) -> Option<BlobTransaction> {
+		let tx = self.txs.remove(hash)?;
+		self.bytes -= tx.len;
+		Some(tx)
+	}
+
+	/// Forgets the transactions `keep` returns `false` for.
+	fn retain(&mut self, keep: impl Fn(&H) -> bool) {
+		let bytes = &mut self.bytes;
+		self.txs.retain(|hash, tx| {
+			let kept = keep(hash);
+			if !kept {
+				*bytes -= tx.len;
+			}
+			kept
+		});
+	}
+
+	fn fits(&self, limits: BlobPoolLimits) -> bool {
+		self.txs.len() <= limits.max_count && self.bytes <= limits.max_bytes
+	}
+}
+
+/// Transaction pool enforcing [`BlobPoolLimits`] on top of the limits of the wrapped pool.
+pub struct BlobLimitedPool<P: TransactionPool> {
+	inner: Arc<P>,
+	limits: BlobPoolLimits,
+	blobs: Arc<Mutex<BlobTransactions<TxHash<P>>>>,
+}
+
+impl<P: EvictTransactions<Block = Block> + 'static> BlobLimitedPool<P> {
+	/// Creates a new [`Self`] enforcing `limits` on the blob transactions of `inner`.
+	pub fn new(inner: Arc<P>, limits: BlobPoolLimits) -> Self {
+		Self { inner, limits, blobs: Default::default() }
+	}
+
+	/// Returns a closure recording which of `xts` are blob transactions once they are in the
+	/// pool, and enforcing the limits.
+	///
+	/// The closure takes the hash of each of `xts` in order, `None` for the ones the pool
+	/// rejected, and returns the hashes of the transactions evicted from the pool. `evicted` is
+	/// set if the first of `xts` gets evicted later on.
+	fn limiter(
+		&self,
+		xts: &[TransactionFor<P>],
+		evicted: Option<Arc<AtomicBool>>,
+	) -> impl FnOnce(&[Option<TxHash<P>>]) -> HashSet<TxHash<P>> + Send + 'static {
+		let inner = self.inner.clone();
+		let limits = self.limits;
+		let blobs = self.blobs.clone();
+		let lengths = xts
+			.iter()
+			.map(|xt| is_blob_transaction(xt).then(|| xt.encoded_size()))
+			.collect::<Vec<_>>();
+
+		move |hashes| {
+			let mut evicted = evicted;
+			let submitted = hashes
+				.iter()
+				.zip(lengths)
+				.filter_map(|(hash, len)| Some((hash.clone()?, len?, evicted.take())))
+				.collect::<Vec<_>>();
+			if submitted.is_empty() {
+				return HashSet::new();
+			}
+
+			let mut blobs = blobs.lock().expect("Blob pool lock poisoned");
+			let hashes = submitted.iter().map(|(hash, ..)| hash.clone()).collect::<HashSet<_>>();
+			for (hash, len, evicted) in submitted {
+				blobs.insert(hash, BlobTransaction { len, evicted });
+			}
+			enforce_limits(&*inner, limits, &mut blobs, &hashes)
+		}
+	}
+}
+
+/// Returns the priorities of the transactions of `blobs` that are still in `pool`.
+///
+/// Ready transactions are looked up one by one, while the future ones have to be listed all at
+/// once, which is cheap as the future queue of the pool is small.
+fn priorities<P: TransactionPool>(
+	pool: &P,
+	blobs: &BlobTransactions<TxHash<P>>,
+) -> HashMap<TxHash<P>, TransactionPriority> {
+	let mut priorities = blobs
+		.txs
+		.keys()
+		.filter_map(|hash| Some((hash.clone(), *pool.ready_transaction(hash)?.priority())))
+		.collect::<HashMap<_, _>>();
+	if priorities.len() < blobs.txs.len() {
+		let future = pool.futures().into_iter().filter(|tx| blobs.txs.contains_key(tx.hash()));
+		priorities.extend(future.map(|tx| (tx.hash().clone(), *tx.priority())));
+	}
+	priorities
+}
+
+/// Forgets the blob transactions that left `pool`, e.g. by getting included in a block.
+fn prune<P: TransactionPool>(pool: &P, blobs: &mut BlobTransactions<TxHash<P>>) {
+	let in_pool = priorities(pool, blobs);
+	blobs.retain(|hash| in_pool.contains_key(hash));
+}
+
+/// Evicts blob transactions from `pool` until the ones still in it fit `limits`.
+///
+/// Only looks at the pool once `blobs` exceed the limits. Transactions with the lowest priority
+/// go first, and among those the `submitted` ones, so that a new transaction doesn't replace an
+/// equally important one. Returns the evicted hashes.
+fn enforce_limits<P: EvictTransactions>(
+	pool: &P,
+	limits: BlobPoolLimits,
+	blobs: &mut BlobTransactions<TxHash<P>>,
+	submitted: &HashSet<TxHash<P>>,
+) -> HashSet<TxHash<P>> {
+	if blobs.fits(limits) {
+		return HashSet::new();
+	}
+
+	// Forget blob transactions that left the pool, they may be all that is over the limits.
+	let priorities = priorities(pool, blobs);
+	blobs.retain(|hash| priorities.contains_key(hash));
+
+	let mut in_pool = priorities.into_iter().collect::<Vec<_>>();
+	in_pool.sort_by_key(|(hash, priority)| (*priority, !submitted.contains(hash)));
+	let mut evicted = HashSet::new();
+	for (hash, _) in in_pool {
+		if blobs.fits(limits) {
+			break;
+		}
+		if let Some(BlobTransaction { evicted: Some(flag), .. }) = blobs.remove(&hash) {
+			flag.store(true, Ordering::Relaxed);
+		}
+		evicted.insert(hash);
+	}
+
+	if !evicted.is_empty() {
+		log::debug!(target: LOG_TARGET, "Evicting {} blob transactions", evicted.len());
+		pool.evict(&evicted.iter().cloned().collect::<Vec<_>>());
+	}
+	evicted
+}
+
+impl<P> TransactionPool for BlobLimitedPool<P>
+where
+	P: EvictTransactions<Block = Block> + 'static,
+{
+	type Block = P::Block;
+	type Hash = P::Hash;
+	type InPoolTransaction = P::InPoolTransaction;
+	type Error = P::Error;
+
+	fn submit_at(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		let limiter = self.limiter(&xts, None);
+		let submit = self.inner.submit_at(at, source, xts);
+
+		Box::pin(async move {
+			let results = submit.await?;
+			let hashes = results.iter().map(|result| result.as_ref().ok().cloned());
+			let evicted = limiter(&hashes.collect::<Vec<_>>());
+
+			Ok(results
+				.into_iter()
+				.map(|result| match result {
+					Ok(hash) if evicted.contains(&hash) => {
+						Err(TxPoolError::ImmediatelyDropped.into())
+					},
+					result => result,
+				})
+				.collect())
+		})
+	}
+
+	fn submit_one(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		let limiter = self.limiter(std::slice::from_ref(&xt), None);
+		let submit = self.inner.submit_one(at, source, xt);
+
+		Box::pin(async move {
+			let hash = submit.await?;
+			if limiter(&[Some(hash.clone())]).contains(&hash) {
+				return Err(TxPoolError::ImmediatelyDropped.into());
+			}
+			Ok(hash)
+		})
+	}
+
+	fn submit_and_watch(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		let evicted = Arc::new(AtomicBool::new(false));
+		let limiter = self.limiter(std::slice::from_ref(&xt), Some(evicted.clone()));
+		let hash = self.inner.hash_of(&xt);
+		let submit = self.inner.submit_and_watch(at, source, xt);
+
+		Box::pin(async move {
+			let watcher = submit.await?;
+			if limiter(&[Some(hash.clone())]).contains(&hash) {
+				return Err(TxPoolError::ImmediatelyDropped.into());
+			}
+			// The wrapped pool reports evicted transactions as invalid.
+			let watcher = watcher.map(move |status| match status {
+				TransactionStatus::Invalid if evicted.load(Ordering::Relaxed) => {
+					TransactionStatus::Dropped
+				},
+				status => status,
+			});
+			Ok(Box::pin(watcher) as Pin<Box<TransactionStatusStreamFor<Self>>>)
+		})
+	}
+
+	fn ready_at(
+		&self,
+		at: NumberFor<Self::Block>,
+	) -> Pin<Box<dyn Future<Output = ReadyIterator<Self>> + Send>> {
+		self.inner.ready_at(at)
+	}
+
+	fn ready(&self) -> ReadyIterator<Self> {
+		self.inner.ready()
+	}
+
+	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		{
+			let mut blobs = self.blobs.lock().expect("Blob pool lock poisoned");
+			for hash in hashes {
+				blobs.remove(hash);
+			}
+		}
+		self.inner.remove_invalid(hashes)
+	}
+
+	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+		self.inner.futures()
+	}
+
+	fn status(&self) -> PoolStatus {
+		self.inner.status()
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		self.inner.import_notification_stream()
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		self.inner.on_broadcasted(propagations)
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		self.inner.hash_of(xt)
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		self.inner.ready_transaction(hash)
+	}
+}
+
+impl<P> MaintainedTransactionPool for BlobLimitedPool<P>
+where
+	P: MaintainedTransactionPool<Block = Block> + EvictTransactions + 'static,
+{
+	fn maintain(&self, event: ChainEvent<Self::Block>) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+		let inner = self.inner.clone();
+		let blobs = self.blobs.clone();
+		Box::pin(async move {
+			inner.maintain(event).await;
+			prune(&*inner, &mut blobs.lock().expect("Blob pool lock poisoned"));
+		})
+	}
+}
+
+impl<P> LocalTransactionPool for BlobLimitedPool<P>
+where
+	P: EvictTransactions<Block = Block>
+		+ LocalTransactionPool<Block = Block, Hash = TxHash<P>, Error = <P as TransactionPool>::Error>
+		+ 'static,
+{
+	type Block = Block;
+	type Hash = TxHash<P>;
+	type Error = <P as TransactionPool>::Error;
+
+	fn submit_local(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		xt: LocalTransactionFor<Self>,
+	) -> Result<Self::Hash, Self::Error> {
+		let limiter = self.limiter(std::slice::from_ref(&xt), None);
+		let hash = self.inner.submit_local(at, xt)?;
+		if limiter(&[Some(hash.clone())]).contains(&hash) {
+			return Err(TxPoolError::ImmediatelyDropped.into());
+		}
+		Ok(hash)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use blobchain_runtime as runtime;
+	use futures::{channel::mpsc, executor::block_on};
+	use sc_transaction_pool_api::{ReadyTransactions, TransactionStatusFor};
+	use sp_core::H256;
+	use sp_runtime::{
+		traits::{BlakeTwo256, Hash},
+		transaction_validity::{TransactionLongevity, TransactionTag},
+		OpaqueExtrinsic,
+	};
+
+	struct Tx {
+		hash: H256,
+		data: OpaqueExtrinsic,
+		priority: TransactionPriority,
+		ready: bool,
+	}
+
+	impl InPoolTransaction for Tx {
+		type Transaction = OpaqueExtrinsic;
+		type Hash = H256;
+
+		fn data(&self) -> &OpaqueExtrinsic {
+			&self.data
+		}
+
+		fn hash(&self) -> &H256 {
+			&self.hash
+		}
+
+		fn priority(&self) -> &TransactionPriority {
+			&self.priority
+		}
+
+		fn longevity(&self) -> &TransactionLongevity {
+			&TransactionLongevity::MAX
+		}
+
+		fn requires(&self) -> &[TransactionTag] {
+			&[]
+		}
+
+		fn provides(&self) -> &[TransactionTag] {
+			&[]
+		}
+
+		fn is_propagable(&self) -> bool {
+			true
+		}
+	}
+
+	struct Ready(std::vec::IntoIter<Arc<Tx>>);
+
+	impl Iterator for Ready {
+		type Item = Arc<Tx>;
+
+		fn next(&mut self) -> Option<Arc<Tx>> {
+			self.0.next()
+		}
+	}
+
+	impl ReadyTransactions for Ready {
+		fn report_invalid(&mut self, _tx: &Arc<Tx>) {}
+	}
+
+	/// Pool accepting every transaction, with the priority and readiness set for the next ones.
+	#[derive(Default)]
+	struct TestPool {
+		txs: Mutex<Vec<Arc<Tx>>>,
+		next: Mutex<(TransactionPriority, bool)>,
+		watchers: Mutex<HashMap<H256, mpsc::UnboundedSender<TransactionStatusFor<Self>>>>,
+		evicted: Mutex<Vec<H256>>,
+	}
+
+	impl TestPool {
+		fn set_next(&self, priority: TransactionPriority, ready: bool) {
+			*self.next.lock().unwrap() = (priority, ready);
+		}
+
+		fn import(&self, xt: OpaqueExtrinsic) -> H256 {
+			let hash = self.hash_of(&xt);
+			let (priority, ready) = *self.next.lock().unwrap();
+			self.txs.lock().unwrap().push(Arc::new(Tx { hash, data: xt, priority, ready }));
+			hash
+		}
+
+		fn remove(&self, hash: &H256) {
+			self.txs.lock().unwrap().retain(|tx| tx.hash != *hash);
+		}
+	}
+
+	impl TransactionPool for TestPool {
+		type Block = Block;
+		type Hash = H256;
+		type InPoolTransaction = Tx;
+		type Error = TxPoolError;
+
+		fn submit_at(
+			&self,
+			_at: H256,
+			_source: TransactionSource,
+			xts: Vec<OpaqueExtrinsic>,
+		) -> PoolFuture<Vec<Result<H256, TxPoolError>>, TxPoolError> {
+			let hashes = xts.into_iter().map(|xt| Ok(self.import(xt))).collect();
+			Box::pin(futures::future::ready(Ok(hashes)))
+		}
+
+		fn submit_one(
+			&self,
+			_at: H256,
+			_source: TransactionSource,
+			xt: OpaqueExtrinsic,
+		) -> PoolFuture<H256, TxPoolError> {
+			Box::pin(futures::future::ready(Ok(self.import(xt))))
+		}
+
+		fn submit_and_watch(
+			&self,
+			_at: H256,
+			_source: TransactionSource,
+			xt: OpaqueExtrinsic,
+		) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, TxPoolError> {
+			let hash = self.import(xt);
+			let (sender, receiver) = mpsc::unbounded();
+			sender.unbounded_send(TransactionStatus::Ready).unwrap();
+			self.watchers.lock().unwrap().insert(hash, sender);
+			Box::pin(futures::future::ready(Ok(
+				Box::pin(receiver) as Pin<Box<TransactionStatusStreamFor<Self>>>
+			)))
+		}
+
+		fn ready_at(
+			&self,
+			_at: NumberFor<Block>,
+		) -> Pin<Box<dyn Future<Output = ReadyIterator<Self>> + Send>> {
+			unimplemented!()
+		}
+
+		fn ready(&self) -> ReadyIterator<Self> {
+			let txs = self.txs.lock().unwrap();
+			let ready = txs.iter().filter(|tx| tx.ready).cloned().collect::<Vec<_>>();
+			Box::new(Ready(ready.into_iter()))
+		}
+
+		fn remove_invalid(&self, hashes: &[H256]) -> Vec<Arc<Tx>> {
+			hashes.iter().for_each(|hash| self.remove(hash));
+			Vec::new()
+		}
+
+		fn futures(&self) -> Vec<Tx> {
+			let txs = self.txs.lock().unwrap();
+			txs.iter()
+				.filter(|tx| !tx.ready)
+				.map(|tx| Tx { hash: tx.hash, data: tx.data.clone(), ..**tx })
+				.collect()
+		}
+
+		fn status(&self) -> PoolStatus {
+			unimplemented!()
+		}
+
+		fn import_notification_stream(&self) -> ImportNotificationStream<H256> {
+			unimplemented!()
+		}
+
+		fn on_broadcasted(&self, _propagations: HashMap<H256, Vec<String>>) {}
+
+		fn hash_of(&self, xt: &OpaqueExtrinsic) -> H256 {
+			BlakeTwo256::hash_of(xt)
+		}
+
+		fn ready_transaction(&self, hash: &H256) -> Option<Arc<Tx>> {
+			self.txs.lock().unwrap().iter().find(|tx| tx.ready && tx.hash == *hash).cloned()
+		}
+	}
+
+	impl EvictTransactions for TestPool {
+		fn evict(&self, hashes: &[H256]) {
+			for hash in hashes {
+				self.remove(hash);
+				self.evicted.lock().unwrap().push(*hash);
+				// Like `ValidatedPool::resubmit` does for transactions resubmitted as unknown.
+				if let Some(watcher) = self.watchers.lock().unwrap().remove(hash) {
+					watcher.unbounded_send(TransactionStatus::Invalid).unwrap();
+				}
+			}
+		}
+	}
+
+	fn xt(call: impl Into<runtime::RuntimeCall>) -> OpaqueExtrinsic {
+		let xt = runtime::UncheckedExtrinsic::new_unsigned(call.into());
+		OpaqueExtrinsic::from_bytes(&xt.encode()).unwrap()
+	}
+
+	fn blob(byte: u8, len: usize) -> OpaqueExtrinsic {
+		xt(pallet_blobmanager::Call::<runtime::Runtime>::upload_blob { blob: vec![byte; len] })
+	}
+
+	fn remark(byte: u8) -> OpaqueExtrinsic {
+		xt(frame_system::Call::<runtime::Runtime>::remark { remark: vec![byte] })
+	}
+
+	fn pool(max_count: usize, max_bytes: usize) -> (Arc<TestPool>, BlobLimitedPool<TestPool>) {
+		let inner = Arc::new(TestPool::default());
+		(inner.clone(), BlobLimitedPool::new(inner, BlobPoolLimits { max_count, max_bytes }))
+	}
+
+	fn submit(pool: &BlobLimitedPool<TestPool>, xt: OpaqueExtrinsic) -> Result<H256, TxPoolError> {
+		block_on(pool.submit_one(H256::zero(), TransactionSource::External, xt))
+	}
+
+	#[test]
+	fn evicts_blob_transactions_with_the_lowest_priority_first() {
+		let (inner, pool) = pool(2, usize::MAX);
+		inner.set_next(10, true);
+		let high = submit(&pool, blob(1, 32)).unwrap();
+		inner.set_next(5, true);
+		let low = submit(&pool, blob(2, 32)).unwrap();
+		// Other transactions are neither counted nor evicted.
+		inner.set_next(1, true);
+		submit(&pool, remark(3)).unwrap();
+		inner.set_next(7, false);
+		let future = submit(&pool, blob(4, 32)).unwrap();
+
+		assert_eq!(*inner.evicted.lock().unwrap(), vec![low]);
+		let in_pool = inner.txs.lock().unwrap().iter().map(|tx| tx.hash).collect::<Vec<_>>();
+		assert_eq!(in_pool.len(), 3);
+		assert!(in_pool.contains(&high) && in_pool.contains(&future));
+	}
+
+	#[test]
+	fn enforces_the_byte_budget() {
+		let len = blob(0, 100).encoded_size();
+		let (inner, pool) = pool(usize::MAX, 2 * len);
+		inner.set_next(3, true);
+		let first = submit(&pool, blob(1, 100)).unwrap();
+		inner.set_next(1, true);
+		let second = submit(&pool, blob(2, 100)).unwrap();
+		assert!(inner.evicted.lock().unwrap().is_empty());
+
+		inner.set_next(2, true);
+		submit(&pool, blob(3, 100)).unwrap();
+		assert_eq!(*inner.evicted.lock().unwrap(), vec![second]);
+		assert!(inner.ready_transaction(&first).is_some());
+	}
+
+	#[test]
+	fn forgets_blob_transactions_that_left_the_pool() {
+		let (inner, pool) = pool(2, usize::MAX);
+		let included = submit(&pool, blob(1, 32)).unwrap();
+		submit(&pool, blob(2, 32)).unwrap();
+		inner.remove(&included);
+
+		submit(&pool, blob(3, 32)).unwrap();
+		assert!(inner.evicted.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn submit_at_reports_evicted_submissions_as_dropped() {
+		let (inner, pool) = pool(1, usize::MAX);
+		inner.set_next(5, true);
+		let kept = submit(&pool, blob(1, 32)).unwrap();
+
+		// Among equal priorities, the submitted transaction goes first.
+		let results = block_on(pool.submit_at(
+			H256::zero(),
+			TransactionSource::External,
+			vec![blob(2, 32), remark(3)],
+		))
+		.unwrap();
+		assert!(matches!(results[0], Err(TxPoolError::ImmediatelyDropped)));
+		assert!(results[1].is_ok());
+		assert!(inner.ready_transaction(&kept).is_some());
+	}
+
+	#[test]
+	fn watchers_see_evicted_transactions_dropped() {
+		let (inner, pool) = pool(1, usize::MAX);
+		inner.set_next(1, true);
+		let watcher =
+			block_on(pool.submit_and_watch(H256::zero(), TransactionSource::External, blob(1, 32)))
+				.unwrap();
+		inner.set_next(2, true);
+		submit(&pool, blob(2, 32)).unwrap();
+
+		let statuses = block_on(watcher.take(2).collect::<Vec<_>>());
+		assert_eq!(statuses, vec![TransactionStatus::Ready, TransactionStatus::Dropped]);
+	}
+}
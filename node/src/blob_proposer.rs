@@ -0,0 +1,425 @@
+//! Transaction pool view for block authoring that packs blob transactions.
+//!
+//! `sc_basic_authorship` fills blocks greedily in the order of [`TransactionPool::ready_at`], so
+//! large blob transactions competing with small ones can strand block length, or get crowded
+//! out entirely. [`BlobPackingPool`] wraps the pool handed to the proposer and reorders the
+//! ready transactions: a share of the block length is reserved for blob transactions, which are
+//! packed into it within the live `max_blobs_per_block` limit, while other transactions fill the
+//! rest of the block.
+
+use crate::{blob_store::limits_at, service::FullClient};
+use blobchain_runtime::{self as runtime, opaque::Block};
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use pallet_blobmanager::{uploaded_blobs, BlobLimits, DefaultLimits};
+use sc_transaction_pool_api::{
+	ImportNotificationStream, InPoolTransaction, PoolFuture, PoolStatus, ReadyTransactions,
+	TransactionFor, TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	traits::{Block as BlockT, NumberFor},
+	transaction_validity::TransactionTag,
+	OpaqueExtrinsic, Percent,
+};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	future::Future,
+	pin::Pin,
+	sync::Arc,
+};
+
+/// Log target of the blob proposer.
+const LOG_TARGET: &str = "blob-proposer";
+
+/// Iterator over the ready transactions of pool `P`.
+pub type ReadyIterator<P> =
+	Box<dyn ReadyTransactions<Item = Arc<<P as TransactionPool>::InPoolTransaction>> + Send>;
+
+/// Returns whether `extrinsic` uploads blobs.
+pub fn is_blob_transaction(extrinsic: &OpaqueExtrinsic) -> bool {
+	blob_count(extrinsic) > 0
+}
+
+/// Returns the number of blobs `extrinsic` uploads, including in wrapper calls like
+/// `Utility::batch`.
+pub fn blob_count(extrinsic: &OpaqueExtrinsic) -> usize {
+	extrinsic
+		.using_encoded(|mut encoded| runtime::UncheckedExtrinsic::decode(&mut encoded))
+		.map(|extrinsic| uploaded_blobs::<runtime::Runtime>(&extrinsic.function).len())
+		.unwrap_or_default()
+}
+
+/// Returns the block length available to normal transactions.
+fn normal_block_length() -> usize {
+	*runtime::BlockLength::get().max.get(DispatchClass::Normal) as usize
+}
+
+/// Transaction pool whose ready transactions are ordered for blob packing.
+///
+/// Everything but [`TransactionPool::ready_at`] and [`TransactionPool::ready`] is forwarded to
+/// the wrapped pool.
+pub struct BlobPackingPool<P> {
+	inner: Arc<P>,
+	client: Arc<FullClient>,
+	reservation: Percent,
+}
+
+impl<P> BlobPackingPool<P> {
+	/// Creates a new [`Self`] reserving `reservation` of the normal block length for the blob
+	/// transactions of `inner`, within the blob limits read from `client`.
+	pub fn new(inner: Arc<P>, client: Arc<FullClient>, reservation: Percent) -> Self {
+		Self { inner, client, reservation }
+	}
+
+	/// Reads the blob limits at the best block, which blocks are authored on, falling back to the
+	/// ceilings.
+	///
+	/// The pool only tells the number of the parent block, and the canonical block with that
+	/// number may be on another fork than the best block.
+	fn limits(&self) -> BlobLimits {
+		let best = self.client.info().best_hash;
+		limits_at(&self.client, best).unwrap_or_else(|e| {
+			log::warn!(target: LOG_TARGET, "Failed to read blob limits at {:?}: {}", best, e);
+			DefaultLimits::<runtime::Runtime>::get()
+		})
+	}
+}
+
+/// Ready transactions of a pool, ordered for a block.
+///
+/// Blob transactions are kept up to the reserved block length and other transactions up to the
+/// rest, both in priority order. Transactions that don't fit go last, so they only get included
+/// if the other kind leaves space. Blob transactions beyond `max_blobs_per_block` are left out,
+/// and a transaction depending on one that goes last or is left out is treated the same way.
+///
+/// Transactions are pulled from the pool, and decoded, one at a time, and only until the block
+/// length is used up.
+struct Packed<T> {
+	inner: Box<dyn ReadyTransactions<Item = Arc<T>> + Send>,
+	max_blobs: usize,
+	blob_budget: usize,
+	other_budget: usize,
+	blobs: usize,
+	blob_length: usize,
+	other_length: usize,
+	last: VecDeque<Arc<T>>,
+	left_out: HashSet<TransactionTag>,
+	deferred: HashSet<TransactionTag>,
+}
+
+impl<T> Packed<T>
+where
+	T: InPoolTransaction<Transaction = OpaqueExtrinsic>,
+{
+	/// Creates a new [`Self`] splitting `block_length` between blob and other transactions.
+	fn new(
+		inner: Box<dyn ReadyTransactions<Item = Arc<T>> + Send>,
+		block_length: usize,
+		reservation: Percent,
+		limits: BlobLimits,
+	) -> Self {
+		let blob_budget = reservation.mul_floor(block_length);
+		Self {
+			inner,
+			max_blobs: limits.max_blobs_per_block as usize,
+			blob_budget,
+			other_budget: block_length - blob_budget,
+			blobs: 0,
+			blob_length: 0,
+			other_length: 0,
+			last: VecDeque::new(),
+			left_out: HashSet::new(),
+			deferred: HashSet::new(),
+		}
+	}
+
+	/// Returns whether the block length is used up, so no further transaction is pulled.
+	fn is_full(&self) -> bool {
+		self.blob_length + self.other_length >= self.blob_budget + self.other_budget
+	}
+
+	/// Returns `tx` if it goes into the block now, or puts it last or leaves it out.
+	fn admit(&mut self, tx: Arc<T>) -> Option<Arc<T>> {
+		let requires =
+			|tags: &HashSet<TransactionTag>| tx.requires().iter().any(|t| tags.contains(t));
+		if requires(&self.left_out) {
+			self.left_out.extend(tx.provides().iter().cloned());
+			return None
+		}
+
+		let len = tx.data().encoded_size();
+		let count = blob_count(tx.data());
+		let requires_deferred = requires(&self.deferred);
+		if count > 0 {
+			if self.blobs + count > self.max_blobs {
+				self.left_out.extend(tx.provides().iter().cloned());
+				return None
+			}
+			self.blobs += count;
+			if !requires_deferred && self.blob_length + len <= self.blob_budget {
+				self.blob_length += len;
+				return Some(tx)
+			}
+		} else if !requires_deferred && self.other_length + len <= self.other_budget {
+			self.other_length += len;
+			return Some(tx)
+		}
+
+		self.deferred.extend(tx.provides().iter().cloned());
+		self.last.push_back(tx);
+		None
+	}
+}
+
+impl<T> Iterator for Packed<T>
+where
+	T: InPoolTransaction<Transaction = OpaqueExtrinsic>,
+{
+	type Item = Arc<T>;
+
+	fn next(&mut self) -> Option<Arc<T>> {
+		while !self.is_full() {
+			let Some(tx) = self.inner.next() else { break };
+			if let Some(tx) = self.admit(tx) {
+				return Some(tx)
+			}
+		}
+
+		while let Some(tx) = self.last.pop_front() {
+			if !tx.requires().iter().any(|tag| self.left_out.contains(tag)) {
+				return Some(tx)
+			}
+			self.left_out.extend(tx.provides().iter().cloned());
+		}
+
+		None
+	}
+}
+
+impl<T> ReadyTransactions for Packed<T>
+where
+	T: InPoolTransaction<Transaction = OpaqueExtrinsic>,
+{
+	fn report_invalid(&mut self, tx: &Arc<T>) {
+		self.left_out.extend(tx.provides().iter().cloned());
+		self.inner.report_invalid(tx);
+	}
+}
+
+impl<P> TransactionPool for BlobPackingPool<P>
+where
+	P: TransactionPool<Block = Block>,
+	P::InPoolTransaction: Send + Sync + 'static,
+{
+	type Block = P::Block;
+	type Hash = P::Hash;
+	type InPoolTransaction = P::InPoolTransaction;
+	type Error = P::Error;
+
+	fn submit_at(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		self.inner.submit_at(at, source, xts)
+	}
+
+	fn submit_one(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		self.inner.submit_one(at, source, xt)
+	}
+
+	fn submit_and_watch(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.inner.submit_and_watch(at, source, xt)
+	}
+
+	fn ready_at(
+		&self,
+		at: NumberFor<Self::Block>,
+	) -> Pin<Box<dyn Future<Output = ReadyIterator<Self>> + Send>> {
+		let ready = self.inner.ready_at(at);
+		let reservation = self.reservation;
+		let limits = self.limits();
+		Box::pin(async move {
+			Box::new(Packed::new(ready.await, normal_block_length(), reservation, limits))
+				as ReadyIterator<Self>
+		})
+	}
+
+	fn ready(&self) -> ReadyIterator<Self> {
+		let limits = self.limits();
+		Box::new(Packed::new(self.inner.ready(), normal_block_length(), self.reservation, limits))
+	}
+
+	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		self.inner.remove_invalid(hashes)
+	}
+
+	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+		self.inner.futures()
+	}
+
+	fn status(&self) -> PoolStatus {
+		self.inner.status()
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		self.inner.import_notification_stream()
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		self.inner.on_broadcasted(propagations)
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		self.inner.hash_of(xt)
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		self.inner.ready_transaction(hash)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+	use sp_runtime::transaction_validity::{TransactionLongevity, TransactionPriority};
+
+	struct Tx {
+		data: OpaqueExtrinsic,
+		hash: H256,
+		requires: Vec<TransactionTag>,
+		provides: Vec<TransactionTag>,
+	}
+
+	impl InPoolTransaction for Tx {
+		type Transaction = OpaqueExtrinsic;
+		type Hash = H256;
+
+		fn data(&self) -> &OpaqueExtrinsic {
+			&self.data
+		}
+
+		fn hash(&self) -> &H256 {
+			&self.hash
+		}
+
+		fn priority(&self) -> &TransactionPriority {
+			&0
+		}
+
+		fn longevity(&self) -> &TransactionLongevity {
+			&TransactionLongevity::MAX
+		}
+
+		fn requires(&self) -> &[TransactionTag] {
+			&self.requires
+		}
+
+		fn provides(&self) -> &[TransactionTag] {
+			&self.provides
+		}
+
+		fn is_propagable(&self) -> bool {
+			true
+		}
+	}
+
+	struct Ready(std::vec::IntoIter<Arc<Tx>>);
+
+	impl Iterator for Ready {
+		type Item = Arc<Tx>;
+
+		fn next(&mut self) -> Option<Arc<Tx>> {
+			self.0.next()
+		}
+	}
+
+	impl ReadyTransactions for Ready {
+		fn report_invalid(&mut self, _tx: &Arc<Tx>) {}
+	}
+
+	/// Block length of the tests, half of it reserved for blob transactions.
+	const BLOCK_LENGTH: usize = 1000;
+
+	fn tx(id: u8, call: impl Into<runtime::RuntimeCall>, requires: &[u8]) -> Arc<Tx> {
+		let xt = runtime::UncheckedExtrinsic::new_unsigned(call.into());
+		Arc::new(Tx {
+			data: OpaqueExtrinsic::from_bytes(&xt.encode()).unwrap(),
+			hash: H256::repeat_byte(id),
+			requires: requires.iter().map(|id| vec![*id]).collect(),
+			provides: vec![vec![id]],
+		})
+	}
+
+	fn blob(id: u8, len: usize, requires: &[u8]) -> Arc<Tx> {
+		let blob = vec![id; len];
+		tx(id, pallet_blobmanager::Call::<runtime::Runtime>::upload_blob { blob }, requires)
+	}
+
+	fn remark(id: u8, len: usize, requires: &[u8]) -> Arc<Tx> {
+		let remark = vec![id; len];
+		tx(id, frame_system::Call::<runtime::Runtime>::remark { remark }, requires)
+	}
+
+	fn pack(ready: Vec<Arc<Tx>>, max_blobs: u32) -> Vec<u8> {
+		let limits = BlobLimits {
+			max_blobs_per_block: max_blobs,
+			..DefaultLimits::<runtime::Runtime>::get()
+		};
+		let ready = Box::new(Ready(ready.into_iter()));
+		Packed::new(ready, BLOCK_LENGTH, Percent::from_percent(50), limits)
+			.map(|tx| tx.hash[0])
+			.collect()
+	}
+
+	#[test]
+	fn blob_transactions_beyond_the_reservation_go_last() {
+		let ready = vec![
+			blob(1, 200, &[]),
+			blob(2, 200, &[]),
+			blob(3, 200, &[]),
+			remark(4, 200, &[]),
+			remark(5, 200, &[]),
+		];
+		assert_eq!(pack(ready, 10), vec![1, 2, 4, 5, 3]);
+	}
+
+	#[test]
+	fn other_transactions_beyond_their_share_go_last() {
+		let ready =
+			vec![remark(1, 300, &[]), remark(2, 300, &[]), blob(3, 100, &[]), remark(4, 50, &[])];
+		assert_eq!(pack(ready, 10), vec![1, 3, 4, 2]);
+	}
+
+	#[test]
+	fn blob_transactions_beyond_max_blobs_per_block_are_left_out() {
+		let ready = vec![
+			blob(1, 10, &[]),
+			blob(2, 10, &[]),
+			blob(3, 10, &[]),
+			remark(4, 10, &[3]),
+			remark(5, 10, &[]),
+		];
+		assert_eq!(pack(ready, 2), vec![1, 2, 5]);
+	}
+
+	#[test]
+	fn transactions_depending_on_deferred_ones_are_deferred() {
+		let ready =
+			vec![remark(1, 600, &[]), remark(2, 10, &[1]), remark(3, 10, &[]), blob(4, 10, &[])];
+		assert_eq!(pack(ready, 10), vec![3, 4, 1, 2]);
+	}
+}
@@ -0,0 +1,494 @@
+//! Request-response protocol serving blobs to peers and fetching them from peers.
+//!
+//! Peers send a SCALE-encoded [`BlobRequest`] on [`PROTOCOL_NAME`] and get back a SCALE-encoded
+//! [`BlobResponse`], answered from the local [`BlobStore`], the offchain index or the state of the
+//! node, in that order. This lets a node that pruned or never synced a blob fetch it with
+//! [`BlobFetcher`] without syncing full state.
+
+use crate::{
+	blob_store::{self, BlobStore},
+	service::{FullBackend, FullClient},
+};
+use blobchain_runtime::{opaque::Block, BlockNumber, Hash, MaxBlobSize};
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use sc_client_api::Backend;
+use sc_network::{
+	request_responses::{IncomingRequest, OutgoingResponse},
+	service::traits::NetworkService,
+	IfDisconnected, NetworkBackend, PeerId,
+};
+use sc_network_sync::SyncingService;
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, Hash as _};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex, MutexGuard},
+	time::{Duration, Instant},
+};
+
+/// Offchain storage of the node, holding the offchain index.
+type OffchainDb = <FullBackend as Backend<Block>>::OffchainStorage;
+
+/// Name of the protocol.
+pub const PROTOCOL_NAME: &str = "/blobchain/blobs/1";
+
+/// Log target of the protocol.
+const LOG_TARGET: &str = "blob-protocol";
+
+/// Maximum size of an encoded request.
+const MAX_REQUEST_SIZE: u64 = 1024;
+
+/// Maximum number of bytes served by a single chunk request.
+pub const MAX_CHUNK_SIZE: u32 = 256 * 1024;
+
+/// Time after which a request is considered failed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Maximum number of requests waiting to be handled.
+const MAX_QUEUED_REQUESTS: usize = 64;
+
+/// Window over which requests of a peer are rate limited.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Maximum number of requests a peer may send per window.
+const MAX_REQUESTS_PER_WINDOW: u32 = 100;
+
+/// Maximum number of response bytes a peer may receive per window.
+const MAX_BYTES_PER_WINDOW: u64 = 32 * 1024 * 1024;
+
+/// Number of tracked peers above which expired rate limit windows are dropped.
+const MAX_TRACKED_PEERS: usize = 1024;
+
+/// Maximum number of peers asked for a blob before giving up.
+const MAX_FETCH_ATTEMPTS: usize = 5;
+
+/// Maximum number of blobs fetched from peers per rate limit window, which keeps the node well
+/// within the rate limits of the peers it asks.
+const MAX_FETCHES_PER_WINDOW: u32 = 16;
+
+/// Time for which a blob that peers didn't have isn't asked for again.
+const NOT_FOUND_TTL: Duration = Duration::from_secs(60);
+
+/// Number of blobs remembered as not found above which expired entries are dropped.
+const MAX_NOT_FOUND: usize = 1024;
+
+mod rep {
+	use sc_network::ReputationChange as Rep;
+
+	/// Reputation change for a request that couldn't be decoded.
+	pub const INVALID_REQUEST: Rep = Rep::new(-(1 << 12), "Invalid blob request");
+
+	/// Reputation change for exceeding the request rate limit.
+	pub const RATE_LIMITED: Rep = Rep::new(-(1 << 10), "Blob request rate exceeded");
+
+	/// Reputation change for a response that couldn't be decoded or doesn't match the request.
+	pub const INVALID_RESPONSE: Rep = Rep::new(-(1 << 12), "Invalid blob response");
+}
+
+/// A blob request.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub enum BlobRequest {
+	/// The blob with the given hash.
+	ByHash(Hash),
+	/// The blob stored at `index` of block `block`.
+	ByLocation { block: BlockNumber, index: u32 },
+	/// Up to [`MAX_CHUNK_SIZE`] bytes of the blob with hash `hash`, starting at `offset`.
+	Chunk { hash: Hash, offset: u32, len: u32 },
+}
+
+/// Response to a [`BlobRequest`].
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub enum BlobResponse {
+	/// The whole blob.
+	Blob(Vec<u8>),
+	/// Part of the blob, together with the length of the whole blob.
+	Chunk { total_len: u32, data: Vec<u8> },
+	/// The blob isn't available on this node.
+	NotFound,
+}
+
+/// Error handling a request.
+enum HandleError {
+	/// The request couldn't be decoded.
+	InvalidRequest(codec::Error),
+	/// The peer exceeded its rate limit.
+	RateLimited,
+	/// Reading a local source of blobs failed.
+	Read(String),
+}
+
+/// Requests and response bytes of a peer in the current rate limit window.
+struct PeerUsage {
+	window_start: Instant,
+	requests: u32,
+	bytes: u64,
+}
+
+/// Handler of incoming blob requests.
+pub struct BlobRequestHandler {
+	client: Arc<FullClient>,
+	offchain_storage: Option<OffchainDb>,
+	store: Option<Arc<BlobStore>>,
+	requests: async_channel::Receiver<IncomingRequest>,
+	peers: HashMap<PeerId, PeerUsage>,
+}
+
+impl BlobRequestHandler {
+	/// Creates a new [`Self`] serving blobs from `store`, `offchain_storage` and the state of
+	/// `client`, along with the configuration of the protocol to register with the network.
+	pub fn new<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
+		client: Arc<FullClient>,
+		offchain_storage: Option<OffchainDb>,
+		store: Option<Arc<BlobStore>>,
+	) -> (Self, N::RequestResponseProtocolConfig) {
+		let (tx, requests) = async_channel::bounded(MAX_QUEUED_REQUESTS);
+		let config = N::request_response_config(
+			PROTOCOL_NAME.into(),
+			Vec::new(),
+			MAX_REQUEST_SIZE,
+			max_response_size(),
+			REQUEST_TIMEOUT,
+			Some(tx),
+		);
+
+		(Self { client, offchain_storage, store, requests, peers: HashMap::new() }, config)
+	}
+
+	/// Answers incoming requests until the network shuts down.
+	///
+	/// Reading blobs blocks, so this should run on a blocking task.
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) =
+			self.requests.next().await
+		{
+			let response = self.handle_request(peer, &payload);
+			if pending_response.send(response).is_err() {
+				log::debug!(target: LOG_TARGET, "Request of {} canceled before responding", peer);
+			}
+		}
+	}
+
+	fn handle_request(&mut self, peer: PeerId, payload: &[u8]) -> OutgoingResponse {
+		let (result, reputation_changes) = match self.response(peer, payload) {
+			Ok(response) => (Ok(response.encode()), Vec::new()),
+			Err(e) => {
+				let reputation_change = match e {
+					HandleError::InvalidRequest(e) => {
+						log::debug!(target: LOG_TARGET, "Invalid request from {}: {}", peer, e);
+						Some(rep::INVALID_REQUEST)
+					},
+					HandleError::RateLimited => {
+						log::debug!(target: LOG_TARGET, "Rate limited requests of {}", peer);
+						Some(rep::RATE_LIMITED)
+					},
+					HandleError::Read(e) => {
+						log::warn!(target: LOG_TARGET, "Failed to read blob: {}", e);
+						None
+					},
+				};
+				(Err(()), reputation_change.into_iter().collect())
+			},
+		};
+
+		OutgoingResponse { result, reputation_changes, sent_feedback: None }
+	}
+
+	fn response(&mut self, peer: PeerId, payload: &[u8]) -> Result<BlobResponse, HandleError> {
+		let request =
+			BlobRequest::decode(&mut &payload[..]).map_err(HandleError::InvalidRequest)?;
+		self.start_request(peer)?;
+
+		let response = match request {
+			BlobRequest::ByHash(hash) => self
+				.blob_by_hash(hash)
+				.map_err(HandleError::Read)?
+				.map_or(BlobResponse::NotFound, BlobResponse::Blob),
+			BlobRequest::ByLocation { block, index } => self
+				.blob(block, index)
+				.map_err(HandleError::Read)?
+				.map_or(BlobResponse::NotFound, BlobResponse::Blob),
+			BlobRequest::Chunk { hash, offset, len } => self
+				.blob_by_hash(hash)
+				.map_err(HandleError::Read)?
+				.map_or(BlobResponse::NotFound, |blob| chunk(blob, offset, len)),
+		};
+
+		self.finish_request(peer, &response);
+		Ok(response)
+	}
+
+	/// Reads the blob with hash `hash` from the blob store, or the offchain index.
+	fn blob_by_hash(&self, hash: Hash) -> Result<Option<Vec<u8>>, String> {
+		if let Some(store) = &self.store {
+			if let Some(blob) = store.blob_by_hash(hash)? {
+				return Ok(Some(blob));
+			}
+		}
+
+		let Some(location) = self.offchain_get(&pallet_blobmanager::offchain_key_by_hash(hash))
+		else {
+			return Ok(None);
+		};
+		let (number, index) = <(BlockNumber, u32)>::decode(&mut &location[..])
+			.map_err(|e| format!("Invalid blob location: {}", e))?;
+
+		// Blobs of other forks at the same height overwrite the location in the index.
+		Ok(self
+			.offchain_get(&pallet_blobmanager::offchain_key_by_location(number, index))
+			.filter(|blob| BlakeTwo256::hash(blob) == hash))
+	}
+
+	/// Reads the blob stored at `index` of block `number` of the canonical chain from the blob
+	/// store, the state of the block, or the offchain index.
+	fn blob(&self, number: BlockNumber, index: u32) -> Result<Option<Vec<u8>>, String> {
+		if let Some(store) = &self.store {
+			if let Some(blob) = store.blob(number, index)? {
+				return Ok(Some(blob));
+			}
+		}
+
+		// The state is only read if it is still there, and always holds the canonical blob,
+		// unlike the offchain index, which also holds the blobs of other forks at the same height.
+		if let Some(hash) = self.client.hash(number).map_err(|e| e.to_string())? {
+			if let Ok(blobs) = blob_store::blobs_at(&self.client, hash, number) {
+				return Ok(blobs.into_iter().nth(index as usize));
+			}
+		}
+
+		Ok(self.offchain_get(&pallet_blobmanager::offchain_key_by_location(number, index)))
+	}
+
+	fn offchain_get(&self, key: &[u8]) -> Option<Vec<u8>> {
+		self.offchain_storage.as_ref()?.get(STORAGE_PREFIX, key)
+	}
+
+	/// Counts a request of `peer` against its rate limit.
+	fn start_request(&mut self, peer: PeerId) -> Result<(), HandleError> {
+		let now = Instant::now();
+		if self.peers.len() > MAX_TRACKED_PEERS {
+			self.peers
+				.retain(|_, usage| now.duration_since(usage.window_start) < RATE_LIMIT_WINDOW);
+		}
+
+		let usage = self.peers.entry(peer).or_insert(PeerUsage {
+			window_start: now,
+			requests: 0,
+			bytes: 0,
+		});
+		if now.duration_since(usage.window_start) >= RATE_LIMIT_WINDOW {
+			*usage = PeerUsage { window_start: now, requests: 0, bytes: 0 };
+		}
+		if usage.requests >= MAX_REQUESTS_PER_WINDOW || usage.bytes >= MAX_BYTES_PER_WINDOW {
+			return Err(HandleError::RateLimited);
+		}
+
+		usage.requests += 1;
+		Ok(())
+	}
+
+	/// Counts the bytes of `response` against the rate limit of `peer`.
+	fn finish_request(&mut self, peer: PeerId, response: &BlobResponse) {
+		if let Some(usage) = self.peers.get_mut(&peer) {
+			usage.bytes = usage.bytes.saturating_add(response.encoded_size() as u64);
+		}
+	}
+}
+
+/// Blobs fetched from peers in the current rate limit window, and blobs peers recently didn't have.
+struct FetchState {
+	window_start: Instant,
+	fetches: u32,
+	not_found: HashMap<Hash, Instant>,
+}
+
+impl FetchState {
+	fn new(now: Instant) -> Self {
+		Self { window_start: now, fetches: 0, not_found: HashMap::new() }
+	}
+
+	/// Counts a fetch of `hash` against the rate limit, returning whether peers should be asked
+	/// for it, or an error if the limit is exceeded.
+	fn start_fetch(&mut self, hash: Hash, now: Instant) -> Result<bool, String> {
+		if self.not_found.len() > MAX_NOT_FOUND {
+			self.not_found.retain(|_, at| now.duration_since(*at) < NOT_FOUND_TTL);
+		}
+		let not_found_at = self.not_found.get(&hash);
+		if not_found_at.is_some_and(|at| now.duration_since(*at) < NOT_FOUND_TTL) {
+			return Ok(false);
+		}
+
+		if now.duration_since(self.window_start) >= RATE_LIMIT_WINDOW {
+			self.window_start = now;
+			self.fetches = 0;
+		}
+		if self.fetches >= MAX_FETCHES_PER_WINDOW {
+			return Err("Too many blobs fetched from peers, try again later".into());
+		}
+
+		self.fetches += 1;
+		Ok(true)
+	}
+}
+
+/// Fetcher of blobs from peers.
+#[derive(Clone)]
+pub struct BlobFetcher {
+	network: Arc<dyn NetworkService>,
+	sync: Arc<SyncingService<Block>>,
+	state: Arc<Mutex<FetchState>>,
+}
+
+impl BlobFetcher {
+	/// Creates a new [`Self`] sending requests over `network` to the peers known to `sync`.
+	pub fn new(network: Arc<dyn NetworkService>, sync: Arc<SyncingService<Block>>) -> Self {
+		Self { network, sync, state: Arc::new(Mutex::new(FetchState::new(Instant::now()))) }
+	}
+
+	/// Fetches the blob with hash `hash` from peers.
+	///
+	/// Peers are asked one after the other, those with the highest best block first, until one
+	/// returns the blob or [`MAX_FETCH_ATTEMPTS`] of them failed to. Blobs not matching `hash`
+	/// are dropped, and the peer sending them is reported.
+	///
+	/// At most [`MAX_FETCHES_PER_WINDOW`] blobs are fetched every [`RATE_LIMIT_WINDOW`], and
+	/// blobs peers didn't have aren't asked for again for [`NOT_FOUND_TTL`].
+	pub async fn fetch_by_hash(&self, hash: Hash) -> Result<Option<Vec<u8>>, String> {
+		if !self.lock_state().start_fetch(hash, Instant::now())? {
+			return Ok(None);
+		}
+
+		let mut peers =
+			self.sync.peers_info().await.map_err(|_| "Syncing service has shut down")?;
+		peers.sort_by_key(|(_, info)| std::cmp::Reverse(info.best_number));
+		let request = BlobRequest::ByHash(hash).encode();
+
+		let mut not_found = false;
+		for (peer, _) in peers.into_iter().take(MAX_FETCH_ATTEMPTS) {
+			let response = match self
+				.network
+				.request(
+					peer,
+					PROTOCOL_NAME.into(),
+					request.clone(),
+					None,
+					IfDisconnected::ImmediateError,
+				)
+				.await
+			{
+				Ok((response, _)) => response,
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Failed to fetch blob {} from {}: {}",
+						hash,
+						peer,
+						e,
+					);
+					continue;
+				},
+			};
+
+			match BlobResponse::decode(&mut &response[..]) {
+				Ok(BlobResponse::Blob(blob)) if BlakeTwo256::hash(&blob) == hash => {
+					return Ok(Some(blob))
+				},
+				Ok(BlobResponse::NotFound) => {
+					log::trace!(target: LOG_TARGET, "Blob {} not found at {}", hash, peer);
+					not_found = true;
+				},
+				_ => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Invalid response for blob {} from {}",
+						hash,
+						peer,
+					);
+					self.network.report_peer(peer, rep::INVALID_RESPONSE);
+				},
+			}
+		}
+
+		// Peers that couldn't be reached may still have the blob.
+		if not_found {
+			self.lock_state().not_found.insert(hash, Instant::now());
+		}
+		Ok(None)
+	}
+
+	fn lock_state(&self) -> MutexGuard<'_, FetchState> {
+		self.state.lock().expect("Blob fetcher lock poisoned")
+	}
+}
+
+/// Cuts the chunk of at most [`MAX_CHUNK_SIZE`] bytes starting at `offset` out of `blob`.
+fn chunk(blob: Vec<u8>, offset: u32, len: u32) -> BlobResponse {
+	let start = (offset as usize).min(blob.len());
+	let end = start.saturating_add(len.min(MAX_CHUNK_SIZE) as usize).min(blob.len());
+	BlobResponse::Chunk { total_len: blob.len() as u32, data: blob[start..end].to_vec() }
+}
+
+/// Maximum size of an encoded response: a whole blob plus encoding overhead.
+fn max_response_size() -> u64 {
+	MaxBlobSize::get() as u64 + 1024
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn start_fetch_limits_fetches_per_window() {
+		let start = Instant::now();
+		let mut state = FetchState::new(start);
+
+		for i in 0..MAX_FETCHES_PER_WINDOW {
+			assert_eq!(state.start_fetch(Hash::repeat_byte(i as u8), start), Ok(true));
+		}
+		assert!(state.start_fetch(Hash::repeat_byte(0xff), start).is_err());
+
+		let next_window = start + RATE_LIMIT_WINDOW;
+		assert_eq!(state.start_fetch(Hash::repeat_byte(0xff), next_window), Ok(true));
+	}
+
+	#[test]
+	fn start_fetch_skips_blobs_recently_not_found() {
+		let start = Instant::now();
+		let mut state = FetchState::new(start);
+		let hash = Hash::repeat_byte(1);
+		state.not_found.insert(hash, start);
+
+		assert_eq!(state.start_fetch(hash, start + NOT_FOUND_TTL / 2), Ok(false));
+		assert_eq!(state.fetches, 0);
+		assert_eq!(state.start_fetch(hash, start + NOT_FOUND_TTL), Ok(true));
+	}
+
+	#[test]
+	fn start_fetch_drops_expired_not_found_blobs() {
+		let start = Instant::now();
+		let mut state = FetchState::new(start);
+		for i in 0..=MAX_NOT_FOUND as u32 {
+			state.not_found.insert(Hash::from_low_u64_be(i.into()), start);
+		}
+
+		assert_eq!(state.start_fetch(Hash::zero(), start + NOT_FOUND_TTL), Ok(true));
+		assert!(state.not_found.is_empty());
+	}
+
+	#[test]
+	fn chunk_is_cut_to_the_blob_and_max_chunk_size() {
+		let blob = vec![7u8; MAX_CHUNK_SIZE as usize + 10];
+		let total_len = blob.len() as u32;
+
+		assert_eq!(
+			chunk(blob.clone(), 5, u32::MAX),
+			BlobResponse::Chunk { total_len, data: vec![7; MAX_CHUNK_SIZE as usize] },
+		);
+		assert_eq!(
+			chunk(blob.clone(), MAX_CHUNK_SIZE, 100),
+			BlobResponse::Chunk { total_len, data: vec![7; 10] },
+		);
+		assert_eq!(chunk(blob, u32::MAX, 1), BlobResponse::Chunk { total_len, data: Vec::new() });
+	}
+}
@@ -0,0 +1,475 @@
+//! RPC methods for reading blobs.
+
+use crate::{blob_protocol::BlobFetcher, blob_store::BlobStore, rpc::DenyUnsafe};
+use blobchain_runtime::{self as runtime, opaque::Block, BlockNumber, Hash};
+use blobchain_verifier::Error as VerifierError;
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned, INTERNAL_ERROR_CODE},
+};
+use mmr_rpc::MmrRuntimeApi;
+use sc_client_api::{Backend, BlockBackend, ProofProvider, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+	storage::StorageKey,
+	Bytes,
+};
+use sp_runtime::traits::{BlakeTwo256, Hash as _, Header as _};
+use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
+use std::{marker::PhantomData, sync::Arc};
+
+/// A blob together with a proof of its storage.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobWithProof {
+	/// The blob.
+	pub blob: Bytes,
+	/// Compact trie proof of the `Blobs` entry of the block against the state root of its
+	/// header, verifiable with `blobchain_verifier::verify_blob_storage_proof`.
+	pub proof: Vec<Bytes>,
+}
+
+/// An upload extrinsic together with a proof of its inclusion in a block.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobInclusionProof {
+	/// Index of the extrinsic in the block.
+	pub index: u32,
+	/// The encoded extrinsic.
+	pub extrinsic: Bytes,
+	/// Trie proof of the extrinsic against the extrinsics root of the block's header,
+	/// verifiable with `blobchain_verifier::verify_blob_inclusion_proof`.
+	pub proof: Vec<Bytes>,
+}
+
+/// A blob hash together with a proof of it against the blob commitment of its block.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobCommitmentProof {
+	/// Hash of the blob.
+	pub blob_hash: Hash,
+	/// Trie proof of the blob hash against the blob root of the block, as committed to by the
+	/// MMR leaf of the block, verifiable with `blobchain_verifier::verify_blob_commitment_proof`.
+	pub proof: Vec<Bytes>,
+}
+
+/// A chunk of a blob together with a proof of it against the chunk commitment of the blob.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobChunkProof {
+	/// The chunk.
+	pub chunk: Bytes,
+	/// Trie proof of the chunk against the chunk commitment of the blob, as accepted by
+	/// `BlobChallenge::respond_with_chunk`.
+	pub proof: Vec<Bytes>,
+}
+
+/// Blob RPC methods.
+#[rpc(server)]
+pub trait BlobApi {
+	/// Returns the blob stored at `index` of block `number` of the canonical chain from the
+	/// node-side blob store, or from peers if it isn't there.
+	///
+	/// The node-side blob store is enabled with `--blob-store-path` or `--blob-store-db`.
+	/// Fetching from peers is an unsafe call, and requires the state of the block, for the hash
+	/// of the blob.
+	#[method(name = "blob_get")]
+	async fn get(&self, number: BlockNumber, index: u32) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the blob with the given hash from the node-side blob store, or from peers if it
+	/// isn't there.
+	///
+	/// The node-side blob store is enabled with `--blob-store-path` or `--blob-store-db`.
+	/// Fetching from peers is an unsafe call.
+	#[method(name = "blob_getByHash")]
+	async fn get_by_hash(&self, hash: Hash) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the blob stored at `index` of block `number` from the offchain index.
+	///
+	/// Requires the node to run with `--enable-offchain-indexing true`.
+	#[method(name = "blob_getIndexed")]
+	fn get_indexed(&self, number: BlockNumber, index: u32) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the blob with the given hash from the offchain index.
+	///
+	/// Requires the node to run with `--enable-offchain-indexing true`.
+	#[method(name = "blob_getIndexedByHash")]
+	fn get_indexed_by_hash(&self, hash: Hash) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the blob stored at `index` of block `block_hash`, together with a proof of its
+	/// storage against the state root of the block.
+	///
+	/// Requires the state of the block.
+	#[method(name = "blob_getBlobWithProof")]
+	fn get_blob_with_proof(&self, block_hash: Hash, index: u32)
+		-> RpcResult<Option<BlobWithProof>>;
+
+	/// Returns the first upload extrinsic of block `block_hash` uploading the blob with
+	/// hash `blob_hash`, together with a proof of its inclusion against the extrinsics root of
+	/// the block.
+	///
+	/// Only requires the body of the block, not its state.
+	#[method(name = "blob_getInclusionProof")]
+	fn get_inclusion_proof(
+		&self,
+		block_hash: Hash,
+		blob_hash: Hash,
+	) -> RpcResult<Option<BlobInclusionProof>>;
+
+	/// Returns the hash of the blob stored at `index` of block `number` of the canonical chain,
+	/// together with a proof of it against the blob root of the block.
+	///
+	/// The blob root is part of the MMR leaf of the block, which `mmr_generateProof` proves
+	/// against the MMR root of any later header. Requires the state of the block, and the node to
+	/// run with `--enable-offchain-indexing true` for the MMR leaf.
+	#[method(name = "blob_getCommitmentProof")]
+	fn get_commitment_proof(
+		&self,
+		number: BlockNumber,
+		index: u32,
+	) -> RpcResult<Option<BlobCommitmentProof>>;
+
+	/// Returns chunk `chunk` of the blob stored at `index` of block `number`, together with a
+	/// proof of it against the chunk commitment of the blob, answering chunk challenges.
+	///
+	/// Requires the node to run with `--enable-offchain-indexing true`.
+	#[method(name = "blob_getChunkProof")]
+	fn get_chunk_proof(
+		&self,
+		number: BlockNumber,
+		index: u32,
+		chunk: u32,
+	) -> RpcResult<Option<BlobChunkProof>>;
+}
+
+/// Implementation of [`BlobApiServer`].
+pub struct Blob<C, S, B> {
+	client: Arc<C>,
+	offchain_storage: Option<S>,
+	blob_store: Option<Arc<BlobStore>>,
+	blob_fetcher: BlobFetcher,
+	deny_unsafe: DenyUnsafe,
+	_backend: PhantomData<B>,
+}
+
+impl<C, S, B> Blob<C, S, B> {
+	/// Creates a new [`Self`] reading from `client`, `offchain_storage` and `blob_store`, and
+	/// fetching blobs missing from `blob_store` with `blob_fetcher` unless `deny_unsafe` denies
+	/// it.
+	pub fn new(
+		client: Arc<C>,
+		offchain_storage: Option<S>,
+		blob_store: Option<Arc<BlobStore>>,
+		blob_fetcher: BlobFetcher,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self {
+			client,
+			offchain_storage,
+			blob_store,
+			blob_fetcher,
+			deny_unsafe,
+			_backend: PhantomData,
+		}
+	}
+
+	/// Reads the blob with hash `hash` from the blob store, or fetches it from peers.
+	async fn blob_by_hash(&self, hash: Hash) -> RpcResult<Option<Bytes>> {
+		if let Some(store) = &self.blob_store {
+			if let Some(blob) = store.blob_by_hash(hash).map_err(internal_error)? {
+				return Ok(Some(blob.into()));
+			}
+		}
+
+		// Every fetch sends requests to peers, which rate limit them.
+		self.deny_unsafe.check_if_safe()?;
+		self.blob_fetcher
+			.fetch_by_hash(hash)
+			.await
+			.map(|blob| blob.map(Into::into))
+			.map_err(internal_error)
+	}
+}
+
+impl<C, S: OffchainStorage, B> Blob<C, S, B> {
+	fn offchain_get(&self, key: &[u8]) -> RpcResult<Option<Vec<u8>>> {
+		let storage = self
+			.offchain_storage
+			.as_ref()
+			.ok_or_else(|| internal_error("Offchain storage is not available"))?;
+		Ok(storage.get(STORAGE_PREFIX, key))
+	}
+}
+
+impl<C, S, B> Blob<C, S, B>
+where
+	C: HeaderBackend<Block> + StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	/// Reads the hashes of the blobs stored in block `number` of the canonical chain from its
+	/// state, along with the hash of the block.
+	fn blob_hashes(&self, number: BlockNumber) -> RpcResult<Option<(Hash, Vec<Hash>)>> {
+		let Some(block_hash) =
+			self.client.hash(number).map_err(|e| internal_error(e.to_string()))?
+		else {
+			return Ok(None);
+		};
+		let key =
+			StorageKey(pallet_blobmanager::BlobHashes::<runtime::Runtime>::hashed_key_for(number));
+		let hashes = self
+			.client
+			.storage(block_hash, &key)
+			.map_err(|e| internal_error(e.to_string()))?
+			.map(|data| Vec::<Hash>::decode(&mut &data.0[..]))
+			.transpose()
+			.map_err(|e| internal_error(format!("Invalid blob hashes: {}", e)))?
+			.unwrap_or_default();
+
+		Ok(Some((block_hash, hashes)))
+	}
+}
+
+#[async_trait]
+impl<C, S, B> BlobApiServer for Blob<C, S, B>
+where
+	C: HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ ProofProvider<Block>
+		+ StorageProvider<Block, B>,
+	C: ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	C::Api: MmrRuntimeApi<Block, Hash, BlockNumber>,
+	S: OffchainStorage + 'static,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	async fn get(&self, number: BlockNumber, index: u32) -> RpcResult<Option<Bytes>> {
+		if let Some(store) = &self.blob_store {
+			if let Some(blob) = store.blob(number, index).map_err(internal_error)? {
+				return Ok(Some(blob.into()));
+			}
+		}
+
+		// Peers are asked by hash, which also makes sure they return the right blob.
+		let Some((_, hashes)) = self.blob_hashes(number)? else {
+			return Ok(None);
+		};
+		match hashes.get(index as usize) {
+			Some(&hash) => self.blob_by_hash(hash).await,
+			None => Ok(None),
+		}
+	}
+
+	async fn get_by_hash(&self, hash: Hash) -> RpcResult<Option<Bytes>> {
+		self.blob_by_hash(hash).await
+	}
+
+	fn get_indexed(&self, number: BlockNumber, index: u32) -> RpcResult<Option<Bytes>> {
+		self.offchain_get(&pallet_blobmanager::offchain_key_by_location(number, index))
+			.map(|blob| blob.map(Into::into))
+	}
+
+	fn get_indexed_by_hash(&self, hash: Hash) -> RpcResult<Option<Bytes>> {
+		let Some(location) = self.offchain_get(&pallet_blobmanager::offchain_key_by_hash(hash))?
+		else {
+			return Ok(None);
+		};
+		let (number, index) = <(BlockNumber, u32)>::decode(&mut &location[..])
+			.map_err(|e| internal_error(format!("Invalid blob location: {}", e)))?;
+
+		self.get_indexed(number, index)
+	}
+
+	fn get_blob_with_proof(
+		&self,
+		block_hash: Hash,
+		index: u32,
+	) -> RpcResult<Option<BlobWithProof>> {
+		let Some(header) =
+			self.client.header(block_hash).map_err(|e| internal_error(e.to_string()))?
+		else {
+			return Ok(None);
+		};
+		let key = blobchain_verifier::blobs_storage_key(*header.number());
+		let proof = self
+			.client
+			.read_proof(block_hash, &mut std::iter::once(&key[..]))
+			.map_err(|e| internal_error(e.to_string()))?
+			.into_compact_proof::<BlakeTwo256>(*header.state_root())
+			.map_err(|e| internal_error(format!("Failed to compact proof: {:?}", e)))?;
+
+		// Read the blob through the proof, which also makes sure the proof is valid.
+		match blobchain_verifier::verify_blob_storage_proof(&header, index, &proof) {
+			Ok(blob) => Ok(Some(BlobWithProof {
+				blob: blob.into(),
+				proof: proof.encoded_nodes.into_iter().map(Into::into).collect(),
+			})),
+			Err(VerifierError::BlobNotFound) => Ok(None),
+			Err(e) => Err(internal_error(format!("Generated invalid proof: {:?}", e))),
+		}
+	}
+
+	fn get_inclusion_proof(
+		&self,
+		block_hash: Hash,
+		blob_hash: Hash,
+	) -> RpcResult<Option<BlobInclusionProof>> {
+		let (Some(header), Some(body)) = (
+			self.client.header(block_hash).map_err(|e| internal_error(e.to_string()))?,
+			self.client.block_body(block_hash).map_err(|e| internal_error(e.to_string()))?,
+		) else {
+			return Ok(None);
+		};
+		let extrinsics = body.iter().map(Encode::encode).collect::<Vec<_>>();
+		let Some(index) = extrinsics.iter().position(|extrinsic| {
+			runtime::UncheckedExtrinsic::decode(&mut &extrinsic[..])
+				.map(blobs_of)
+				.is_ok_and(|blobs| blobs.iter().any(|blob| BlakeTwo256::hash(blob) == blob_hash))
+		}) else {
+			return Ok(None);
+		};
+		let index = index as u32;
+		let proof = ordered_trie_proof(&extrinsics, index)?;
+
+		blobchain_verifier::verify_blob_inclusion_proof(
+			&header,
+			index,
+			&extrinsics[index as usize],
+			&proof,
+			|extrinsic| {
+				blobs_of(extrinsic)
+					.into_iter()
+					.find(|blob| BlakeTwo256::hash(blob) == blob_hash)
+			},
+		)
+		.map_err(|e| internal_error(format!("Generated invalid proof: {:?}", e)))?;
+
+		Ok(Some(BlobInclusionProof {
+			index,
+			extrinsic: extrinsics[index as usize].clone().into(),
+			proof: proof.into_iter().map(Into::into).collect(),
+		}))
+	}
+
+	fn get_commitment_proof(
+		&self,
+		number: BlockNumber,
+		index: u32,
+	) -> RpcResult<Option<BlobCommitmentProof>> {
+		// Read the blob hashes the runtime committed to at the canonical block, not the offchain
+		// index, which also holds the blobs of other forks at the same height.
+		let Some((block_hash, hashes)) = self.blob_hashes(number)? else {
+			return Ok(None);
+		};
+		let Some(&blob_hash) = hashes.get(index as usize) else {
+			return Ok(None);
+		};
+		let values = hashes.iter().map(|hash| hash.as_bytes().to_vec()).collect::<Vec<_>>();
+		let proof = ordered_trie_proof(&values, index)?;
+
+		// Check the proof against the MMR leaf of the block, which its child block adds.
+		let best_hash = self.client.info().best_hash;
+		let (leaves, _) = self
+			.client
+			.runtime_api()
+			.generate_proof(best_hash, vec![number.saturating_add(1)], None)
+			.map_err(|e| internal_error(e.to_string()))?
+			.map_err(|e| {
+				internal_error(format!("MMR leaf of block #{} unavailable: {:?}", number, e))
+			})?;
+		let leaf = leaves
+			.into_iter()
+			.next()
+			.and_then(|leaf| {
+				leaf.into_opaque_leaf()
+					.try_decode::<pallet_blobmanager::BlobMmrLeaf<BlockNumber, Hash>>()
+			})
+			.filter(|leaf| leaf.block_number == number && leaf.block_hash == block_hash)
+			.ok_or_else(|| internal_error(format!("Invalid MMR leaf of block #{}", number)))?;
+
+		blobchain_verifier::verify_blob_commitment_proof::<BlakeTwo256>(
+			leaf.blob_root,
+			index,
+			blob_hash,
+			&proof,
+		)
+		.map_err(|e| internal_error(format!("Generated invalid proof: {:?}", e)))?;
+
+		Ok(Some(BlobCommitmentProof {
+			blob_hash,
+			proof: proof.into_iter().map(Into::into).collect(),
+		}))
+	}
+
+	fn get_chunk_proof(
+		&self,
+		number: BlockNumber,
+		index: u32,
+		chunk: u32,
+	) -> RpcResult<Option<BlobChunkProof>> {
+		let Some(blob) =
+			self.offchain_get(&pallet_blobmanager::offchain_key_by_location(number, index))?
+		else {
+			return Ok(None);
+		};
+
+		Ok(pallet_blobchallenge::chunk_proof::<BlakeTwo256>(&blob, chunk).map(|(chunk, proof)| {
+			BlobChunkProof {
+				chunk: chunk.into(),
+				proof: proof.into_iter().map(Into::into).collect(),
+			}
+		}))
+	}
+}
+
+/// Returns the blob uploaded by `extrinsic`, if it is an upload extrinsic of a single blob.
+pub fn blob_of(extrinsic: runtime::UncheckedExtrinsic) -> Option<Vec<u8>> {
+	match extrinsic.function {
+		runtime::RuntimeCall::BlobManager(
+			pallet_blobmanager::Call::upload_blob { blob } |
+			pallet_blobmanager::Call::upload_blob_with_meta { blob, .. } |
+			pallet_blobmanager::Call::upload_to_channel { blob, .. },
+		) => Some(blob),
+		_ => None,
+	}
+}
+
+/// Returns the blobs uploaded by `extrinsic`, in order, if it is an upload extrinsic, and
+/// nothing otherwise.
+///
+/// Uploads nested in the wrapper calls of the runtime, e.g. `Utility::batch`, are included. Those
+/// of a `Utility::batch` that got interrupted may not have been stored.
+pub fn blobs_of(extrinsic: runtime::UncheckedExtrinsic) -> Vec<Vec<u8>> {
+	pallet_blobmanager::uploaded_blobs::<runtime::Runtime>(&extrinsic.function)
+		.into_iter()
+		.map(<[u8]>::to_vec)
+		.collect()
+}
+
+/// Builds the ordered trie of `values`, keyed by their compact encoded index like the extrinsics
+/// trie of a block, and proves value `index` in it.
+fn ordered_trie_proof(values: &[Vec<u8>], index: u32) -> RpcResult<Vec<Vec<u8>>> {
+	let trie_error = |e| internal_error(format!("Failed to build trie: {:?}", e));
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut root = Default::default();
+	{
+		let mut trie = TrieDBMutBuilder::<LayoutV0<BlakeTwo256>>::new(&mut db, &mut root).build();
+		for (i, value) in values.iter().enumerate() {
+			trie.insert(&blobchain_verifier::extrinsic_key(i as u32), value)
+				.map_err(trie_error)?;
+		}
+	}
+
+	sp_trie::generate_trie_proof::<LayoutV0<BlakeTwo256>, _, _, _>(
+		&db,
+		root,
+		&[blobchain_verifier::extrinsic_key(index)],
+	)
+	.map_err(trie_error)
+}
+
+fn internal_error(message: impl Into<String>) -> ErrorObjectOwned {
+	ErrorObject::owned(INTERNAL_ERROR_CODE, message, None::<()>)
+}
@@ -0,0 +1,466 @@
+//! Node-side store of blobs from finalized blocks.
+//!
+//! The [`BlobStore`] follows finality, copies the blobs of every finalized block out of the
+//! runtime state, or out of the block body once the state is pruned, and keeps them in a
+//! [`BlobBackend`] of its own, subject to its own retention policy. Blob RPC methods read from
+//! the store, so serving a blob never requires the state of the block that uploaded it.
+
+use crate::{blob_rpc::blobs_of, service::FullClient};
+use blobchain_runtime::{self as runtime, BlockNumber, Hash};
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use futures::StreamExt;
+use pallet_blobmanager::{BlobLimits, DefaultLimits};
+use sc_client_api::{AuxStore, BlockBackend, BlockchainEvents, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::traits::{BlakeTwo256, Hash as _, Header as _};
+use std::{
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+/// Log target of the blob store.
+const LOG_TARGET: &str = "blob-store";
+
+/// Prefix of the keys the store writes to a shared database.
+const KEY_PREFIX: &[u8] = b"blob-store:";
+
+/// Result type of blob store operations.
+pub type Result<T> = std::result::Result<T, String>;
+
+/// Key-value storage a [`BlobStore`] keeps its data in.
+pub trait BlobBackend: Send + Sync {
+	/// Returns the value stored under `key`.
+	fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+	/// Atomically inserts and deletes the given keys.
+	fn commit(&self, insert: &[(Vec<u8>, Vec<u8>)], delete: &[Vec<u8>]) -> Result<()>;
+}
+
+/// [`BlobBackend`] keeping every value in a file of a directory.
+pub struct FsBackend {
+	path: PathBuf,
+}
+
+/// Changes of a commit to an [`FsBackend`], as `(insert, delete)`.
+type Journal = (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>);
+
+impl FsBackend {
+	/// Opens the store in directory `path`, creating it if needed.
+	///
+	/// Finishes a commit interrupted by a crash, if any.
+	pub fn open(path: &Path) -> Result<Self> {
+		std::fs::create_dir_all(path)
+			.map_err(|e| format!("Failed to create blob store at {}: {}", path.display(), e))?;
+		let backend = Self { path: path.to_path_buf() };
+		backend.replay_journal()?;
+		Ok(backend)
+	}
+
+	fn file(&self, key: &[u8]) -> PathBuf {
+		self.path.join(sp_core::bytes::to_hex(key, false))
+	}
+
+	/// File recording the commit in progress. Keys map to hex file names, so it never collides
+	/// with a value.
+	fn journal(&self) -> PathBuf {
+		self.path.join("journal")
+	}
+
+	fn replay_journal(&self) -> Result<()> {
+		let journal = match std::fs::read(self.journal()) {
+			Ok(journal) => journal,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+			Err(e) => return Err(e.to_string()),
+		};
+		// A journal only exists once it was completely written, see `commit`.
+		let (insert, delete) = Journal::decode(&mut &journal[..]).map_err(|e| e.to_string())?;
+		log::info!(target: LOG_TARGET, "Finishing interrupted blob store commit");
+		self.apply(&insert, &delete)?;
+		std::fs::remove_file(self.journal()).map_err(|e| e.to_string())
+	}
+
+	/// Applies the changes of a commit, which can be repeated after a crash.
+	fn apply(&self, insert: &[(Vec<u8>, Vec<u8>)], delete: &[Vec<u8>]) -> Result<()> {
+		for (key, value) in insert {
+			// Write to a temporary file first so readers never see partial values.
+			let file = self.file(key);
+			let tmp = file.with_extension("tmp");
+			write_synced(&tmp, value)?;
+			std::fs::rename(&tmp, &file).map_err(|e| e.to_string())?;
+		}
+		for key in delete {
+			match std::fs::remove_file(self.file(key)) {
+				Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+				_ => {},
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Writes `value` to file `path` and flushes it to disk.
+fn write_synced(path: &Path, value: &[u8]) -> Result<()> {
+	use std::io::Write;
+
+	let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+	file.write_all(value).and_then(|()| file.sync_all()).map_err(|e| e.to_string())
+}
+
+impl BlobBackend for FsBackend {
+	fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+		match std::fs::read(self.file(key)) {
+			Ok(value) => Ok(Some(value)),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(e.to_string()),
+		}
+	}
+
+	fn commit(&self, insert: &[(Vec<u8>, Vec<u8>)], delete: &[Vec<u8>]) -> Result<()> {
+		// Record the changes in a journal first, renamed into place once complete, so that a
+		// commit interrupted by a crash is finished when the store is opened again.
+		let tmp = self.journal().with_extension("tmp");
+		write_synced(&tmp, &(insert, delete).encode())?;
+		std::fs::rename(&tmp, self.journal()).map_err(|e| e.to_string())?;
+
+		self.apply(insert, delete)?;
+		std::fs::remove_file(self.journal()).map_err(|e| e.to_string())
+	}
+}
+
+/// [`BlobBackend`] keeping values in the auxiliary column of the node's own database, whichever
+/// of RocksDB or ParityDB it is.
+pub struct DbBackend {
+	client: Arc<FullClient>,
+}
+
+impl DbBackend {
+	/// Creates a new [`Self`] storing values through `client`.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		Self { client }
+	}
+}
+
+impl BlobBackend for DbBackend {
+	fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+		self.client.get_aux(&[KEY_PREFIX, key].concat()).map_err(|e| e.to_string())
+	}
+
+	fn commit(&self, insert: &[(Vec<u8>, Vec<u8>)], delete: &[Vec<u8>]) -> Result<()> {
+		let insert = insert
+			.iter()
+			.map(|(key, value)| ([KEY_PREFIX, key].concat(), value))
+			.collect::<Vec<_>>();
+		let delete = delete.iter().map(|key| [KEY_PREFIX, key].concat()).collect::<Vec<_>>();
+
+		self.client
+			.insert_aux(
+				&insert.iter().map(|(key, value)| (&key[..], &value[..])).collect::<Vec<_>>(),
+				&delete.iter().map(|key| &key[..]).collect::<Vec<_>>(),
+			)
+			.map_err(|e| e.to_string())
+	}
+}
+
+/// Store of blobs from finalized blocks.
+pub struct BlobStore {
+	backend: Box<dyn BlobBackend>,
+	retention: Option<BlockNumber>,
+}
+
+impl BlobStore {
+	/// Creates a new [`Self`] keeping the blobs of the last `retention` finalized blocks in
+	/// `backend`, or all blobs if `retention` is `None`.
+	pub fn new(backend: Box<dyn BlobBackend>, retention: Option<BlockNumber>) -> Self {
+		Self { backend, retention }
+	}
+
+	/// Returns the blob stored at `index` of block `number`.
+	pub fn blob(&self, number: BlockNumber, index: u32) -> Result<Option<Vec<u8>>> {
+		self.backend.get(&blob_key(number, index))
+	}
+
+	/// Returns the location of the blob with hash `hash`.
+	pub fn location(&self, hash: Hash) -> Result<Option<(BlockNumber, u32)>> {
+		self.get_decoded(&hash_key(hash))
+	}
+
+	/// Returns the blob with hash `hash`.
+	pub fn blob_by_hash(&self, hash: Hash) -> Result<Option<Vec<u8>>> {
+		match self.location(hash)? {
+			Some((number, index)) => self.blob(number, index),
+			None => Ok(None),
+		}
+	}
+
+	/// Returns the hashes of the blobs of block `number`, in upload order.
+	pub fn block_hashes(&self, number: BlockNumber) -> Result<Vec<Hash>> {
+		Ok(self.get_decoded(&block_key(number))?.unwrap_or_default())
+	}
+
+	/// Stores the blobs of finalized block `number`, then drops blocks outside the retention
+	/// window.
+	pub fn import_block(&self, number: BlockNumber, blobs: Vec<Vec<u8>>) -> Result<()> {
+		let hashes = blobs.iter().map(|blob| BlakeTwo256::hash(blob)).collect::<Vec<_>>();
+		let mut insert = vec![(block_key(number), hashes.encode())];
+		for (index, (hash, blob)) in hashes.iter().zip(blobs).enumerate() {
+			insert.push((hash_key(*hash), (number, index as u32).encode()));
+			insert.push((blob_key(number, index as u32), blob));
+		}
+
+		let mut delete = Vec::new();
+		let oldest = self.get_decoded::<BlockNumber>(OLDEST_KEY)?.unwrap_or(number);
+		let keep_from = self
+			.retention
+			.map_or(oldest, |retention| number.saturating_sub(retention.saturating_sub(1)));
+		for pruned in oldest..keep_from.min(number) {
+			for (index, hash) in self.block_hashes(pruned)?.into_iter().enumerate() {
+				// The same blob may have been stored again in a block that is kept, which the
+				// hash then points to instead.
+				if !hashes.contains(&hash) && self.location(hash)? == Some((pruned, index as u32)) {
+					delete.push(hash_key(hash));
+				}
+				delete.push(blob_key(pruned, index as u32));
+			}
+			delete.push(block_key(pruned));
+		}
+		insert.push((OLDEST_KEY.to_vec(), oldest.max(keep_from).encode()));
+		insert.push((LATEST_KEY.to_vec(), number.encode()));
+
+		self.backend.commit(&insert, &delete)
+	}
+
+	fn get_decoded<T: Decode>(&self, key: &[u8]) -> Result<Option<T>> {
+		self.backend
+			.get(key)?
+			.map(|value| T::decode(&mut &value[..]).map_err(|e| e.to_string()))
+			.transpose()
+	}
+
+	/// Follows finality of `client`, importing the blobs of every finalized block.
+	///
+	/// Blocks finalized before the store was created, or while the node wasn't running, are
+	/// imported on the first finality notification, as far as their state or body is still
+	/// available and they fall within the retention window.
+	///
+	/// Writes to the backend synchronously, so it has to be spawned as a blocking task.
+	pub async fn run(self: Arc<Self>, client: Arc<FullClient>) {
+		let mut finality_notifications = client.finality_notification_stream();
+
+		while let Some(notification) = finality_notifications.next().await {
+			let finalized = *notification.header.number();
+			let latest = match self.get_decoded::<BlockNumber>(LATEST_KEY) {
+				// The genesis block has no blobs
+				Ok(latest) => latest.unwrap_or(0),
+				Err(e) => {
+					log::warn!(target: LOG_TARGET, "Failed to read blob store: {}", e);
+					continue;
+				},
+			};
+			let first = match self.retention {
+				Some(retention) => latest.max(finalized.saturating_sub(retention)),
+				None => latest,
+			};
+
+			let mut failed = Vec::new();
+			for number in first.saturating_add(1)..=finalized {
+				if let Err(e) = self.import_finalized(&client, number) {
+					log::debug!(
+						target: LOG_TARGET,
+						"Failed to store blobs of block #{}: {}",
+						number,
+						e,
+					);
+					failed.push(number);
+				}
+			}
+			if let (Some(first), Some(last)) = (failed.first(), failed.last()) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to store blobs of {} blocks between #{} and #{}, their body may be pruned",
+					failed.len(),
+					first,
+					last,
+				);
+			}
+		}
+	}
+
+	fn import_finalized(&self, client: &FullClient, number: BlockNumber) -> Result<()> {
+		let hash = client
+			.hash(number)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block #{} not found", number))?;
+		let blobs = match blobs_at(client, hash, number) {
+			Ok(blobs) => blobs,
+			Err(e) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Reading blobs of block #{} from its body, its state is unavailable: {}",
+					number,
+					e,
+				);
+				blobs_in_body(client, hash)?
+			},
+		};
+
+		log::debug!(target: LOG_TARGET, "Storing {} blobs of block #{}", blobs.len(), number);
+		self.import_block(number, blobs)
+	}
+}
+
+/// Reads the blobs uploaded in block `number` with hash `hash` from its state.
+pub fn blobs_at(client: &FullClient, hash: Hash, number: BlockNumber) -> Result<Vec<Vec<u8>>> {
+	let key = StorageKey(pallet_blobmanager::Blobs::<runtime::Runtime>::hashed_key_for(number));
+	client
+		.storage(hash, &key)
+		.map_err(|e| e.to_string())?
+		.map(|data| Vec::<Vec<u8>>::decode(&mut &data.0[..]))
+		.transpose()
+		.map_err(|e| e.to_string())
+		.map(Option::unwrap_or_default)
+}
+
+/// Reads the blobs uploaded in block `hash` from its body.
+///
+/// Without the state of the block, uploads that failed can't be told apart from the ones that
+/// stored their blobs, so this is only a fallback for blocks whose state is pruned.
+fn blobs_in_body(client: &FullClient, hash: Hash) -> Result<Vec<Vec<u8>>> {
+	let body = client
+		.block_body(hash)
+		.map_err(|e| e.to_string())?
+		.ok_or_else(|| format!("Body of block {:?} not found", hash))?;
+	let mut blobs = Vec::new();
+	for extrinsic in body {
+		let extrinsic = runtime::UncheckedExtrinsic::decode(&mut &extrinsic.encode()[..])
+			.map_err(|e| format!("Invalid extrinsic in block {:?}: {}", hash, e))?;
+		blobs.extend(blobs_of(extrinsic));
+	}
+	Ok(blobs)
+}
+
+/// Reads the live blob limits from the state of block `hash`.
+pub fn limits_at(client: &FullClient, hash: Hash) -> Result<BlobLimits> {
+	let key = StorageKey(pallet_blobmanager::Limits::<runtime::Runtime>::hashed_key().to_vec());
+	client
+		.storage(hash, &key)
+		.map_err(|e| e.to_string())?
+		.map(|data| BlobLimits::decode(&mut &data.0[..]))
+		.transpose()
+		.map_err(|e| e.to_string())
+		.map(|limits| limits.unwrap_or_else(DefaultLimits::<runtime::Runtime>::get))
+}
+
+/// Key of the oldest block the store still holds blobs of.
+const OLDEST_KEY: &[u8] = b"oldest";
+
+/// Key of the latest block imported into the store.
+const LATEST_KEY: &[u8] = b"latest";
+
+fn blob_key(number: BlockNumber, index: u32) -> Vec<u8> {
+	(b"blob", number.to_be_bytes(), index.to_be_bytes()).encode()
+}
+
+fn hash_key(hash: Hash) -> Vec<u8> {
+	(b"hash", hash).encode()
+}
+
+fn block_key(number: BlockNumber) -> Vec<u8> {
+	(b"block", number.to_be_bytes()).encode()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{collections::BTreeMap, sync::Mutex};
+
+	/// [`BlobBackend`] keeping values in memory.
+	#[derive(Default)]
+	struct MemBackend(Mutex<BTreeMap<Vec<u8>, Vec<u8>>>);
+
+	impl BlobBackend for MemBackend {
+		fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+			Ok(self.0.lock().unwrap().get(key).cloned())
+		}
+
+		fn commit(&self, insert: &[(Vec<u8>, Vec<u8>)], delete: &[Vec<u8>]) -> Result<()> {
+			let mut values = self.0.lock().unwrap();
+			values.extend(insert.iter().cloned());
+			for key in delete {
+				values.remove(key);
+			}
+			Ok(())
+		}
+	}
+
+	fn store(retention: Option<BlockNumber>) -> BlobStore {
+		BlobStore::new(Box::new(MemBackend::default()), retention)
+	}
+
+	#[test]
+	fn import_block_stores_blobs() {
+		let store = store(None);
+		let blobs = vec![vec![1u8; 32], vec![2u8; 16]];
+		store.import_block(1, blobs.clone()).unwrap();
+
+		let hashes = blobs.iter().map(|blob| BlakeTwo256::hash(blob)).collect::<Vec<_>>();
+		assert_eq!(store.block_hashes(1).unwrap(), hashes);
+		assert_eq!(store.blob(1, 1).unwrap(), Some(blobs[1].clone()));
+		assert_eq!(store.location(hashes[0]).unwrap(), Some((1, 0)));
+		assert_eq!(store.blob_by_hash(hashes[1]).unwrap(), Some(blobs[1].clone()));
+	}
+
+	#[test]
+	fn import_block_drops_blocks_outside_retention() {
+		let store = store(Some(2));
+		let blobs = (1..=3u8).map(|byte| vec![byte; 32]).collect::<Vec<_>>();
+		for (number, blob) in (1..).zip(&blobs) {
+			store.import_block(number, vec![blob.clone()]).unwrap();
+		}
+
+		let dropped = BlakeTwo256::hash(&blobs[0]);
+		assert_eq!(store.blob(1, 0).unwrap(), None);
+		assert_eq!(store.location(dropped).unwrap(), None);
+		assert!(store.block_hashes(1).unwrap().is_empty());
+		assert_eq!(store.blob(2, 0).unwrap(), Some(blobs[1].clone()));
+		assert_eq!(store.blob(3, 0).unwrap(), Some(blobs[2].clone()));
+	}
+
+	#[test]
+	fn import_block_keeps_all_blocks_without_retention() {
+		let store = store(None);
+		for number in 1..=5 {
+			store.import_block(number, vec![vec![number as u8; 8]]).unwrap();
+		}
+
+		assert!((1..=5).all(|number| store.blob(number, 0).unwrap().is_some()));
+	}
+
+	#[test]
+	fn blob_stored_again_in_a_kept_block_stays_found_by_hash() {
+		let store = store(Some(2));
+		let blob = vec![7u8; 32];
+		let hash = BlakeTwo256::hash(&blob);
+		store.import_block(1, vec![blob.clone()]).unwrap();
+		store.import_block(2, vec![blob.clone()]).unwrap();
+		store.import_block(3, vec![vec![8u8; 32]]).unwrap();
+
+		assert_eq!(store.blob(1, 0).unwrap(), None);
+		assert_eq!(store.location(hash).unwrap(), Some((2, 0)));
+		assert_eq!(store.blob_by_hash(hash).unwrap(), Some(blob));
+	}
+
+	#[test]
+	fn blob_stored_again_in_the_imported_block_stays_found_by_hash() {
+		let store = store(Some(1));
+		let blob = vec![7u8; 32];
+		let hash = BlakeTwo256::hash(&blob);
+		store.import_block(1, vec![blob.clone()]).unwrap();
+		store.import_block(2, vec![vec![8u8; 32], blob.clone()]).unwrap();
+
+		assert_eq!(store.blob(1, 0).unwrap(), None);
+		assert_eq!(store.location(hash).unwrap(), Some((2, 1)));
+		assert_eq!(store.blob_by_hash(hash).unwrap(), Some(blob));
+	}
+}
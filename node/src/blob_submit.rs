@@ -0,0 +1,264 @@
+//! RPC method for submitting blobs and following their inclusion.
+//!
+//! `author_submitAndWatchExtrinsic` only reports generic transaction statuses.
+//! `blob_submitAndWatch` reports the same statuses for a signed `upload_blob` extrinsic, but once
+//! the extrinsic is in a block, it also reads the events of the block and reports where the blob
+//! got stored, or the error its dispatch failed with.
+
+use crate::blob_rpc::blob_of;
+use blobchain_runtime::{self as runtime, opaque::Block, BlockNumber, Hash};
+use codec::Decode;
+use frame_support::traits::PalletInfoAccess;
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, RpcResult, SubscriptionResult},
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned, INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+	PendingSubscriptionSink, SubscriptionMessage,
+};
+use sc_client_api::{Backend, StorageProvider};
+use sc_transaction_pool_api::{
+	TransactionFor, TransactionPool, TransactionSource, TransactionStatus,
+};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, Bytes};
+use sp_runtime::{DispatchError, ModuleError};
+use std::{marker::PhantomData, sync::Arc};
+
+/// What an `upload_blob` extrinsic did in the block that includes it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlobOutcome {
+	/// The blob got stored at `index` of block `block_number`, with hash `hash`.
+	#[serde(rename_all = "camelCase")]
+	Stored { block_number: BlockNumber, index: u32, hash: Hash },
+	/// The dispatch failed. `error` is the name of the `BlobManager` error variant, e.g.
+	/// `ExceededMaxBlobsPerBlock`, or the debug representation of any other dispatch error.
+	Failed { error: String },
+}
+
+/// Inclusion of an `upload_blob` extrinsic in a block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobInclusion {
+	/// Hash of the block.
+	pub block_hash: Hash,
+	/// Index of the extrinsic in the block.
+	pub extrinsic_index: u32,
+	/// What the extrinsic did.
+	pub outcome: BlobOutcome,
+}
+
+/// Status of a blob transaction, as reported by `blob_submitAndWatch`.
+///
+/// Mirrors the transaction statuses of the pool, with the inclusion statuses extended by the
+/// outcome of the extrinsic.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlobTransactionStatus {
+	/// The transaction is waiting for a dependency in the pool.
+	Future,
+	/// The transaction is ready to be included.
+	Ready,
+	/// The transaction got broadcast to the given peers.
+	Broadcast(Vec<String>),
+	/// The transaction got included in a block.
+	InBlock(BlobInclusion),
+	/// The block including the transaction got retracted.
+	Retracted(Hash),
+	/// The block including the transaction wasn't finalized in time; the subscription ends.
+	FinalityTimeout(Hash),
+	/// The block including the transaction got finalized; the subscription ends.
+	Finalized(BlobInclusion),
+	/// The transaction got replaced by the one with the given hash.
+	Usurped(Hash),
+	/// The transaction got dropped from the pool.
+	Dropped,
+	/// The transaction is no longer valid.
+	Invalid,
+}
+
+/// Blob submission RPC methods.
+#[rpc(server)]
+pub trait BlobSubmitApi {
+	/// Submits the signed `upload_blob` extrinsic `extrinsic` and reports its status until it is
+	/// finalized or leaves the pool.
+	#[subscription(
+		name = "blob_submitAndWatch",
+		unsubscribe = "blob_unwatch",
+		item = BlobTransactionStatus
+	)]
+	async fn submit_and_watch(&self, extrinsic: Bytes) -> SubscriptionResult;
+}
+
+/// Implementation of [`BlobSubmitApiServer`].
+pub struct BlobSubmit<C, P, B> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	_backend: PhantomData<fn() -> B>,
+}
+
+impl<C, P, B> BlobSubmit<C, P, B> {
+	/// Creates a new [`Self`] submitting to `pool` and reading events from `client`.
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self { client, pool, _backend: PhantomData }
+	}
+}
+
+impl<C, P, B> BlobSubmit<C, P, B>
+where
+	C: StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	fn blob_status(
+		&self,
+		status: TransactionStatus<Hash, Hash>,
+	) -> Result<BlobTransactionStatus, String> {
+		Ok(match status {
+			TransactionStatus::Future => BlobTransactionStatus::Future,
+			TransactionStatus::Ready => BlobTransactionStatus::Ready,
+			TransactionStatus::Broadcast(peers) => BlobTransactionStatus::Broadcast(peers),
+			TransactionStatus::InBlock((block_hash, index)) => {
+				BlobTransactionStatus::InBlock(self.inclusion(block_hash, index as u32)?)
+			},
+			TransactionStatus::Retracted(block_hash) => {
+				BlobTransactionStatus::Retracted(block_hash)
+			},
+			TransactionStatus::FinalityTimeout(block_hash) => {
+				BlobTransactionStatus::FinalityTimeout(block_hash)
+			},
+			TransactionStatus::Finalized((block_hash, index)) => {
+				BlobTransactionStatus::Finalized(self.inclusion(block_hash, index as u32)?)
+			},
+			TransactionStatus::Usurped(hash) => BlobTransactionStatus::Usurped(hash),
+			TransactionStatus::Dropped => BlobTransactionStatus::Dropped,
+			TransactionStatus::Invalid => BlobTransactionStatus::Invalid,
+		})
+	}
+
+	/// Reads the outcome of extrinsic `extrinsic_index` from the events of block `block_hash`.
+	fn inclusion(&self, block_hash: Hash, extrinsic_index: u32) -> Result<BlobInclusion, String> {
+		type EventRecord = frame_system::EventRecord<runtime::RuntimeEvent, Hash>;
+
+		let key = StorageKey(frame_system::Events::<runtime::Runtime>::hashed_key().to_vec());
+		let events = self
+			.client
+			.storage(block_hash, &key)
+			.map_err(|e| e.to_string())?
+			.map(|data| Vec::<EventRecord>::decode(&mut &data.0[..]))
+			.transpose()
+			.map_err(|e| format!("Failed to decode events of {}: {}", block_hash, e))?
+			.unwrap_or_default();
+
+		let phase = frame_system::Phase::ApplyExtrinsic(extrinsic_index);
+		let outcome = events
+			.into_iter()
+			.filter(|record| record.phase == phase)
+			.find_map(|record| match record.event {
+				runtime::RuntimeEvent::BlobManager(pallet_blobmanager::Event::BlobStored {
+					block_number,
+					index,
+					hash,
+				}) => Some(BlobOutcome::Stored { block_number, index, hash }),
+				runtime::RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+					dispatch_error,
+					..
+				}) => Some(BlobOutcome::Failed { error: error_name(dispatch_error) }),
+				_ => None,
+			})
+			.ok_or_else(|| {
+				format!(
+					"No outcome of extrinsic {} in the events of {}",
+					extrinsic_index, block_hash
+				)
+			})?;
+
+		Ok(BlobInclusion { block_hash, extrinsic_index, outcome })
+	}
+}
+
+#[async_trait]
+impl<C, P, B> BlobSubmitApiServer for BlobSubmit<C, P, B>
+where
+	C: HeaderBackend<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+	P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+	B: Backend<Block> + 'static,
+{
+	async fn submit_and_watch(
+		&self,
+		pending: PendingSubscriptionSink,
+		extrinsic: Bytes,
+	) -> SubscriptionResult {
+		let extrinsic = match check_extrinsic::<P>(&extrinsic) {
+			Ok(extrinsic) => extrinsic,
+			Err(e) => {
+				pending.reject(e).await;
+				return Ok(());
+			},
+		};
+		let best_hash = self.client.info().best_hash;
+		let statuses = match self
+			.pool
+			.submit_and_watch(best_hash, TransactionSource::External, extrinsic)
+			.await
+		{
+			Ok(statuses) => statuses,
+			Err(e) => {
+				pending.reject(internal_error(e.to_string())).await;
+				return Ok(());
+			},
+		};
+
+		let sink = pending.accept().await?;
+		let mut statuses = statuses.fuse();
+		let mut closed = Box::pin(sink.closed().fuse());
+		loop {
+			let status = futures::select! {
+				status = statuses.next() => match status {
+					Some(status) => status,
+					None => return Ok(()),
+				},
+				_ = closed => return Ok(()),
+			};
+			let status = self.blob_status(status)?;
+			sink.send(SubscriptionMessage::from_json(&status)?).await?;
+		}
+	}
+}
+
+/// Decodes `extrinsic`, making sure it is a signed upload extrinsic of a single blob.
+fn check_extrinsic<P: TransactionPool<Block = Block>>(
+	extrinsic: &[u8],
+) -> RpcResult<TransactionFor<P>> {
+	let decoded = runtime::UncheckedExtrinsic::decode(&mut &extrinsic[..])
+		.map_err(|e| invalid_params(format!("Invalid extrinsic: {}", e)))?;
+	if decoded.signature.is_none() || blob_of(decoded).is_none() {
+		return Err(invalid_params("Not a signed upload extrinsic of a single blob"));
+	}
+
+	TransactionFor::<P>::decode(&mut &extrinsic[..])
+		.map_err(|e| invalid_params(format!("Invalid extrinsic: {}", e)))
+}
+
+/// Returns the name of the `BlobManager` error `error` stands for, or its debug representation
+/// if it is another error.
+fn error_name(error: DispatchError) -> String {
+	if let DispatchError::Module(ModuleError { index, error: ref encoded, .. }) = error {
+		if index as usize == <runtime::BlobManager as PalletInfoAccess>::index() {
+			if let Ok(error) =
+				pallet_blobmanager::Error::<runtime::Runtime>::decode(&mut &encoded[..])
+			{
+				return <&'static str>::from(error).into();
+			}
+		}
+	}
+	format!("{:?}", error)
+}
+
+fn invalid_params(message: impl Into<String>) -> ErrorObjectOwned {
+	ErrorObject::owned(INVALID_PARAMS_CODE, message, None::<()>)
+}
+
+fn internal_error(message: impl Into<String>) -> ErrorObjectOwned {
+	ErrorObject::owned(INTERNAL_ERROR_CODE, message, None::<()>)
+}
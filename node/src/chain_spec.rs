@@ -1,4 +1,5 @@
 use blobchain_runtime::{AccountId, Signature, WASM_BINARY};
+use pallet_blobmanager::AuthorityId as BlobManagerId;
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
@@ -28,9 +29,9 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate the Aura, Grandpa and blob attestation keys of an authority.
+pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId, BlobManagerId) {
+	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s), get_from_seed::<BlobManagerId>(s))
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -93,7 +94,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AuraId, GrandpaId, BlobManagerId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
@@ -109,6 +110,9 @@ fn testnet_genesis(
 		"grandpa": {
 			"authorities": initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
 		},
+		"blobManager": {
+			"authorities": initial_authorities.iter().map(|x| (x.2.clone())).collect::<Vec<_>>(),
+		},
 		"sudo": {
 			// Assign network admin rights.
 			"key": Some(root_key),
@@ -0,0 +1,328 @@
+//! The `check-blobs` command, verifying stored blobs against the blocks that uploaded them.
+
+use crate::{blob_rpc::blobs_of, service::FullClient};
+use blobchain_runtime::{self as runtime, BlockNumber, Hash};
+use codec::{Decode, Encode};
+use frame_system::{EventRecord, Phase};
+use pallet_blobmanager::{blob_chunk_commitment, ChunkCommitment};
+use sc_cli::{CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{BlockBackend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::traits::{BlakeTwo256, Hash as _};
+
+/// The `check-blobs` command.
+///
+/// Walks a range of blocks in the local database, decodes the `upload_blob` and `upload_blobs`
+/// extrinsics of each block and compares their payloads with the `Blobs` storage entry of that
+/// block. The hash and chunk commitment of every stored blob are recomputed and compared with
+/// the `BlobHashes` and `BlobChunkCommitments` entries of the block.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CheckBlobsCmd {
+	/// First block to check.
+	#[arg(long, default_value_t = 1)]
+	pub from: BlockNumber,
+
+	/// Last block to check. Defaults to the best block.
+	#[arg(long)]
+	pub to: Option<BlockNumber>,
+
+	/// Print the report as JSON.
+	#[arg(long)]
+	pub json: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// A problem found while checking a block.
+#[derive(Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Issue {
+	/// The block isn't in the database.
+	MissingBlock { block: BlockNumber },
+	/// Bodies of the blocks in the range have been pruned.
+	BodyPruned { from: BlockNumber, to: BlockNumber },
+	/// State of the blocks in the range has been pruned.
+	StatePruned { from: BlockNumber, to: BlockNumber },
+	/// A blob of a successful `upload_blob` or `upload_blobs` extrinsic has no matching entry in
+	/// storage.
+	MissingBlob { block: BlockNumber, index: u32 },
+	/// The stored blob differs from the payload of the extrinsic that uploaded it.
+	Mismatch { block: BlockNumber, index: u32, expected_len: usize, stored_len: usize },
+	/// A stored blob doesn't correspond to any top-level upload extrinsic, e.g. because it was
+	/// uploaded through a call wrapped in another one.
+	Unaccounted { block: BlockNumber, index: u32 },
+	/// The hash of a stored blob differs from its `BlobHashes` entry, or one of them is missing.
+	HashMismatch { block: BlockNumber, index: u32 },
+	/// The chunk commitment of a stored blob differs from its `BlobChunkCommitments` entry, or
+	/// the entry is missing.
+	CommitmentMismatch { block: BlockNumber, index: u32 },
+	/// Storage or an extrinsic of the block couldn't be decoded.
+	Undecodable { block: BlockNumber, reason: String },
+}
+
+/// Result of a `check-blobs` run.
+#[derive(Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+	/// First block checked.
+	pub from: BlockNumber,
+	/// Last block checked.
+	pub to: BlockNumber,
+	/// Number of blocks whose body and state were available.
+	pub blocks_checked: u32,
+	/// Number of blobs compared with storage.
+	pub blobs_checked: u32,
+	/// Problems found.
+	pub issues: Vec<Issue>,
+}
+
+impl Report {
+	/// Records a pruned block, merging it with the previous issue if that covers the block
+	/// before.
+	fn pruned(&mut self, block: BlockNumber, state: bool) {
+		match (self.issues.last_mut(), state) {
+			(Some(Issue::StatePruned { to, .. }), true)
+			| (Some(Issue::BodyPruned { to, .. }), false)
+				if *to + 1 == block =>
+			{
+				*to = block
+			},
+			(_, true) => self.issues.push(Issue::StatePruned { from: block, to: block }),
+			(_, false) => self.issues.push(Issue::BodyPruned { from: block, to: block }),
+		}
+	}
+}
+
+impl std::fmt::Display for Issue {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::MissingBlock { block } => write!(f, "#{}: block not found", block),
+			Self::BodyPruned { from, to } => write!(f, "#{}..=#{}: block bodies pruned", from, to),
+			Self::StatePruned { from, to } => write!(f, "#{}..=#{}: state pruned", from, to),
+			Self::MissingBlob { block, index } => {
+				write!(f, "#{} blob {}: uploaded but missing from storage", block, index)
+			},
+			Self::Mismatch { block, index, expected_len, stored_len } => write!(
+				f,
+				"#{} blob {}: stored blob ({} bytes) differs from uploaded blob ({} bytes)",
+				block, index, stored_len, expected_len
+			),
+			Self::Unaccounted { block, index } => {
+				write!(f, "#{} blob {}: not uploaded by a top-level extrinsic", block, index)
+			},
+			Self::HashMismatch { block, index } => {
+				write!(f, "#{} blob {}: stored blob doesn't match its stored hash", block, index)
+			},
+			Self::CommitmentMismatch { block, index } => write!(
+				f,
+				"#{} blob {}: stored blob doesn't match its stored chunk commitment",
+				block, index
+			),
+			Self::Undecodable { block, reason } => write!(f, "#{}: {}", block, reason),
+		}
+	}
+}
+
+impl CheckBlobsCmd {
+	/// Run the command.
+	pub fn run(&self, client: &FullClient) -> sc_cli::Result<()> {
+		let to = self.to.unwrap_or_else(|| client.info().best_number);
+		let mut report = Report { from: self.from, to, ..Default::default() };
+
+		for number in self.from..=to {
+			check_block(client, number, &mut report)?;
+		}
+
+		if self.json {
+			let json = serde_json::to_string_pretty(&report)
+				.map_err(|e| format!("Failed to serialize report: {}", e))?;
+			println!("{}", json);
+		} else {
+			for issue in &report.issues {
+				println!("{}", issue);
+			}
+			println!(
+				"Checked {} blobs in {} of {} blocks (#{}..=#{}), {} issues found",
+				report.blobs_checked,
+				report.blocks_checked,
+				to.saturating_sub(self.from) + 1,
+				self.from,
+				to,
+				report.issues.len()
+			);
+		}
+
+		if report.issues.is_empty() {
+			Ok(())
+		} else {
+			Err(format!("{} issues found", report.issues.len()).into())
+		}
+	}
+}
+
+fn check_block(
+	client: &FullClient,
+	number: BlockNumber,
+	report: &mut Report,
+) -> sc_cli::Result<()> {
+	let Some(hash) = client.hash(number)? else {
+		report.issues.push(Issue::MissingBlock { block: number });
+		return Ok(());
+	};
+	let Some(body) = client.block_body(hash)? else {
+		report.pruned(number, false);
+		return Ok(());
+	};
+	let (Ok(stored), Ok(events)) = (
+		client.storage(hash, &blobs_key(number)),
+		client.storage(
+			hash,
+			&StorageKey(frame_system::Events::<runtime::Runtime>::hashed_key().to_vec()),
+		),
+	) else {
+		report.pruned(number, true);
+		return Ok(());
+	};
+
+	let undecodable = |reason: String| Issue::Undecodable { block: number, reason };
+	let stored = match stored.map(|data| Vec::<Vec<u8>>::decode(&mut &data.0[..])).transpose() {
+		Ok(stored) => stored.unwrap_or_default(),
+		Err(e) => {
+			report.issues.push(undecodable(format!("invalid Blobs entry: {}", e)));
+			return Ok(());
+		},
+	};
+	let events = match events
+		.map(|data| Vec::<EventRecord<runtime::RuntimeEvent, Hash>>::decode(&mut &data.0[..]))
+		.transpose()
+	{
+		Ok(events) => events.unwrap_or_default(),
+		Err(e) => {
+			report.issues.push(undecodable(format!("invalid System::Events entry: {}", e)));
+			return Ok(());
+		},
+	};
+	let failed = |index: usize| {
+		events.iter().any(|record| {
+			record.phase == Phase::ApplyExtrinsic(index as u32) &&
+				matches!(
+					record.event,
+					runtime::RuntimeEvent::System(frame_system::Event::ExtrinsicFailed { .. })
+				)
+		})
+	};
+
+	let mut uploaded = Vec::new();
+	for (index, extrinsic) in body.iter().enumerate() {
+		let extrinsic = match runtime::UncheckedExtrinsic::decode(&mut &extrinsic.encode()[..]) {
+			Ok(extrinsic) => extrinsic,
+			Err(e) => {
+				report.issues.push(undecodable(format!("invalid extrinsic {}: {}", index, e)));
+				continue;
+			},
+		};
+		let blobs = blobs_of(extrinsic);
+		if !blobs.is_empty() && !failed(index) {
+			uploaded.extend(blobs);
+		}
+	}
+
+	report.blocks_checked += 1;
+	for index in 0..uploaded.len().max(stored.len()) {
+		let issue = match (uploaded.get(index), stored.get(index)) {
+			(Some(expected), Some(blob)) if expected == blob => None,
+			(Some(expected), Some(blob)) => Some(Issue::Mismatch {
+				block: number,
+				index: index as u32,
+				expected_len: expected.len(),
+				stored_len: blob.len(),
+			}),
+			(Some(_), None) => Some(Issue::MissingBlob { block: number, index: index as u32 }),
+			(None, _) => Some(Issue::Unaccounted { block: number, index: index as u32 }),
+		};
+		report.blobs_checked += 1;
+		report.issues.extend(issue);
+	}
+
+	// Compare the stored blobs with their stored hashes and chunk commitments
+	let hashes = match read_storage::<Vec<Hash>>(client, hash, blob_hashes_key(number)) {
+		Ok(hashes) => hashes.unwrap_or_default(),
+		Err(e) => {
+			report.issues.push(undecodable(format!("invalid BlobHashes entry: {}", e)));
+			return Ok(());
+		},
+	};
+	for index in 0..stored.len().max(hashes.len()) {
+		let blob = stored.get(index);
+		let index = index as u32;
+		if blob.map(|blob| BlakeTwo256::hash(blob)).as_ref() != hashes.get(index as usize) {
+			report.issues.push(Issue::HashMismatch { block: number, index });
+		}
+		let Some(blob) = blob else { continue };
+		let key = chunk_commitment_key(number, index);
+		let expected = blob_chunk_commitment::<BlakeTwo256>(blob);
+		let issue = match read_storage::<ChunkCommitment<Hash>>(client, hash, key) {
+			Ok(commitment) if commitment == Some(expected) => None,
+			Ok(_) => Some(Issue::CommitmentMismatch { block: number, index }),
+			Err(e) => Some(undecodable(format!("invalid BlobChunkCommitments entry: {}", e))),
+		};
+		report.issues.extend(issue);
+	}
+
+	Ok(())
+}
+
+/// Reads and decodes the storage entry `key` at block `hash`, `None` if there is none.
+fn read_storage<T: Decode>(
+	client: &FullClient,
+	hash: Hash,
+	key: StorageKey,
+) -> Result<Option<T>, String> {
+	client
+		.storage(hash, &key)
+		.map_err(|e| e.to_string())?
+		.map(|data| T::decode(&mut &data.0[..]).map_err(|e| e.to_string()))
+		.transpose()
+}
+
+/// Storage key of the `Blobs` entry of block `number`.
+fn blobs_key(number: BlockNumber) -> StorageKey {
+	StorageKey(pallet_blobmanager::Blobs::<runtime::Runtime>::hashed_key_for(number))
+}
+
+/// Storage key of the `BlobHashes` entry of block `number`.
+fn blob_hashes_key(number: BlockNumber) -> StorageKey {
+	StorageKey(pallet_blobmanager::BlobHashes::<runtime::Runtime>::hashed_key_for(number))
+}
+
+/// Storage key of the `BlobChunkCommitments` entry of blob `index` of block `number`.
+fn chunk_commitment_key(number: BlockNumber, index: u32) -> StorageKey {
+	StorageKey(pallet_blobmanager::BlobChunkCommitments::<runtime::Runtime>::hashed_key_for(
+		number, index,
+	))
+}
+
+impl CliConfiguration for CheckBlobsCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -7,6 +7,47 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	#[clap(flatten)]
+	pub blob: BlobParams,
+}
+
+/// Blob-specific node parameters.
+#[derive(Debug, Clone, clap::Args)]
+pub struct BlobParams {
+	/// Keep the blobs of finalized blocks in files under this directory and serve blob RPCs
+	/// from there.
+	#[arg(long, value_name = "PATH")]
+	pub blob_store_path: Option<std::path::PathBuf>,
+
+	/// Keep the blobs of finalized blocks in the node's database instead of a directory.
+	#[arg(long, conflicts_with = "blob_store_path")]
+	pub blob_store_db: bool,
+
+	/// Only keep the blobs of this many most recent finalized blocks, at least one. Defaults to
+	/// keeping all blobs.
+	#[arg(long, value_name = "BLOCKS", value_parser = clap::value_parser!(u32).range(1..))]
+	pub blob_store_retention: Option<blobchain_runtime::BlockNumber>,
+
+	/// Percentage of the normal block length reserved for blob transactions when authoring
+	/// blocks. Other transactions only use the reserved length if blobs leave it unused.
+	#[arg(
+		long,
+		value_name = "PERCENT",
+		default_value_t = 50,
+		value_parser = clap::value_parser!(u8).range(0..=100)
+	)]
+	pub blob_block_reservation: u8,
+
+	/// Maximum number of blob transactions in the transaction pool. Once exceeded, the blob
+	/// transactions with the lowest priority are evicted.
+	#[arg(long, value_name = "COUNT", default_value_t = 16)]
+	pub blob_pool_max_count: usize,
+
+	/// Maximum total size of the blob transactions in the transaction pool, in bytes. Once
+	/// exceeded, the blob transactions with the lowest priority are evicted.
+	#[arg(long, value_name = "BYTES", default_value_t = 8 * 1024 * 1024)]
+	pub blob_pool_max_bytes: usize,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -43,4 +84,14 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Upload a file as a blob through a running node.
+	UploadBlob(crate::blob_cmd::UploadBlobCmd),
+
+	/// Build, sign and submit a blob transaction in separate steps.
+	#[command(subcommand)]
+	BlobTx(crate::blob_cmd::BlobTxSubcommand),
+
+	/// Check stored blobs against the blocks that uploaded them.
+	CheckBlobs(crate::check_blobs::CheckBlobsCmd),
 }
@@ -197,8 +197,18 @@ pub fn run() -> sc_cli::Result<()> {
             let runner = cli.create_runner(cmd)?;
             runner.sync_run(|config| cmd.run::<Block>(&config))
         }
+        Some(Subcommand::UploadBlob(cmd)) => cmd.run(),
+        Some(Subcommand::BlobTx(cmd)) => cmd.run(),
+        Some(Subcommand::CheckBlobs(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| {
+                let PartialComponents { client, .. } = service::new_partial(&config)?;
+                cmd.run(&client)
+            })
+        }
         None => {
             let runner = cli.create_runner(&cli.run)?;
+            let blob_params = cli.blob.clone();
             runner.run_node_until_exit(|config| async move {
                 match config.network.network_backend {
                     sc_network::config::NetworkBackendType::Libp2p => service::new_full::<
@@ -206,10 +216,12 @@ pub fn run() -> sc_cli::Result<()> {
                             blobchain_runtime::opaque::Block,
                             <blobchain_runtime::opaque::Block as sp_runtime::traits::Block>::Hash,
                         >,
-                    >(config)
+                    >(
+                        config, blob_params
+                    )
                     .map_err(sc_cli::Error::Service),
                     sc_network::config::NetworkBackendType::Litep2p => {
-                        service::new_full::<sc_network::Litep2pNetworkBackend>(config)
+                        service::new_full::<sc_network::Litep2pNetworkBackend>(config, blob_params)
                             .map_err(sc_cli::Error::Service)
                     }
                 }
@@ -2,11 +2,21 @@
 #![warn(missing_docs)]
 
 mod benchmarking;
+mod blob_cmd;
+mod blob_metrics;
+mod blob_pool;
+mod blob_proposer;
+mod blob_protocol;
+mod blob_rpc;
+mod blob_store;
+mod blob_submit;
 mod chain_spec;
+mod check_blobs;
 mod cli;
 mod command;
 mod rpc;
 mod service;
+mod transaction;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -7,8 +7,10 @@
 
 use std::sync::Arc;
 
-use blobchain_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use crate::{blob_protocol::BlobFetcher, blob_store::BlobStore};
+use blobchain_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Nonce};
 use jsonrpsee::RpcModule;
+use sc_client_api::{Backend, BlockBackend, ProofProvider, StorageProvider};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -17,28 +19,42 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 pub use sc_rpc_api::DenyUnsafe;
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B> {
     /// The client instance to use.
     pub client: Arc<C>,
     /// Transaction pool instance.
     pub pool: Arc<P>,
+    /// The backend instance to use.
+    pub backend: Arc<B>,
+    /// Node-side blob store, if enabled.
+    pub blob_store: Option<Arc<BlobStore>>,
+    /// Fetcher of blobs from peers.
+    pub blob_fetcher: BlobFetcher,
     /// Whether to deny unsafe calls
     pub deny_unsafe: DenyUnsafe,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-    deps: FullDeps<C, P>,
+pub fn create_full<C, P, B>(
+    deps: FullDeps<C, P, B>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
     C: ProvideRuntimeApi<Block>,
     C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+    C: BlockBackend<Block> + ProofProvider<Block> + StorageProvider<Block, B>,
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: mmr_rpc::MmrRuntimeApi<Block, Hash, BlockNumber>,
     C::Api: BlockBuilder<Block>,
-    P: TransactionPool + 'static,
+    P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+    B: Backend<Block> + 'static,
 {
+    use crate::{
+        blob_rpc::{Blob, BlobApiServer},
+        blob_submit::{BlobSubmit, BlobSubmitApiServer},
+    };
+    use mmr_rpc::{Mmr, MmrApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -46,11 +62,34 @@ where
     let FullDeps {
         client,
         pool,
+        backend,
+        blob_store,
+        blob_fetcher,
         deny_unsafe,
     } = deps;
 
-    module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(BlobSubmit::new(client.clone(), pool).into_rpc())?;
+    module.merge(
+        Mmr::new(
+            client.clone(),
+            backend
+                .offchain_storage()
+                .ok_or("Backend doesn't provide an offchain storage")?,
+        )
+        .into_rpc(),
+    )?;
+    module.merge(
+        Blob::<_, _, B>::new(
+            client,
+            backend.offchain_storage(),
+            blob_store,
+            blob_fetcher,
+            deny_unsafe,
+        )
+        .into_rpc(),
+    )?;
 
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
@@ -1,5 +1,13 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
+use crate::{
+    blob_metrics::BlobMetrics,
+    blob_pool::{BlobLimitedPool, BlobPoolLimits},
+    blob_proposer::BlobPackingPool,
+    blob_protocol::{BlobFetcher, BlobRequestHandler},
+    blob_store::{BlobBackend, BlobStore, DbBackend, FsBackend},
+    cli::BlobParams,
+};
 use blobchain_runtime::{self, opaque::Block, RuntimeApi};
 use futures::FutureExt;
 use sc_client_api::{Backend, BlockBackend};
@@ -9,6 +17,7 @@ use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpS
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_runtime::Percent;
 use std::{sync::Arc, time::Duration};
 
 pub(crate) type FullClient = sc_service::TFullClient<
@@ -16,7 +25,7 @@ pub(crate) type FullClient = sc_service::TFullClient<
     RuntimeApi,
     sc_executor::WasmExecutor<sp_io::SubstrateHostFunctions>,
 >;
-type FullBackend = sc_service::TFullBackend<Block>;
+pub(crate) type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
 /// The minimum period of blocks on which justifications will be
@@ -130,6 +139,7 @@ pub fn new_full<
     N: sc_network::NetworkBackend<Block, <Block as sp_runtime::traits::Block>::Hash>,
 >(
     config: Configuration,
+    blob_params: BlobParams,
 ) -> Result<TaskManager, ServiceError> {
     let sc_service::PartialComponents {
         client,
@@ -142,6 +152,14 @@ pub fn new_full<
         other: (block_import, grandpa_link, mut telemetry),
     } = new_partial(&config)?;
 
+    let transaction_pool = Arc::new(BlobLimitedPool::new(
+        transaction_pool,
+        BlobPoolLimits {
+            max_count: blob_params.blob_pool_max_count,
+            max_bytes: blob_params.blob_pool_max_bytes,
+        },
+    ));
+
     let mut net_config = sc_network::config::FullNetworkConfiguration::<
         Block,
         <Block as sp_runtime::traits::Block>::Hash,
@@ -172,6 +190,39 @@ pub fn new_full<
         Vec::default(),
     ));
 
+    let blob_backend: Option<Box<dyn BlobBackend>> = match blob_params.blob_store_path {
+        Some(ref path) => Some(Box::new(
+            FsBackend::open(path).map_err(ServiceError::Other)?,
+        )),
+        None if blob_params.blob_store_db => Some(Box::new(DbBackend::new(client.clone()))),
+        None => None,
+    };
+    let blob_store = blob_backend
+        .map(|backend| Arc::new(BlobStore::new(backend, blob_params.blob_store_retention)));
+
+    if let Some(blob_store) = &blob_store {
+        // Writes every blob to disk, and backfills all blocks missed while the node was down.
+        task_manager.spawn_handle().spawn_blocking(
+            "blob-store",
+            None,
+            blob_store.clone().run(client.clone()),
+        );
+    }
+
+    // Registered without a blob store too, serving blobs from the offchain index and the state.
+    // Reads blobs from disk, so it runs on a blocking task rather than a networking one.
+    let (blob_request_handler, blob_protocol_config) = BlobRequestHandler::new::<N>(
+        client.clone(),
+        backend.offchain_storage(),
+        blob_store.clone(),
+    );
+    net_config.add_request_response_protocol(blob_protocol_config);
+    task_manager.spawn_handle().spawn_blocking(
+        "blob-request-handler",
+        Some("networking"),
+        blob_request_handler.run(),
+    );
+
     let (network, system_rpc_tx, tx_handler_controller, network_starter, sync_service) =
         sc_service::build_network(sc_service::BuildNetworkParams {
             config: &config,
@@ -186,6 +237,8 @@ pub fn new_full<
             metrics,
         })?;
 
+    let blob_fetcher = BlobFetcher::new(network.clone(), sync_service.clone());
+
     if config.offchain_worker.enabled {
         task_manager.spawn_handle().spawn(
             "offchain-workers-runner",
@@ -207,6 +260,20 @@ pub fn new_full<
         );
     }
 
+    // Canonicalizes the MMR nodes of finalized blocks in the offchain database, where the MMR RPC
+    // reads them from to generate proofs.
+    if config.offchain_worker.indexing_enabled {
+        task_manager.spawn_essential_handle().spawn_blocking(
+            "mmr-gadget",
+            None,
+            mmr_gadget::MmrGadget::start(
+                client.clone(),
+                backend.clone(),
+                sp_mmr_primitives::INDEXING_PREFIX.to_vec(),
+            ),
+        );
+    }
+
     let role = config.role.clone();
     let force_authoring = config.force_authoring;
     let backoff_authoring_blocks: Option<()> = None;
@@ -214,14 +281,26 @@ pub fn new_full<
     let enable_grandpa = !config.disable_grandpa;
     let prometheus_registry = config.prometheus_registry().cloned();
 
+    if let Some(registry) = prometheus_registry.as_ref() {
+        task_manager.spawn_handle().spawn(
+            "blob-metrics",
+            None,
+            BlobMetrics::register(registry)?.run(client.clone(), transaction_pool.clone()),
+        );
+    }
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
+        let backend = backend.clone();
 
         Box::new(move |deny_unsafe, _| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
+                backend: backend.clone(),
+                blob_store: blob_store.clone(),
+                blob_fetcher: blob_fetcher.clone(),
                 deny_unsafe,
             };
             crate::rpc::create_full(deps).map_err(Into::into)
@@ -247,7 +326,11 @@ pub fn new_full<
         let proposer_factory = sc_basic_authorship::ProposerFactory::new(
             task_manager.spawn_handle(),
             client.clone(),
-            transaction_pool.clone(),
+            Arc::new(BlobPackingPool::new(
+                transaction_pool.clone(),
+                client.clone(),
+                Percent::from_percent(blob_params.blob_block_reservation),
+            )),
             prometheus_registry.as_ref(),
             telemetry.as_ref().map(|x| x.handle()),
         );
@@ -0,0 +1,89 @@
+//! Construction and signing of extrinsics outside of the runtime.
+//!
+//! Shared by the benchmarking extrinsic builders in [`super::benchmarking`] and the blob
+//! sub-commands in [`super::blob_cmd`].
+
+use blobchain_runtime as runtime;
+use runtime::{AccountId, Hash, Nonce};
+use sp_core::{crypto::KeyTypeId, sr25519, Encode, Pair};
+use sp_keystore::KeystorePtr;
+use sp_runtime::{generic::Era, traits::SignedExtension};
+
+/// Data covered by the signature which isn't itself part of the extrinsic.
+pub type AdditionalSigned = <runtime::SignedExtra as SignedExtension>::AdditionalSigned;
+
+/// Builds the signed extensions of a transaction sent with the given `nonce` and `era`.
+pub fn signed_extra(nonce: Nonce, era: Era) -> runtime::SignedExtra {
+	(
+		frame_system::CheckNonZeroSender::<runtime::Runtime>::new(),
+		frame_system::CheckSpecVersion::<runtime::Runtime>::new(),
+		frame_system::CheckTxVersion::<runtime::Runtime>::new(),
+		frame_system::CheckGenesis::<runtime::Runtime>::new(),
+		frame_system::CheckEra::<runtime::Runtime>::from(era),
+		frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
+		frame_system::CheckWeight::<runtime::Runtime>::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		pallet_blobmanager::CheckBlobUploads::<runtime::Runtime>::new(),
+	)
+}
+
+/// Builds the [`AdditionalSigned`] data matching [`signed_extra`].
+///
+/// `era_hash` is the hash of the block the era starts at, or the genesis hash for immortal
+/// transactions.
+pub fn additional_signed(
+	spec_version: u32,
+	transaction_version: u32,
+	genesis_hash: Hash,
+	era_hash: Hash,
+) -> AdditionalSigned {
+	((), spec_version, transaction_version, genesis_hash, era_hash, (), (), (), ())
+}
+
+/// Key used to sign a transaction.
+pub enum Signer {
+	/// A key pair held in memory, e.g. derived from a secret URI.
+	Pair(sr25519::Pair),
+	/// A key held in a keystore.
+	Keystore { keystore: KeystorePtr, key_type: KeyTypeId, public: sr25519::Public },
+}
+
+impl Signer {
+	/// Public key of the signer.
+	pub fn public(&self) -> sr25519::Public {
+		match self {
+			Self::Pair(pair) => pair.public(),
+			Self::Keystore { public, .. } => *public,
+		}
+	}
+
+	/// Account the signed transaction is sent from.
+	pub fn account_id(&self) -> AccountId {
+		AccountId::from(self.public())
+	}
+
+	/// Signs `payload` with the signer's key.
+	pub fn sign(&self, payload: &[u8]) -> Result<runtime::Signature, String> {
+		match self {
+			Self::Pair(pair) => Ok(pair.sign(payload).into()),
+			Self::Keystore { keystore, key_type, public } => keystore
+				.sr25519_sign(*key_type, public, payload)
+				.map_err(|e| format!("Keystore error: {}", e))?
+				.map(Into::into)
+				.ok_or_else(|| format!("Key {} not found in keystore", public)),
+		}
+	}
+}
+
+/// Signs `call` with `signer`, producing an extrinsic ready to be submitted.
+pub fn sign_extrinsic(
+	signer: &Signer,
+	call: runtime::RuntimeCall,
+	extra: runtime::SignedExtra,
+	additional: AdditionalSigned,
+) -> Result<runtime::UncheckedExtrinsic, String> {
+	let raw_payload = runtime::SignedPayload::from_raw(call.clone(), extra.clone(), additional);
+	let signature = raw_payload.using_encoded(|e| signer.sign(e))?;
+
+	Ok(runtime::UncheckedExtrinsic::new_signed(call, signer.account_id().into(), signature, extra))
+}
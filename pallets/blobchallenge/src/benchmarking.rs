@@ -0,0 +1,102 @@
+//! Benchmarking setup for pallet-blobchallenge
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as BlobChallenge;
+use alloc::vec;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{
+	fungible::{Inspect, Unbalanced},
+	tokens::Precision,
+	Get,
+};
+use frame_system::RawOrigin;
+use sp_runtime::Saturating;
+
+/// Funds `who` with enough to hold a few challenge deposits
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::ChallengeDeposit::get()
+		.saturating_mul(10u32.into())
+		.saturating_add(T::Currency::minimum_balance());
+	T::Currency::increase_balance(who, amount, Precision::Exact).expect("funding failed");
+}
+
+/// Stores `blob` and challenges it, or chunk `chunk` of it, as the whitelisted caller
+fn open_challenge<T: Config>(blob: Vec<u8>, chunk: Option<u32>) -> ChallengeId {
+	let (block_number, index) = T::BenchmarkHelper::store_blob(blob);
+	let challenger: T::AccountId = whitelisted_caller();
+	fund::<T>(&challenger);
+	let challenge = NextChallengeId::<T>::get();
+	BlobChallenge::<T>::challenge(
+		RawOrigin::Signed(challenger).into(),
+		ChallengeTarget { block_number, index, chunk },
+	)
+	.expect("challenge() fail");
+	challenge
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn challenge() {
+		// Setup code
+		let (block_number, index) = T::BenchmarkHelper::store_blob(vec![0u8; 32]);
+		let challenger: T::AccountId = whitelisted_caller();
+		fund::<T>(&challenger);
+		let target = ChallengeTarget { block_number, index, chunk: Some(0) };
+
+		#[extrinsic_call]
+		challenge(RawOrigin::Signed(challenger), target.clone());
+
+		// Verification code
+		assert_eq!(OpenChallenges::<T>::get(target), Some(0));
+	}
+
+	#[benchmark]
+	fn respond_with_blob(b: Linear<0, 1_048_576>) {
+		// Setup code
+		let blob = vec![0u8; b as usize];
+		let challenge = open_challenge::<T>(blob.clone(), None);
+		let responder: T::AccountId = account("responder", 0, 0);
+
+		#[extrinsic_call]
+		respond_with_blob(RawOrigin::Signed(responder), challenge, blob);
+
+		// Verification code
+		assert_eq!(Challenges::<T>::get(challenge), None);
+	}
+
+	#[benchmark]
+	fn respond_with_chunk(b: Linear<1, BLOB_CHUNK_SIZE>) {
+		// Setup code
+		let blob = vec![0u8; b as usize];
+		let challenge = open_challenge::<T>(blob.clone(), Some(0));
+		let responder: T::AccountId = account("responder", 0, 0);
+		let (chunk, proof) = chunk_proof::<T::Hashing>(&blob, 0).expect("chunk exists");
+
+		#[extrinsic_call]
+		respond_with_chunk(RawOrigin::Signed(responder), challenge, chunk, proof);
+
+		// Verification code
+		assert_eq!(Challenges::<T>::get(challenge), None);
+	}
+
+	#[benchmark]
+	fn settle_challenge() {
+		// Setup code
+		let challenge = open_challenge::<T>(vec![0u8; 32], Some(0));
+		let deadline = Challenges::<T>::get(challenge).expect("challenge exists").deadline;
+		frame_system::Pallet::<T>::set_block_number(deadline.saturating_add(1u32.into()));
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		settle_challenge(RawOrigin::Signed(caller), challenge);
+
+		// Verification code
+		assert_eq!(Challenges::<T>::get(challenge), None);
+	}
+
+	impl_benchmark_test_suite!(BlobChallenge, crate::mock::new_test_ext(), crate::mock::Test);
+}
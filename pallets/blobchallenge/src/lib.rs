@@ -0,0 +1,425 @@
+// SPDX-License-Identifier: Unlicense
+
+//! # BlobChallenge Pallet
+//! A pallet for challenging the availability of stored blobs
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! # Overview
+//!
+//! Blobs kept outside the state, through offchain indexing, in node-side blob stores or by
+//! storage providers, are only useful as long as someone can still serve them. This pallet backs
+//! that with a challenge game.
+//!
+//! Within [`Config::ChallengeWindow`] blocks after a blob got stored, anyone can challenge it
+//! with [`Pallet::challenge`], either as a whole or a single chunk of it, holding
+//! [`Config::ChallengeDeposit`] from their account.
+//!
+//! Anyone holding the data, typically the uploader or a storage provider, then has
+//! [`Config::ResponsePeriod`] blocks to answer on chain: with the whole blob through
+//! [`Pallet::respond_with_blob`], or with the challenged chunk and a proof of it against the
+//! chunk commitment of the blob through [`Pallet::respond_with_chunk`]. The deposit of an
+//! answered challenge goes to the responder.
+//!
+//! A challenge left unanswered is settled with [`Pallet::settle_challenge`] once the response
+//! period is over: the deposit is returned to the challenger and [`Config::OnBlobUnavailable`]
+//! is notified, which rewards the challenger, e.g. pallet-storageprovider slashes the storage
+//! providers of the blob and pays a share of it to the challenger. Without such a handler a
+//! challenger only gets their deposit back.
+
+// Ensure we're 'no_std' when compiling for WebAssembly.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+use codec::{Compact, Decode, Encode, MaxEncodedLen};
+use pallet_blobmanager::{ChunkCommitment, BLOB_CHUNK_SIZE};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Hash as HashT, RuntimeDebug};
+use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+pub use pallet::*;
+
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+/// Identifier of a challenge.
+pub type ChallengeId = u32;
+
+/// What a challenge is against.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct ChallengeTarget<BlockNumber> {
+	/// The block the blob is stored in
+	pub block_number: BlockNumber,
+	/// The index of the blob in the block
+	pub index: u32,
+	/// The challenged chunk of the blob, the whole blob if `None`
+	pub chunk: Option<u32>,
+}
+
+/// Open challenge.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct Challenge<AccountId, Balance, BlockNumber> {
+	/// The account that opened the challenge
+	pub challenger: AccountId,
+	/// The deposit held from the challenger
+	pub deposit: Balance,
+	/// What the challenge is against
+	pub target: ChallengeTarget<BlockNumber>,
+	/// The last block at which the challenge can be answered
+	pub deadline: BlockNumber,
+}
+
+/// Handler of blobs found unavailable.
+///
+/// Called within the weight of [`Pallet::settle_challenge`], so it should be cheap.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnBlobUnavailable<AccountId, BlockNumber> {
+	/// Called after the challenge of `challenger` against `target` went unanswered.
+	fn on_blob_unavailable(challenger: &AccountId, target: &ChallengeTarget<BlockNumber>);
+}
+
+/// Helper setting up blobs to challenge in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<BlockNumber> {
+	/// Stores `blob` in the current block through [`Config::Blobs`], returning its block number
+	/// and index.
+	fn store_blob(blob: Vec<u8>) -> (BlockNumber, u32);
+}
+
+/// Whether `proof` proves that `chunk` is chunk `index` of the blob with chunk commitment
+/// `commitment`, see [`pallet_blobmanager::blob_chunk_commitment`].
+pub fn verify_chunk_proof<H: HashT>(
+	commitment: &ChunkCommitment<H::Output>,
+	index: u32,
+	chunk: &[u8],
+	proof: &[Vec<u8>],
+) -> bool {
+	index < commitment.count &&
+		sp_trie::verify_trie_proof::<LayoutV0<H>, _, _, _>(
+			&commitment.root,
+			proof,
+			&[(Compact(index).encode(), Some(chunk))],
+		)
+		.is_ok()
+}
+
+/// Chunk `index` of `blob` and its proof against the chunk commitment of `blob`, verifiable
+/// with [`verify_chunk_proof`], if `blob` has such a chunk.
+pub fn chunk_proof<H: HashT>(blob: &[u8], index: u32) -> Option<(Vec<u8>, Vec<Vec<u8>>)> {
+	let chunks = blob.chunks(BLOB_CHUNK_SIZE as usize).collect::<Vec<_>>();
+	let chunk = chunks.get(index as usize)?.to_vec();
+
+	let mut db = MemoryDB::<H>::default();
+	let mut root = Default::default();
+	{
+		let mut trie = TrieDBMutBuilder::<LayoutV0<H>>::new(&mut db, &mut root).build();
+		for (i, chunk) in chunks.iter().enumerate() {
+			trie.insert(&Compact(i as u32).encode(), chunk).ok()?;
+		}
+	}
+	let proof =
+		sp_trie::generate_trie_proof::<LayoutV0<H>, _, _, _>(&db, root, &[Compact(index).encode()])
+			.ok()?;
+	Some((chunk, proof))
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, MutateHold},
+			tokens::{Fortitude, Precision, Restriction},
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_blobmanager::BlobProvider;
+	use sp_runtime::Saturating;
+
+	/// Balance of [`Config::Currency`].
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// [`Challenge`] of the runtime.
+	pub type ChallengeOf<T> =
+		Challenge<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching hold reason
+		type RuntimeHoldReason: From<HoldReason>;
+		type WeightInfo: WeightInfo;
+		/// Currency holding challenge deposits
+		type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+		/// Provider of the challenged blobs, typically pallet-blobmanager
+		type Blobs: BlobProvider<BlockNumberFor<Self>, Self::Hash>;
+		/// The deposit held from challengers, going to the responder of an answered challenge
+		type ChallengeDeposit: Get<BalanceOf<Self>>;
+		/// The number of blocks after storing a blob during which it can be challenged
+		type ChallengeWindow: Get<BlockNumberFor<Self>>;
+		/// The number of blocks after opening a challenge during which it can be answered
+		type ResponsePeriod: Get<BlockNumberFor<Self>>;
+		/// Handler of blobs found unavailable, `()` does nothing
+		type OnBlobUnavailable: OnBlobUnavailable<Self::AccountId, BlockNumberFor<Self>>;
+		/// Helper setting up blobs to challenge in benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<BlockNumberFor<Self>>;
+	}
+
+	/// Reasons for holding funds
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit of an open challenge
+		ChallengeDeposit,
+	}
+
+	#[pallet::storage]
+	pub type NextChallengeId<T: Config> = StorageValue<_, ChallengeId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Challenges<T: Config> =
+		StorageMap<_, Twox64Concat, ChallengeId, ChallengeOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type OpenChallenges<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ChallengeTarget<BlockNumberFor<T>>,
+		ChallengeId,
+		OptionQuery,
+	>;
+
+	// Errors that can be returned by this pallet
+	#[pallet::error]
+	pub enum Error<T> {
+		// Challenged blob doesn't exist
+		BlobNotFound,
+		// Challenged chunk is beyond the end of the blob
+		ChunkOutOfRange,
+		// Blob was stored too long ago to be challenged
+		ChallengeWindowClosed,
+		// Target is already challenged
+		AlreadyChallenged,
+		// No challenge identifiers left
+		ChallengeIdOverflow,
+		// Challenge doesn't exist
+		ChallengeNotFound,
+		// Challenge can no longer be answered
+		ResponsePeriodOver,
+		// Challenge can still be answered
+		ResponsePeriodNotOver,
+		// Response doesn't match the challenged blob or chunk
+		InvalidResponse,
+	}
+
+	// Events that can be emitted
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Challenge opened, can be answered until block `deadline`
+		ChallengeOpened {
+			challenge: ChallengeId,
+			challenger: T::AccountId,
+			target: ChallengeTarget<BlockNumberFor<T>>,
+			deadline: BlockNumberFor<T>,
+		},
+		/// Challenge answered, the deposit went to the responder
+		ChallengeAnswered { challenge: ChallengeId, responder: T::AccountId },
+		/// Challenge went unanswered, the challenged blob or chunk is considered unavailable
+		BlobUnavailable { challenge: ChallengeId, target: ChallengeTarget<BlockNumberFor<T>> },
+	}
+
+	// Dispatchable functions
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Challenge the availability of a blob or one of its chunks, holding the deposit
+		/// Callable by anyone within the challenge window of the blob
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::challenge())]
+		pub fn challenge(
+			origin: OriginFor<T>,
+			target: ChallengeTarget<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let challenger = ensure_signed(origin)?;
+
+			// Verify target
+			let commitment = T::Blobs::chunk_commitment(target.block_number, target.index)
+				.ok_or(Error::<T>::BlobNotFound)?;
+			ensure!(
+				target.chunk.map_or(true, |chunk| chunk < commitment.count),
+				Error::<T>::ChunkOutOfRange
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now <= target.block_number.saturating_add(T::ChallengeWindow::get()),
+				Error::<T>::ChallengeWindowClosed
+			);
+			ensure!(!OpenChallenges::<T>::contains_key(&target), Error::<T>::AlreadyChallenged);
+
+			// Allocate challenge identifier
+			let challenge = NextChallengeId::<T>::get();
+			let next = challenge.checked_add(1).ok_or(Error::<T>::ChallengeIdOverflow)?;
+			NextChallengeId::<T>::put(next);
+
+			// Hold deposit
+			let deposit = T::ChallengeDeposit::get();
+			T::Currency::hold(&HoldReason::ChallengeDeposit.into(), &challenger, deposit)?;
+
+			// Open challenge
+			let deadline = now.saturating_add(T::ResponsePeriod::get());
+			OpenChallenges::<T>::insert(&target, challenge);
+			Challenges::<T>::insert(
+				challenge,
+				Challenge {
+					challenger: challenger.clone(),
+					deposit,
+					target: target.clone(),
+					deadline,
+				},
+			);
+
+			// Emit ChallengeOpened event
+			Self::deposit_event(Event::ChallengeOpened { challenge, challenger, target, deadline });
+
+			Ok(())
+		}
+
+		/// Answer challenge `challenge` with the whole challenged blob
+		/// Callable by anyone until the deadline of the challenge
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::respond_with_blob(blob.len() as u32))]
+		pub fn respond_with_blob(
+			origin: OriginFor<T>,
+			challenge: ChallengeId,
+			blob: Vec<u8>,
+		) -> DispatchResult {
+			let responder = ensure_signed(origin)?;
+			let open = Self::answerable(challenge)?;
+
+			// Verify blob
+			let target = &open.target;
+			ensure!(
+				T::Blobs::blob_hash(target.block_number, target.index) ==
+					Some(T::Hashing::hash(&blob)),
+				Error::<T>::InvalidResponse
+			);
+
+			Self::answer(challenge, open, responder)
+		}
+
+		/// Answer chunk challenge `challenge` with the challenged chunk and its proof against the
+		/// chunk commitment of the blob
+		/// Callable by anyone until the deadline of the challenge
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::respond_with_chunk(
+			proof.iter().fold(chunk.len(), |len, node| len.saturating_add(node.len())) as u32
+		))]
+		pub fn respond_with_chunk(
+			origin: OriginFor<T>,
+			challenge: ChallengeId,
+			chunk: Vec<u8>,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let responder = ensure_signed(origin)?;
+			let open = Self::answerable(challenge)?;
+
+			// Verify chunk, challenges of whole blobs need the whole blob
+			let target = &open.target;
+			let index = target.chunk.ok_or(Error::<T>::InvalidResponse)?;
+			let commitment = T::Blobs::chunk_commitment(target.block_number, target.index)
+				.ok_or(Error::<T>::BlobNotFound)?;
+			ensure!(
+				verify_chunk_proof::<T::Hashing>(&commitment, index, &chunk, &proof),
+				Error::<T>::InvalidResponse
+			);
+
+			Self::answer(challenge, open, responder)
+		}
+
+		/// Settle unanswered challenge `challenge`, returning the deposit to the challenger
+		/// Callable by anyone after the deadline of the challenge
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::settle_challenge())]
+		pub fn settle_challenge(origin: OriginFor<T>, challenge: ChallengeId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let open = Challenges::<T>::get(challenge).ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() > open.deadline,
+				Error::<T>::ResponsePeriodNotOver
+			);
+
+			// Return deposit
+			T::Currency::release(
+				&HoldReason::ChallengeDeposit.into(),
+				&open.challenger,
+				open.deposit,
+				Precision::BestEffort,
+			)?;
+
+			// Close challenge
+			Self::close(challenge, &open.target);
+
+			// Notify handler
+			T::OnBlobUnavailable::on_blob_unavailable(&open.challenger, &open.target);
+
+			// Emit BlobUnavailable event
+			Self::deposit_event(Event::BlobUnavailable { challenge, target: open.target });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Challenge `challenge`, if it can still be answered
+		fn answerable(challenge: ChallengeId) -> Result<ChallengeOf<T>, DispatchError> {
+			let open = Challenges::<T>::get(challenge).ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= open.deadline,
+				Error::<T>::ResponsePeriodOver
+			);
+			Ok(open)
+		}
+
+		/// Close challenge `challenge`, answered by `responder`, paying the deposit to them
+		fn answer(
+			challenge: ChallengeId,
+			open: ChallengeOf<T>,
+			responder: T::AccountId,
+		) -> DispatchResult {
+			// Pay deposit to the responder
+			T::Currency::transfer_on_hold(
+				&HoldReason::ChallengeDeposit.into(),
+				&open.challenger,
+				&responder,
+				open.deposit,
+				Precision::BestEffort,
+				Restriction::Free,
+				Fortitude::Polite,
+			)?;
+
+			// Close challenge
+			Self::close(challenge, &open.target);
+
+			// Emit ChallengeAnswered event
+			Self::deposit_event(Event::ChallengeAnswered { challenge, responder });
+
+			Ok(())
+		}
+
+		/// Remove challenge `challenge` against `target`
+		fn close(challenge: ChallengeId, target: &ChallengeTarget<BlockNumberFor<T>>) {
+			Challenges::<T>::remove(challenge);
+			OpenChallenges::<T>::remove(target);
+		}
+	}
+}
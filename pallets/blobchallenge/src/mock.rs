@@ -0,0 +1,114 @@
+#![cfg(test)]
+use super::*;
+use crate as pallet_blobchallenge;
+use frame_support::{derive_impl, parameter_types, sp_runtime::BuildStorage, traits::ConstU64};
+use pallet_blobmanager::{blob_chunk_commitment, BlobMeta, BlobProvider};
+use sp_runtime::{
+	testing::H256,
+	traits::{BlakeTwo256, Hash as _},
+};
+use std::collections::BTreeMap;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+pub enum Test {
+	System: frame_system,
+	Balances: pallet_balances,
+	BlobChallenge: pallet_blobchallenge,
+}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// Deposit held from challengers
+pub const DEPOSIT: u64 = 10;
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type Blobs = MockBlobs;
+	type ChallengeDeposit = ConstU64<DEPOSIT>;
+	type ChallengeWindow = ConstU64<10>;
+	type ResponsePeriod = ConstU64<5>;
+	type OnBlobUnavailable = RecordUnavailable;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBlobs;
+}
+
+parameter_types! {
+	/// Blobs served by [`MockBlobs`], by `(block_number, index)`
+	pub static StoredBlobs: BTreeMap<(u64, u32), Vec<u8>> = BTreeMap::new();
+	/// Blobs reported to [`RecordUnavailable`], as `(challenger, target)`
+	pub static Unavailable: Vec<(u64, ChallengeTarget<u64>)> = Vec::new();
+}
+
+/// Stores `blob` at `index` in block `block_number` of [`MockBlobs`]
+pub fn store_blob(block_number: u64, index: u32, blob: Vec<u8>) {
+	StoredBlobs::mutate(|blobs| blobs.insert((block_number, index), blob));
+}
+
+/// Blob provider serving [`StoredBlobs`]
+pub struct MockBlobs;
+
+impl BlobProvider<u64, H256> for MockBlobs {
+	fn blob_count(block_number: u64) -> u32 {
+		StoredBlobs::get().keys().filter(|(number, _)| *number == block_number).count() as u32
+	}
+
+	fn blob(block_number: u64, index: u32) -> Option<Vec<u8>> {
+		StoredBlobs::get().get(&(block_number, index)).cloned()
+	}
+
+	fn blob_hash(block_number: u64, index: u32) -> Option<H256> {
+		Self::blob(block_number, index).map(|blob| BlakeTwo256::hash(&blob))
+	}
+
+	fn blob_meta(_block_number: u64, _index: u32) -> Option<BlobMeta<u64>> {
+		None
+	}
+
+	fn chunk_commitment(block_number: u64, index: u32) -> Option<ChunkCommitment<H256>> {
+		Self::blob(block_number, index).map(|blob| blob_chunk_commitment::<BlakeTwo256>(&blob))
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<u64> for MockBlobs {
+	fn store_blob(blob: Vec<u8>) -> (u64, u32) {
+		let block_number = System::block_number();
+		let index = Self::blob_count(block_number);
+		store_blob(block_number, index, blob);
+		(block_number, index)
+	}
+}
+
+/// Handler recording unavailable blobs in [`Unavailable`]
+pub struct RecordUnavailable;
+
+impl OnBlobUnavailable<u64, u64> for RecordUnavailable {
+	fn on_blob_unavailable(challenger: &u64, target: &ChallengeTarget<u64>) {
+		Unavailable::mutate(|unavailable| unavailable.push((*challenger, target.clone())));
+	}
+}
+
+// Build genesis storage according to the mock runtime
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	// Fund Challenger Account 1, Responder Account 2
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
+}
@@ -0,0 +1,232 @@
+#![cfg(test)]
+use super::*;
+use crate::{mock::*, ChallengeTarget, Challenges, Error, Event, NextChallengeId, OpenChallenges};
+use frame_support::{assert_noop, assert_ok};
+use pallet_blobmanager::{blob_chunk_commitment, BLOB_CHUNK_SIZE};
+use sp_runtime::traits::BlakeTwo256;
+
+/// Blob of two chunks, the second one partial
+fn two_chunk_blob() -> Vec<u8> {
+	(0..BLOB_CHUNK_SIZE + 100).map(|i| i as u8).collect()
+}
+
+/// Chunk `index` of `blob` and its proof against the chunk commitment of `blob`
+fn prove_chunk(blob: &[u8], index: u32) -> (Vec<u8>, Vec<Vec<u8>>) {
+	chunk_proof::<BlakeTwo256>(blob, index).unwrap()
+}
+
+fn target(chunk: Option<u32>) -> ChallengeTarget<u64> {
+	ChallengeTarget { block_number: 1, index: 0, chunk }
+}
+
+#[test]
+fn challenge_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		store_blob(1, 0, two_chunk_blob());
+
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(None)));
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(Some(1))));
+
+		assert_eq!(NextChallengeId::<Test>::get(), 2);
+		assert_eq!(OpenChallenges::<Test>::get(target(Some(1))), Some(1));
+		assert_eq!(
+			Challenges::<Test>::get(1),
+			Some(Challenge {
+				challenger: 1,
+				deposit: DEPOSIT,
+				target: target(Some(1)),
+				deadline: 7
+			})
+		);
+		assert_eq!(Balances::free_balance(1), 100 - 2 * DEPOSIT);
+		assert_eq!(Balances::reserved_balance(1), 2 * DEPOSIT);
+		System::assert_last_event(
+			Event::ChallengeOpened {
+				challenge: 1,
+				challenger: 1,
+				target: target(Some(1)),
+				deadline: 7,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn challenge_fails_for_invalid_target() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		store_blob(1, 0, two_chunk_blob());
+
+		assert_noop!(
+			BlobChallenge::challenge(
+				RuntimeOrigin::signed(1),
+				ChallengeTarget { block_number: 1, index: 1, chunk: None }
+			),
+			Error::<Test>::BlobNotFound
+		);
+		assert_noop!(
+			BlobChallenge::challenge(RuntimeOrigin::signed(1), target(Some(2))),
+			Error::<Test>::ChunkOutOfRange
+		);
+
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(None)));
+		assert_noop!(
+			BlobChallenge::challenge(RuntimeOrigin::signed(2), target(None)),
+			Error::<Test>::AlreadyChallenged
+		);
+
+		// Challenge window of the blob is over
+		System::set_block_number(12);
+		assert_noop!(
+			BlobChallenge::challenge(RuntimeOrigin::signed(1), target(Some(0))),
+			Error::<Test>::ChallengeWindowClosed
+		);
+	})
+}
+
+#[test]
+fn challenge_fails_without_deposit() {
+	new_test_ext().execute_with(|| {
+		store_blob(1, 0, two_chunk_blob());
+		assert!(BlobChallenge::challenge(RuntimeOrigin::signed(3), target(None)).is_err());
+		assert_eq!(NextChallengeId::<Test>::get(), 0);
+	})
+}
+
+#[test]
+fn respond_with_blob_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let blob = two_chunk_blob();
+		store_blob(1, 0, blob.clone());
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(None)));
+
+		assert_ok!(BlobChallenge::respond_with_blob(RuntimeOrigin::signed(2), 0, blob));
+
+		assert_eq!(Challenges::<Test>::get(0), None);
+		assert_eq!(OpenChallenges::<Test>::get(target(None)), None);
+		assert_eq!(Balances::free_balance(1), 100 - DEPOSIT);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 100 + DEPOSIT);
+		System::assert_last_event(Event::ChallengeAnswered { challenge: 0, responder: 2 }.into());
+
+		// Target can be challenged again
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(None)));
+	})
+}
+
+#[test]
+fn respond_with_blob_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let blob = two_chunk_blob();
+		store_blob(1, 0, blob.clone());
+
+		assert_noop!(
+			BlobChallenge::respond_with_blob(RuntimeOrigin::signed(2), 0, blob.clone()),
+			Error::<Test>::ChallengeNotFound
+		);
+
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(None)));
+		assert_noop!(
+			BlobChallenge::respond_with_blob(RuntimeOrigin::signed(2), 0, blob[1..].to_vec()),
+			Error::<Test>::InvalidResponse
+		);
+
+		// Response period is over
+		System::set_block_number(8);
+		assert_noop!(
+			BlobChallenge::respond_with_blob(RuntimeOrigin::signed(2), 0, blob),
+			Error::<Test>::ResponsePeriodOver
+		);
+	})
+}
+
+#[test]
+fn respond_with_chunk_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let blob = two_chunk_blob();
+		store_blob(1, 0, blob.clone());
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(Some(1))));
+
+		let (chunk, proof) = prove_chunk(&blob, 1);
+		assert_eq!(chunk.len(), 100);
+		assert_ok!(BlobChallenge::respond_with_chunk(RuntimeOrigin::signed(2), 0, chunk, proof));
+
+		assert_eq!(Challenges::<Test>::get(0), None);
+		assert_eq!(Balances::free_balance(2), 100 + DEPOSIT);
+		System::assert_last_event(Event::ChallengeAnswered { challenge: 0, responder: 2 }.into());
+	})
+}
+
+#[test]
+fn respond_with_chunk_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let blob = two_chunk_blob();
+		store_blob(1, 0, blob.clone());
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(None)));
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(Some(1))));
+
+		// Whole blob challenges need the whole blob
+		let (chunk, proof) = prove_chunk(&blob, 0);
+		assert_noop!(
+			BlobChallenge::respond_with_chunk(RuntimeOrigin::signed(2), 0, chunk.clone(), proof),
+			Error::<Test>::InvalidResponse
+		);
+
+		// Proof of another chunk
+		let (_, proof) = prove_chunk(&blob, 0);
+		assert_noop!(
+			BlobChallenge::respond_with_chunk(RuntimeOrigin::signed(2), 1, chunk, proof),
+			Error::<Test>::InvalidResponse
+		);
+	})
+}
+
+#[test]
+fn settle_challenge_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		store_blob(1, 0, two_chunk_blob());
+		assert_ok!(BlobChallenge::challenge(RuntimeOrigin::signed(1), target(Some(0))));
+
+		assert_noop!(
+			BlobChallenge::settle_challenge(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::ResponsePeriodNotOver
+		);
+
+		System::set_block_number(8);
+		assert_ok!(BlobChallenge::settle_challenge(RuntimeOrigin::signed(2), 0));
+
+		assert_eq!(Challenges::<Test>::get(0), None);
+		assert_eq!(OpenChallenges::<Test>::get(target(Some(0))), None);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Unavailable::get(), vec![(1, target(Some(0)))]);
+		System::assert_last_event(
+			Event::BlobUnavailable { challenge: 0, target: target(Some(0)) }.into(),
+		);
+
+		assert_noop!(
+			BlobChallenge::settle_challenge(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::ChallengeNotFound
+		);
+	})
+}
+
+#[test]
+fn chunk_proof_works() {
+	let blob = two_chunk_blob();
+	let commitment = blob_chunk_commitment::<BlakeTwo256>(&blob);
+	let (chunk, proof) = prove_chunk(&blob, 1);
+
+	assert!(verify_chunk_proof::<BlakeTwo256>(&commitment, 1, &chunk, &proof));
+	assert!(!verify_chunk_proof::<BlakeTwo256>(&commitment, 0, &chunk, &proof));
+	assert!(!verify_chunk_proof::<BlakeTwo256>(&commitment, 1, &chunk[1..], &proof));
+	assert!(!verify_chunk_proof::<BlakeTwo256>(&commitment, 2, &chunk, &proof));
+	assert_eq!(chunk_proof::<BlakeTwo256>(&blob, 2), None);
+}
@@ -0,0 +1,191 @@
+//! Weights for `pallet_blobchallenge`
+//!
+//! None of these weights come from a benchmark run yet. They are estimated by hand from the
+//! storage accesses listed above each of them, until the benchmarks in `benchmarking.rs` are run
+//! on reference hardware. The responses scale with the size `b` of the blob or chunk proof they
+//! hash.
+//!
+//! `settle_challenge` includes [`Config::OnBlobUnavailable`](crate::Config::OnBlobUnavailable)
+//! as the runtime configures it: pallet-storageprovider slashing the 3 storage providers of the
+//! blob, at 40_000_000 picoseconds for each of the two transfers from their bonds.
+
+// Command to regenerate:
+// target/release/blobchain-node
+// benchmark
+// pallet
+// --runtime
+// target/release/wbuild/blobchain-runtime/blobchain_runtime.compact.compressed.wasm
+// --genesis-builder=runtime
+// --pallet
+// pallet_blobchallenge
+// --extrinsic
+// *
+// --template
+// ../../frame-weight-template.hbs
+// --output
+// pallets/blobchallenge/src/weights.rs
+// --steps
+// 20
+// --repeat
+// 50
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_blobchallenge`.
+pub trait WeightInfo {
+	fn challenge() -> Weight;
+	fn respond_with_blob(b: u32, ) -> Weight;
+	fn respond_with_chunk(b: u32, ) -> Weight;
+	fn settle_challenge() -> Weight;
+}
+
+/// Weights for `pallet_blobchallenge` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:1 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::NextChallengeId` (r:1 w:1)
+	/// Proof: `BlobChallenge::NextChallengeId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::Challenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	fn challenge() -> Weight {
+		Weight::from_parts(32_610_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobChallenge::Challenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:0)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 1048576]`.
+	fn respond_with_blob(b: u32, ) -> Weight {
+		Weight::from_parts(49_873_000, 6196)
+			.saturating_add(Weight::from_parts(1_283, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobChallenge::Challenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:1 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 270336]`.
+	fn respond_with_chunk(b: u32, ) -> Weight {
+		Weight::from_parts(53_217_000, 6196)
+			.saturating_add(Weight::from_parts(1_291, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobChallenge::Challenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:4 w:4)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:5 w:5)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::BlobProviders` (r:1 w:0)
+	/// Proof: `StorageProvider::BlobProviders` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:3 w:3)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn settle_challenge() -> Weight {
+		Weight::from_parts(282_105_000, 32008)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(14_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:1 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::NextChallengeId` (r:1 w:1)
+	/// Proof: `BlobChallenge::NextChallengeId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::Challenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	fn challenge() -> Weight {
+		Weight::from_parts(32_610_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobChallenge::Challenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:0)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 1048576]`.
+	fn respond_with_blob(b: u32, ) -> Weight {
+		Weight::from_parts(49_873_000, 6196)
+			.saturating_add(Weight::from_parts(1_283, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobChallenge::Challenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:1 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 270336]`.
+	fn respond_with_chunk(b: u32, ) -> Weight {
+		Weight::from_parts(53_217_000, 6196)
+			.saturating_add(Weight::from_parts(1_291, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobChallenge::Challenges` (r:1 w:1)
+	/// Proof: `BlobChallenge::Challenges` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:4 w:4)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(103), added: 2578, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:5 w:5)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BlobChallenge::OpenChallenges` (r:0 w:1)
+	/// Proof: `BlobChallenge::OpenChallenges` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::BlobProviders` (r:1 w:0)
+	/// Proof: `StorageProvider::BlobProviders` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:3 w:3)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn settle_challenge() -> Weight {
+		Weight::from_parts(282_105_000, 32008)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(14_u64))
+	}
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Unlicense
+
+//! Runtime APIs of the BlobManager pallet, returning the metadata of stored blobs and the state
+//! of channels.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_blobmanager::{BlobCodec, BlobMeta, Channel, ChannelId, ChannelMessage};
+
+sp_api::decl_runtime_apis! {
+	/// Metadata of the blobs stored by the BlobManager pallet.
+	pub trait BlobManagerApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// Returns the metadata of the blob stored at `index` in block `block_number`, if it was
+		/// uploaded with any.
+		fn blob_meta(block_number: BlockNumber, index: u32) -> Option<BlobMeta<BlockNumber>>;
+
+		/// Returns the metadata of the blobs stored in block `block_number`, along with their
+		/// indexes, ordered by index. Blobs uploaded without metadata are left out.
+		fn blob_metas(block_number: BlockNumber) -> Vec<(u32, BlobMeta<BlockNumber>)>;
+	}
+
+	/// Channels of the BlobManager pallet.
+	pub trait BlobChannelApi<AccountId, BlockNumber, Hash>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Returns the owner, last sequence number and accumulator of channel `channel`, if it
+		/// exists.
+		fn channel(channel: ChannelId) -> Option<Channel<AccountId, Hash>>;
+
+		/// Returns the messages of channel `channel` with sequence numbers from `from` on, at most
+		/// `count` of them.
+		fn channel_messages(
+			channel: ChannelId,
+			from: u64,
+			count: u32,
+		) -> Vec<(u64, ChannelMessage<BlockNumber, Hash>)>;
+	}
+}
@@ -6,7 +6,8 @@ use crate::Pallet as Template;
 use alloc::vec;
 use frame_benchmarking::v2::*;
 use frame_support::traits::Get;
-use frame_system::RawOrigin;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{RuntimeAppPublic, Saturating};
 
 #[benchmarks]
 mod benchmarks {
@@ -25,15 +26,15 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn upload_blob() {
+	fn upload_blob(b: Linear<0, { <T as Config>::MaxBlobSize::get() }>) {
 		// Setup code
 		// Set Uploader
 		let uploader: T::AccountId = whitelisted_caller();
 		Template::<T>::set_uploader(RawOrigin::Root.into(), uploader.clone())
 			.expect("set_uploader() fail");
 
-		// Prepare blob to upload
-		let blob = vec![0u8; <T as Config>::MaxBlobSize::get() as usize];
+		// Prepare blob of `b` bytes to upload
+		let blob = vec![0u8; b as usize];
 
 		#[extrinsic_call]
 		upload_blob(RawOrigin::Signed(uploader), blob.clone());
@@ -45,5 +46,251 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn set_limits() {
+		// Setup code
+		let limits = BlobLimits {
+			max_blobs_per_block: <T as Config>::MaxBlobsPerBlock::get(),
+			max_blob_size: <T as Config>::MaxBlobSize::get(),
+		};
+
+		#[extrinsic_call]
+		set_limits(RawOrigin::Root, limits);
+
+		// Verification code
+		assert_eq!(Limits::<T>::get(), limits);
+	}
+
+	#[benchmark]
+	fn pause_uploads() {
+		// Setup code
+		// Schedule resumption, which is the worst case
+		let resume_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		pause_uploads(RawOrigin::Root, Some(resume_at));
+
+		// Verification code
+		assert!(Paused::<T>::get());
+	}
+
+	#[benchmark]
+	fn resume_uploads() {
+		// Setup code
+		// Pause with scheduled resumption, which has to be canceled
+		let resume_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Template::<T>::pause_uploads(RawOrigin::Root.into(), Some(resume_at))
+			.expect("pause_uploads() fail");
+
+		#[extrinsic_call]
+		resume_uploads(RawOrigin::Root);
+
+		// Verification code
+		assert!(!Paused::<T>::get());
+	}
+
+	#[benchmark]
+	fn propose_uploader() {
+		// Setup code
+		let uploader: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		propose_uploader(RawOrigin::Root, uploader.clone());
+
+		// Verification code
+		assert_eq!(PendingUploader::<T>::get().map(|pending| pending.uploader), Some(uploader));
+	}
+
+	#[benchmark]
+	fn accept_uploader() {
+		// Setup code
+		// Propose Uploader and wait for the handover delay
+		let uploader: T::AccountId = whitelisted_caller();
+		Template::<T>::propose_uploader(RawOrigin::Root.into(), uploader.clone())
+			.expect("propose_uploader() fail");
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + T::HandoverDelay::get());
+
+		#[extrinsic_call]
+		accept_uploader(RawOrigin::Signed(uploader.clone()));
+
+		// Verification code
+		assert_eq!(Uploader::<T>::get(), Some(uploader));
+	}
+
+	#[benchmark]
+	fn cancel_uploader_handover() {
+		// Setup code
+		// Propose Uploader
+		let uploader: T::AccountId = whitelisted_caller();
+		Template::<T>::propose_uploader(RawOrigin::Root.into(), uploader)
+			.expect("propose_uploader() fail");
+
+		#[extrinsic_call]
+		cancel_uploader_handover(RawOrigin::Root);
+
+		// Verification code
+		assert_eq!(PendingUploader::<T>::get(), None);
+	}
+
+	#[benchmark]
+	fn upload_blobs(
+		n: Linear<1, { <T as Config>::MaxBlobsPerBlock::get() }>,
+		b: Linear<0, { <T as Config>::MaxBlobSize::get() }>,
+	) {
+		// Setup code
+		// Set Uploader
+		let uploader: T::AccountId = whitelisted_caller();
+		Template::<T>::set_uploader(RawOrigin::Root.into(), uploader.clone())
+			.expect("set_uploader() fail");
+
+		// Prepare `n` blobs of `b` bytes in total to upload
+		let blobs = vec![vec![0u8; (b / n) as usize]; n as usize];
+
+		#[extrinsic_call]
+		upload_blobs(RawOrigin::Signed(uploader), blobs);
+
+		// Verification code
+		assert_eq!(Blobs::<T>::iter_values().next().expect("No blobs stored").len(), n as usize);
+	}
+
+	#[benchmark]
+	fn upload_blob_with_meta(b: Linear<0, { <T as Config>::MaxBlobSize::get() }>) {
+		// Setup code
+		// Set Uploader
+		let uploader: T::AccountId = whitelisted_caller();
+		Template::<T>::set_uploader(RawOrigin::Root.into(), uploader.clone())
+			.expect("set_uploader() fail");
+
+		// Prepare blob of `b` bytes and metadata to upload
+		let blob = vec![0u8; b as usize];
+		let meta = BlobMeta {
+			version: BLOB_META_VERSION,
+			codec: BlobCodec::Raw,
+			uncompressed_size: blob.len() as u32,
+			expires_at: Some(frame_system::Pallet::<T>::block_number() + 10u32.into()),
+			tag: vec![0u8; MAX_BLOB_TAG_LEN as usize].try_into().unwrap(),
+		};
+
+		#[extrinsic_call]
+		upload_blob_with_meta(RawOrigin::Signed(uploader), blob, meta.clone());
+
+		// Verification code
+		assert_eq!(BlobMetas::<T>::iter_values().next().expect("No blob metadata stored"), meta);
+	}
+
+	#[benchmark]
+	fn create_channel() {
+		// Setup code
+		let owner: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		create_channel(RawOrigin::Root, owner.clone());
+
+		// Verification code
+		assert_eq!(Channels::<T>::get(0).map(|channel| channel.owner), Some(owner));
+	}
+
+	#[benchmark]
+	fn upload_to_channel(b: Linear<0, { <T as Config>::MaxBlobSize::get() }>) {
+		// Setup code
+		// Create channel with a message, so the accumulator is updated
+		let owner: T::AccountId = whitelisted_caller();
+		Template::<T>::create_channel(RawOrigin::Root.into(), owner.clone())
+			.expect("create_channel() fail");
+		Template::<T>::upload_to_channel(RawOrigin::Signed(owner.clone()).into(), 0, vec![1u8])
+			.expect("upload_to_channel() fail");
+
+		// Prepare blob of `b` bytes to upload
+		let blob = vec![0u8; b as usize];
+
+		#[extrinsic_call]
+		upload_to_channel(RawOrigin::Signed(owner), 0, blob);
+
+		// Verification code
+		assert_eq!(Channels::<T>::get(0).map(|channel| channel.sequence), Some(2));
+	}
+
+	#[benchmark]
+	fn attest_availability() {
+		// Setup code
+		// Maximum number of authorities, one attestation short of the quorum
+		let authorities = (0..T::MaxAuthorities::get())
+			.map(|_| T::AuthorityId::generate_pair(None))
+			.collect::<Vec<_>>();
+		Authorities::<T>::put(BoundedVec::truncate_from(authorities.clone()));
+		let block_number: BlockNumberFor<T> = 1u32.into();
+		BlobHashes::<T>::insert(block_number, BoundedVec::truncate_from(vec![T::Hash::default()]));
+		let attested = (0..Template::<T>::availability_quorum().saturating_sub(1)).collect();
+		Availability::<T>::insert(block_number, BoundedVec::truncate_from(attested));
+		frame_system::Pallet::<T>::set_block_number(2u32.into());
+
+		// Attest as the last authority
+		let authority_index = T::MaxAuthorities::get() - 1;
+		let attestation = AvailabilityAttestation { block_number, authority_index };
+		let signature = attestation
+			.using_encoded(|payload| authorities[authority_index as usize].sign(&payload))
+			.expect("signing failed");
+
+		#[extrinsic_call]
+		attest_availability(RawOrigin::None, attestation, signature);
+
+		// Verification code
+		assert!(Template::<T>::is_available(block_number));
+	}
+
+	#[benchmark]
+	fn blob_root(n: Linear<0, { <T as Config>::MaxBlobsPerBlock::get() }>) {
+		// Setup code
+		// Store `n` blob hashes
+		let block_number: BlockNumberFor<T> = 1u32.into();
+		let hashes = (0..n).map(|i| T::Hashing::hash_of(&i)).collect::<Vec<_>>();
+		BlobHashes::<T>::insert(block_number, BoundedVec::truncate_from(hashes.clone()));
+
+		let root;
+		#[block]
+		{
+			root = Template::<T>::blob_root(block_number);
+		}
+
+		// Verification code
+		assert_eq!(root, blob_commitment_root::<T::Hashing>(&hashes));
+	}
+
+	#[benchmark]
+	fn set_authorities(n: Linear<0, { <T as Config>::MaxAuthorities::get() }>) {
+		// Setup code
+		let authorities = (0..n).map(|_| T::AuthorityId::generate_pair(None)).collect::<Vec<_>>();
+		let authorities = BoundedVec::truncate_from(authorities);
+
+		#[extrinsic_call]
+		set_authorities(RawOrigin::Root, authorities.clone());
+
+		// Verification code
+		assert_eq!(Authorities::<T>::get(), authorities);
+	}
+
+	#[benchmark]
+	fn prune_availability() {
+		// Setup code
+		// Attestations of every authority for the block whose attestation window just ended
+		let block_number: BlockNumberFor<T> = 1u32.into();
+		let attested = (0..T::MaxAuthorities::get()).collect();
+		Availability::<T>::insert(block_number, BoundedVec::truncate_from(attested));
+		AvailableBlocks::<T>::insert(block_number, ());
+		let now = block_number
+			.saturating_add(T::AttestationWindow::get())
+			.saturating_add(1u32.into());
+
+		#[block]
+		{
+			Template::<T>::prune_availability(now);
+		}
+
+		// Verification code
+		assert!(Availability::<T>::get(block_number).is_empty());
+		assert!(!Template::<T>::is_available(block_number));
+	}
+
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,130 @@
+//! Signed extension rejecting blob uploads while they are paused or with invalid payloads.
+
+use crate::{Call, Config, Paused, ValidateBlob, WrappedCalls};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::traits::IsSubType;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+
+/// Code of the [`InvalidTransaction::Custom`] error of uploads while paused.
+pub const UPLOADS_PAUSED: u8 = 0;
+
+/// Code of the [`InvalidTransaction::Custom`] error of uploads of blobs rejected by
+/// [`Config::BlobValidator`].
+pub const INVALID_BLOB: u8 = 1;
+
+/// Returns the blobs uploaded by `call`, in upload order, including by the calls nested in it
+/// according to [`Config::WrappedCalls`].
+pub fn uploaded_blobs<T: Config>(call: &<T as frame_system::Config>::RuntimeCall) -> Vec<&[u8]>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	let mut blobs = Vec::new();
+	collect_blobs::<T>(call, &mut blobs);
+	blobs
+}
+
+fn collect_blobs<'a, T: Config>(
+	call: &'a <T as frame_system::Config>::RuntimeCall,
+	blobs: &mut Vec<&'a [u8]>,
+) where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	match call.is_sub_type() {
+		Some(
+			Call::upload_blob { blob } |
+			Call::upload_blob_with_meta { blob, .. } |
+			Call::upload_to_channel { blob, .. },
+		) => blobs.push(blob),
+		Some(Call::upload_blobs { blobs: batch }) => blobs.extend(batch.iter().map(|b| &b[..])),
+		_ => T::WrappedCalls::wrapped(call)
+			.into_iter()
+			.for_each(|call| collect_blobs::<T>(call, blobs)),
+	}
+}
+
+/// Rejects blob upload transactions while uploads are paused, and those uploading blobs rejected
+/// by [`Config::BlobValidator`], so they don't enter the transaction pool or get included in
+/// blocks only to fail.
+///
+/// Uploads nested in the calls of [`Config::WrappedCalls`] are checked like direct ones.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckBlobUploads<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckBlobUploads<T> {
+	/// Create new `SignedExtension` to check blob uploads.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckBlobUploads<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> core::fmt::Debug for CheckBlobUploads<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "CheckBlobUploads")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckBlobUploads<T>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckBlobUploads";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let blobs = uploaded_blobs::<T>(call);
+		if blobs.is_empty() {
+			return Ok(ValidTransaction::default());
+		}
+		if Paused::<T>::get() {
+			return Err(InvalidTransaction::Custom(UPLOADS_PAUSED).into());
+		}
+		if !blobs.iter().all(|blob| T::BlobValidator::validate(who, blob)) {
+			return Err(InvalidTransaction::Custom(INVALID_BLOB).into());
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
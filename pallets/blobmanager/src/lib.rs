@@ -13,6 +13,61 @@
 //!
 //! Care should be taken not to exceed [PoV size per block]
 //!
+//! The number of blobs per block and the size of a blob are limited by [`Limits`], which the
+//! admin origin can change with [`Pallet::set_limits`] up to the [`Config::MaxBlobsPerBlock`] and
+//! [`Config::MaxBlobSize`] ceilings.
+//!
+//! The uploader is changed in two steps: the admin origin proposes a new uploader with
+//! [`Pallet::propose_uploader`], which confirms with [`Pallet::accept_uploader`] once
+//! [`Config::HandoverDelay`] has passed. [`Pallet::set_uploader`] still changes it at once.
+//!
+//! Uploads can be paused in an emergency with [`Pallet::pause_uploads`], optionally resuming
+//! automatically at a given block through the scheduler. While paused, [`CheckBlobUploads`]
+//! keeps upload transactions out of the transaction pool, including uploads nested in the calls
+//! of [`Config::WrappedCalls`].
+//!
+//! Rollups needing a strict order across blocks upload to channels, created by the admin origin
+//! with [`Pallet::create_channel`]. Blobs appended by the owner of a channel with
+//! [`Pallet::upload_to_channel`] get consecutive sequence numbers, and the channel keeps a hash
+//! chain over all of them ([`channel_accumulator`]), so clients can check that the messages they
+//! got since any checkpoint are complete.
+//!
+//! Other pallets can react to stored blobs through [`Config::OnBlobStored`], and read them
+//! through the [`BlobProvider`] implementation of [`Pallet`], without depending on the layout of
+//! [`Blobs`].
+//!
+//! Every uploaded blob has to pass [`Config::BlobValidator`], which lets runtimes enforce a
+//! payload format, e.g. of rollup batches, without forking the pallet.
+//!
+//! Blobs uploaded with [`Pallet::upload_blob_with_meta`] have a [`BlobMeta`] stored alongside
+//! them in [`BlobMetas`], describing how to decode them.
+//!
+//! The hashes of the blobs of every block are kept in [`BlobHashes`], committed to by
+//! [`Pallet::blob_root`] ([`blob_commitment_root`]). Runtimes put these commitments into the leaves
+//! of a Merkle Mountain Range ([`BlobMmrLeaf`]), so blobs of old blocks can be proven against a
+//! single recent header.
+//!
+//! Every blob is also committed to in chunks of [`BLOB_CHUNK_SIZE`] bytes, kept in
+//! [`BlobChunkCommitments`] ([`blob_chunk_commitment`]), so single chunks of a blob can be proven
+//! on chain, e.g. to answer data-availability challenges, without the whole blob.
+//!
+//! Every stored blob is also written to the offchain database through offchain indexing, keyed
+//! by its location ([`offchain_key_by_location`]) and by its hash ([`offchain_key_by_hash`]).
+//! Nodes running with `--enable-offchain-indexing true` therefore keep blobs around after the
+//! `Blobs` entries have been pruned from their state.
+//!
+//! Authorities attest that they hold the blobs of a block from their offchain worker, once the
+//! block is [`Config::AttestationDelay`] blocks old, by reading them back from the offchain
+//! database and submitting an [`AvailabilityAttestation`] signed with their
+//! [`Config::AuthorityId`] key in an unsigned transaction ([`Pallet::attest_availability`]).
+//! Runtimes use [`AuthorityId`] for it, a dedicated key of type [`KEY_TYPE`], so attestations are
+//! never signed with the consensus keys of the authorities. The attesting authorities are kept in
+//! [`Authorities`], set at genesis and by the admin origin with [`Pallet::set_authorities`]. The
+//! attestations of every block are collected in [`Availability`], and the blobs of a block are
+//! available once a [`Config::AvailabilityQuorum`] of the authorities attested them, recorded in
+//! [`AvailableBlocks`]. Both are dropped once the [`Config::AttestationWindow`] of the block is
+//! over, leaving the `BlobsAvailable` event as the record of its availability.
+//!
 //! [PoV size per block]: https://github.com/paritytech/polkadot-sdk/blob/c987da33935898cd5b2f8605d548bc48727c1815/polkadot/primitives/src/v8/mod.rs#L429
 
 // Ensure we're 'no_std' when compiling for WebAssembly.
@@ -20,34 +75,364 @@
 
 extern crate alloc;
 use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{ConstU32, Hash as HashT},
+	BoundedVec, KeyTypeId, RuntimeDebug, StateVersion,
+};
 
 pub use pallet::*;
 
 mod benchmarking;
+mod extension;
 mod mock;
 mod tests;
 pub mod weights;
 pub use weights::*;
 
+pub use extension::*;
+
+/// Prefix of the offchain index keys blobs are written under.
+pub const OFFCHAIN_PREFIX: &[u8] = b"blobmanager::blob::";
+
+/// Offchain index key of the blob stored at `index` in block `block_number`.
+///
+/// The value is the blob itself.
+pub fn offchain_key_by_location<BlockNumber: Encode>(
+	block_number: BlockNumber,
+	index: u32,
+) -> Vec<u8> {
+	(OFFCHAIN_PREFIX, b"location", block_number, index).encode()
+}
+
+/// Offchain index key of the blob with hash `hash`.
+///
+/// The value is the SCALE encoded `(block_number, index)` location of the blob, which can be
+/// resolved with [`offchain_key_by_location`].
+pub fn offchain_key_by_hash<Hash: Encode>(hash: Hash) -> Vec<u8> {
+	(OFFCHAIN_PREFIX, b"hash", hash).encode()
+}
+
+/// Log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::blobmanager";
+
+/// Name of the scheduler task resuming paused uploads.
+pub const RESUME_TASK_NAME: [u8; 32] = *b"blobmanager::resume_uploads\0\0\0\0\0";
+
+/// Validation of blob payloads before they are accepted.
+///
+/// Runs while validating upload transactions for the pool and again in every upload call, within
+/// the weight of the call, so it should be cheap.
+pub trait ValidateBlob<AccountId> {
+	/// Returns whether `blob` uploaded by `who` is well-formed.
+	fn validate(who: &AccountId, blob: &[u8]) -> bool;
+}
+
+/// Accepts every blob.
+impl<AccountId> ValidateBlob<AccountId> for () {
+	fn validate(_who: &AccountId, _blob: &[u8]) -> bool {
+		true
+	}
+}
+
+/// Access to the calls wrapped by other calls, e.g. the calls batched by `pallet_utility`.
+///
+/// Lets [`CheckBlobUploads`] and [`uploaded_blobs`] find uploads nested in wrapper calls, which
+/// would otherwise slip past the checks of the transaction pool.
+pub trait WrappedCalls<Call> {
+	/// Returns the calls wrapped by `call`, in dispatch order, or none if it doesn't wrap any.
+	fn wrapped(call: &Call) -> Vec<&Call>;
+}
+
+/// Wraps no calls, so only direct uploads are found.
+impl<Call> WrappedCalls<Call> for () {
+	fn wrapped(_call: &Call) -> Vec<&Call> {
+		Vec::new()
+	}
+}
+
+/// Handler of newly stored blobs.
+///
+/// Called within the weight of the upload call, so it should be cheap.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnBlobStored<AccountId, BlockNumber, Hash> {
+	/// Called after the blob of `size` bytes with hash `hash`, uploaded by `uploader`, got stored
+	/// at `index` in block `block_number`.
+	fn on_blob_stored(
+		uploader: &AccountId,
+		block_number: BlockNumber,
+		index: u32,
+		hash: Hash,
+		size: u32,
+	);
+}
+
+/// Read access to stored blobs and their metadata.
+pub trait BlobProvider<BlockNumber, Hash> {
+	/// Returns the number of blobs stored in block `block_number`.
+	fn blob_count(block_number: BlockNumber) -> u32;
+
+	/// Returns the blob stored at `index` in block `block_number`, if any.
+	fn blob(block_number: BlockNumber, index: u32) -> Option<Vec<u8>>;
+
+	/// Returns the hash of the blob stored at `index` in block `block_number`, if any.
+	fn blob_hash(block_number: BlockNumber, index: u32) -> Option<Hash>;
+
+	/// Returns the metadata of the blob stored at `index` in block `block_number`, if it was
+	/// uploaded with any.
+	fn blob_meta(block_number: BlockNumber, index: u32) -> Option<BlobMeta<BlockNumber>>;
+
+	/// Returns the commitment to the chunks of the blob stored at `index` in block
+	/// `block_number`, if any.
+	fn chunk_commitment(block_number: BlockNumber, index: u32) -> Option<ChunkCommitment<Hash>>;
+}
+
+/// Live limits of blob uploads.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct BlobLimits {
+	/// The maximum number of blobs stored per block
+	pub max_blobs_per_block: u32,
+	/// The maximum size of a single blob (in bytes)
+	pub max_blob_size: u32,
+}
+
+/// Current version of [`BlobMeta`].
+pub const BLOB_META_VERSION: u8 = 1;
+
+/// The maximum length of [`BlobMeta::tag`] (in bytes).
+pub const MAX_BLOB_TAG_LEN: u32 = 32;
+
+/// How the content of a blob is encoded.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub enum BlobCodec {
+	/// Raw bytes
+	Raw,
+	/// SCALE encoded data
+	Scale,
+	/// JSON document
+	Json,
+	/// Zstandard compressed data
+	Zstd,
+	/// Brotli compressed data
+	Brotli,
+}
+
+impl BlobCodec {
+	/// Whether blobs with this codec are compressed.
+	pub fn is_compressed(&self) -> bool {
+		matches!(self, Self::Zstd | Self::Brotli)
+	}
+}
+
+/// Description of a blob, stored alongside it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct BlobMeta<BlockNumber> {
+	/// The version of the metadata, [`BLOB_META_VERSION`]
+	pub version: u8,
+	/// How the content of the blob is encoded
+	pub codec: BlobCodec,
+	/// The size of the blob after decompression (in bytes)
+	pub uncompressed_size: u32,
+	/// The block after which the blob is no longer needed, if any
+	pub expires_at: Option<BlockNumber>,
+	/// Tag chosen by the uploader
+	pub tag: BoundedVec<u8, ConstU32<MAX_BLOB_TAG_LEN>>,
+}
+
+/// [`BlobMeta`] of the runtime.
+pub type BlobMetaFor<T> = BlobMeta<frame_system::pallet_prelude::BlockNumberFor<T>>;
+
+/// Identifier of a channel.
+pub type ChannelId = u32;
+
+/// Ordered log of blobs, e.g. the inbox of a rollup.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct Channel<AccountId, Hash> {
+	/// The account allowed to append to the channel
+	pub owner: AccountId,
+	/// The sequence number of the last message, zero if there is none
+	pub sequence: u64,
+	/// The hash chain over all messages, see [`channel_accumulator`]
+	pub accumulator: Hash,
+}
+
+/// Blob appended to a channel.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct ChannelMessage<BlockNumber, Hash> {
+	/// The block the blob is stored in
+	pub block_number: BlockNumber,
+	/// The index of the blob in the block
+	pub index: u32,
+	/// The hash of the blob
+	pub hash: Hash,
+}
+
+/// Accumulator of a channel after appending message `sequence`, the blob with hash `hash`, to a
+/// channel with accumulator `accumulator`.
+///
+/// The accumulator of a channel without messages is the default hash.
+pub fn channel_accumulator<H: HashT>(
+	accumulator: H::Output,
+	sequence: u64,
+	hash: H::Output,
+) -> H::Output {
+	H::hash_of(&(accumulator, sequence, hash))
+}
+
+/// Commitment to the blobs with hashes `hashes`, in order of their index.
+///
+/// This is the root of the ordered trie of `hashes`, keyed by the compact encoded index like the
+/// extrinsics root, so the hash of any blob can be proven against it.
+pub fn blob_commitment_root<H: HashT>(hashes: &[H::Output]) -> H::Output {
+	H::ordered_trie_root(
+		hashes.iter().map(|hash| hash.as_ref().to_vec()).collect(),
+		StateVersion::V0,
+	)
+}
+
+/// Leaf of a Merkle Mountain Range over the blobs of every block.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct BlobMmrLeaf<BlockNumber, Hash> {
+	/// The number of the block
+	pub block_number: BlockNumber,
+	/// The hash of the block
+	pub block_hash: Hash,
+	/// The commitment to the blobs stored in the block, see [`blob_commitment_root`]
+	pub blob_root: Hash,
+}
+
+/// The size of the chunks blobs are committed to (in bytes).
+pub const BLOB_CHUNK_SIZE: u32 = 256 * 1024;
+
+/// Commitment to the chunks of a blob.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct ChunkCommitment<Hash> {
+	/// The root of the ordered trie of the chunks
+	pub root: Hash,
+	/// The number of chunks
+	pub count: u32,
+}
+
+/// Commitment to the chunks of [`BLOB_CHUNK_SIZE`] bytes of `blob`, the last one possibly
+/// shorter.
+///
+/// Like [`blob_commitment_root`], the root is the root of the ordered trie of the chunks, keyed
+/// by the compact encoded chunk index.
+pub fn blob_chunk_commitment<H: HashT>(blob: &[u8]) -> ChunkCommitment<H::Output> {
+	let chunks = blob.chunks(BLOB_CHUNK_SIZE as usize).map(<[u8]>::to_vec).collect::<Vec<_>>();
+	ChunkCommitment {
+		count: chunks.len() as u32,
+		root: H::ordered_trie_root(chunks, StateVersion::V0),
+	}
+}
+
+/// Key type of the keys authorities sign availability attestations with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"blob");
+
+/// Application crypto of the keys authorities sign availability attestations with.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::app_crypto::{app_crypto, sr25519};
+
+	app_crypto!(sr25519, KEY_TYPE);
+}
+
+/// Key of an authority signing availability attestations, of type [`KEY_TYPE`].
+pub type AuthorityId = crypto::Public;
+
+/// Attestation of an authority that it holds the blobs of a block.
+///
+/// Signed with the [`Config::AuthorityId`] key of the authority, over its SCALE encoding.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct AvailabilityAttestation<BlockNumber> {
+	/// The block whose blobs the authority holds
+	pub block_number: BlockNumber,
+	/// The index of the authority in [`Authorities`]
+	pub authority_index: u32,
+}
+
+/// Uploader handover waiting for the proposed uploader to accept it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct PendingHandover<AccountId, BlockNumber> {
+	/// The proposed uploader
+	pub uploader: AccountId,
+	/// The first block at which the proposed uploader can accept
+	pub accept_from: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			schedule::{v3::Named as ScheduleNamed, DispatchTime, LOWEST_PRIORITY},
+			Bounded, BoundedInline,
+		},
+	};
+	use frame_system::{
+		offchain::{SendTransactionTypes, SubmitTransaction},
+		pallet_prelude::*,
+	};
+	use sp_runtime::{
+		offchain::StorageKind,
+		traits::{Hash, One},
+		BoundToRuntimeAppPublic, Perbill, RuntimeAppPublic, SaturatedConversion, Saturating,
+	};
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type WeightInfo: WeightInfo;
-		/// The maximum number of blobs stored per block
+		/// The ceiling of the maximum number of blobs stored per block
 		type MaxBlobsPerBlock: Get<u32>;
-		/// The maximum size of a single blob (in bytes)
+		/// The ceiling of the maximum size of a single blob (in bytes)
 		type MaxBlobSize: Get<u32>;
 		/// Origin allowed to set Uploader
 		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Origin allowed to pause and resume uploads
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The overarching call type
+		type RuntimeCall: From<Call<Self>> + Encode;
+		/// The overarching type of the origins of all pallets
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+		/// Scheduler resuming paused uploads
+		type Scheduler: ScheduleNamed<
+			BlockNumberFor<Self>,
+			<Self as Config>::RuntimeCall,
+			Self::PalletsOrigin,
+		>;
+		/// The number of blocks between proposing an uploader and it accepting, may be zero
+		type HandoverDelay: Get<BlockNumberFor<Self>>;
+		/// Validator of blob payloads, `()` accepts every blob
+		type BlobValidator: ValidateBlob<Self::AccountId>;
+		/// Calls wrapping other calls that uploads can be nested in, `()` only finds direct uploads
+		type WrappedCalls: WrappedCalls<<Self as frame_system::Config>::RuntimeCall>;
+		/// Handler of newly stored blobs, `()` does nothing
+		type OnBlobStored: OnBlobStored<Self::AccountId, BlockNumberFor<Self>, Self::Hash>;
+		/// Key authorities sign availability attestations with, typically [`AuthorityId`]
+		type AuthorityId: Member
+			+ Parameter
+			+ RuntimeAppPublic
+			+ Ord
+			+ MaxEncodedLen
+			+ MaybeSerializeDeserialize;
+		/// The maximum number of authorities
+		type MaxAuthorities: Get<u32>;
+		/// The share of the authorities whose attestations make the blobs of a block available
+		type AvailabilityQuorum: Get<Perbill>;
+		/// The number of blocks authorities wait before attesting the blobs of a block, giving it
+		/// time to get finalized
+		type AttestationDelay: Get<BlockNumberFor<Self>>;
+		/// The number of blocks after storing blobs during which their availability can be
+		/// attested, more than the attestation delay
+		type AttestationWindow: Get<BlockNumberFor<Self>>;
+		/// The priority of availability attestation transactions
+		type UnsignedPriority: Get<TransactionPriority>;
 	}
 
 	#[pallet::storage]
@@ -62,6 +447,91 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	// Default limits: the ceilings
+	#[pallet::type_value]
+	pub fn DefaultLimits<T: Config>() -> BlobLimits {
+		BlobLimits {
+			max_blobs_per_block: T::MaxBlobsPerBlock::get(),
+			max_blob_size: T::MaxBlobSize::get(),
+		}
+	}
+
+	#[pallet::storage]
+	pub type Limits<T: Config> = StorageValue<_, BlobLimits, ValueQuery, DefaultLimits<T>>;
+
+	#[pallet::storage]
+	pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	pub type PendingUploader<T: Config> =
+		StorageValue<_, PendingHandover<T::AccountId, BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextChannelId<T: Config> = StorageValue<_, ChannelId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Channels<T: Config> =
+		StorageMap<_, Twox64Concat, ChannelId, Channel<T::AccountId, T::Hash>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type ChannelMessages<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ChannelId,
+		Twox64Concat,
+		u64,
+		ChannelMessage<BlockNumberFor<T>, T::Hash>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	pub type BlobHashes<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::Hash, T::MaxBlobsPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	pub type BlobChunkCommitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		u32,
+		ChunkCommitment<T::Hash>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	pub type BlobMetas<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		u32,
+		BlobMetaFor<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	pub type Availability<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		BoundedVec<u32, T::MaxAuthorities>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	pub type Authorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AuthorityId, T::MaxAuthorities>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type AvailableBlocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, (), OptionQuery>;
+
 	// Errors that can be returned by this pallet
 	#[pallet::error]
 	pub enum Error<T> {
@@ -73,6 +543,46 @@ pub mod pallet {
 		ExceededMaxBlobsPerBlock,
 		// Trying to add too big blob
 		ExceededMaxBlobSize,
+		// Trying to set limits above the compile-time ceilings
+		LimitsAboveCeiling,
+		// Uploads are paused
+		UploadsPaused,
+		// Uploads are not paused
+		UploadsNotPaused,
+		// Trying to schedule resuming uploads at a past block
+		ResumeInPast,
+		// No uploader handover is pending
+		NoPendingHandover,
+		// Only callable by the proposed Uploader
+		CallableByProposedUploaderOnly,
+		// The handover delay hasn't passed yet
+		HandoverDelayNotPassed,
+		// Trying to upload an empty batch of blobs
+		EmptyBatch,
+		// Blob metadata version is not supported
+		UnsupportedMetaVersion,
+		// Uncompressed size in blob metadata differs from the size of an uncompressed blob
+		UncompressedSizeMismatch,
+		// Blob metadata expiry is not in the future
+		ExpiryInPast,
+		// Blob rejected by the blob validator
+		InvalidBlob,
+		// Channel doesn't exist
+		ChannelNotFound,
+		// Only callable by the owner of the channel
+		CallableByChannelOwnerOnly,
+		// No channel identifiers left
+		ChannelIdOverflow,
+		// The block can't be attested yet or anymore
+		AttestationOutsideWindow,
+		// No blobs stored in the attested block
+		NoBlobsToAttest,
+		// The attesting authority is not among the current authorities
+		UnknownAuthority,
+		// The authority already attested the block
+		AlreadyAttested,
+		// More attestations than authorities
+		TooManyAttestations,
 	}
 
 	// Events that can be emitted
@@ -80,19 +590,49 @@ pub mod pallet {
 	#[pallet::generate_deposit(fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Blob stored
-		BlobStored,
+		BlobStored { block_number: BlockNumberFor<T>, index: u32, hash: T::Hash },
+		/// Blob limits changed
+		LimitsSet { limits: BlobLimits },
+		/// Authorities attesting the availability of blobs set
+		AuthoritiesSet { authorities: u32 },
+		/// Uploads paused, until block `resume_at` if scheduled
+		UploadsPaused { resume_at: Option<BlockNumberFor<T>> },
+		/// Uploads resumed
+		UploadsResumed,
+		/// Uploader proposed, can accept from block `accept_from`
+		UploaderProposed { uploader: T::AccountId, accept_from: BlockNumberFor<T> },
+		/// Proposed Uploader accepted and became the Uploader
+		UploaderHandoverAccepted { uploader: T::AccountId },
+		/// Uploader handover canceled
+		UploaderHandoverCanceled { uploader: T::AccountId },
+		/// Channel created
+		ChannelCreated { channel: ChannelId, owner: T::AccountId },
+		/// Blob appended to a channel as message `sequence`
+		ChannelMessageAppended {
+			channel: ChannelId,
+			sequence: u64,
+			block_number: BlockNumberFor<T>,
+			index: u32,
+			hash: T::Hash,
+			accumulator: T::Hash,
+		},
+		/// Authority attested that it holds the blobs of a block
+		AvailabilityAttested { block_number: BlockNumberFor<T>, authority_index: u32 },
+		/// Blobs of a block reached the availability quorum of attestations
+		BlobsAvailable { block_number: BlockNumberFor<T>, attestations: u32 },
 	}
 
 	// Genesis config
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub uploader: Option<T::AccountId>,
+		pub authorities: Vec<T::AuthorityId>,
 	}
 
 	// Genesis config (default)
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { uploader: None }
+			Self { uploader: None, authorities: Vec::new() }
 		}
 	}
 
@@ -103,6 +643,74 @@ pub mod pallet {
 			if let Some(uploader) = &self.uploader {
 				Uploader::<T>::put(uploader);
 			}
+			let authorities = BoundedVec::try_from(self.authorities.clone())
+				.expect("Genesis authorities must not exceed MaxAuthorities");
+			Authorities::<T>::put(authorities);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// Drop attestations of the block whose attestation window is over
+			Self::prune_availability(now);
+			T::WeightInfo::prune_availability()
+		}
+
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			// Attest the blobs of the block the attestation delay ago, finalized by now
+			if now <= T::AttestationDelay::get() {
+				return
+			}
+			let block_number = now.saturating_sub(T::AttestationDelay::get());
+			if let Err(error) = Self::attest_held_blobs(block_number) {
+				log::debug!(
+					target: LOG_TARGET,
+					"Not attesting blobs of block {:?}: {}",
+					block_number,
+					error,
+				);
+			}
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::AttestationWindow::get() > T::AttestationDelay::get(),
+				"Attestation window must be longer than the attestation delay"
+			);
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::attest_availability { attestation, signature } = call else {
+				return InvalidTransaction::Call.into()
+			};
+
+			// Check attestation against the current state
+			let authority = Self::check_attestation(attestation).map_err(|error| match error {
+				Error::<T>::AttestationOutsideWindow
+					if attestation.block_number >= <frame_system::Pallet<T>>::block_number() =>
+					InvalidTransaction::Future,
+				Error::<T>::UnknownAuthority => InvalidTransaction::BadSigner,
+				_ => InvalidTransaction::Stale,
+			})?;
+
+			// Verify signature
+			let valid = attestation.using_encoded(|payload| authority.verify(&payload, signature));
+			if !valid {
+				return InvalidTransaction::BadProof.into()
+			}
+
+			ValidTransaction::with_tag_prefix("BlobAvailability")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((attestation.block_number, attestation.authority_index))
+				.longevity(T::AttestationWindow::get().saturated_into::<u64>())
+				.propagate(true)
+				.build()
 		}
 	}
 
@@ -126,35 +734,598 @@ pub mod pallet {
 		/// Upload new Blob
 		/// Only callable by Uploader
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::upload_blob())]
+		#[pallet::weight(T::WeightInfo::upload_blob(blob.len() as u32))]
 		pub fn upload_blob(origin: OriginFor<T>, blob: Vec<u8>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			// Only callable by Uploader
-			if let Some(uploader) = Uploader::<T>::get() {
-				ensure!(sender == uploader, Error::<T>::CallableByUploaderOnly);
-			} else {
-				return Err(Error::<T>::UploaderNotSet.into());
+			Self::store_blobs(sender, None, alloc::vec![(blob, None)])
+		}
+
+		/// Set new blob limits
+		/// Callable by AdminOrigin, limits can't exceed the compile-time ceilings
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_limits())]
+		pub fn set_limits(origin: OriginFor<T>, limits: BlobLimits) -> DispatchResult {
+			// Verify origin
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			// Verify limits
+			ensure!(
+				limits.max_blobs_per_block <= T::MaxBlobsPerBlock::get() &&
+					limits.max_blob_size <= T::MaxBlobSize::get(),
+				Error::<T>::LimitsAboveCeiling
+			);
+
+			// Update Limits
+			Limits::<T>::put(limits);
+
+			// Emit LimitsSet event
+			Self::deposit_event(Event::LimitsSet { limits });
+
+			Ok(())
+		}
+
+		/// Pause uploads, resuming automatically at block `resume_at` if set
+		/// Callable by PauseOrigin, pausing again replaces the scheduled resumption
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::pause_uploads())]
+		pub fn pause_uploads(
+			origin: OriginFor<T>,
+			resume_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			// Verify origin
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			// Replace scheduled resumption
+			Self::cancel_resume();
+			if let Some(resume_at) = resume_at {
+				ensure!(
+					resume_at > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::ResumeInPast
+				);
+				let call = <T as Config>::RuntimeCall::from(Call::<T>::resume_uploads {});
+				// The encoded call is a few bytes, well below the inline limit
+				let call = Bounded::Inline(BoundedInline::truncate_from(call.encode()));
+				T::Scheduler::schedule_named(
+					RESUME_TASK_NAME,
+					DispatchTime::At(resume_at),
+					None,
+					LOWEST_PRIORITY,
+					frame_system::RawOrigin::Root.into(),
+					call,
+				)?;
+			}
+
+			// Pause
+			Paused::<T>::put(true);
+
+			// Emit UploadsPaused event
+			Self::deposit_event(Event::UploadsPaused { resume_at });
+
+			Ok(())
+		}
+
+		/// Resume uploads
+		/// Callable by PauseOrigin, or by the scheduler at the block set by `pause_uploads`
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::resume_uploads())]
+		pub fn resume_uploads(origin: OriginFor<T>) -> DispatchResult {
+			// Verify origin
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(Paused::<T>::get(), Error::<T>::UploadsNotPaused);
+
+			// Resume, dropping any scheduled resumption
+			Self::cancel_resume();
+			Paused::<T>::kill();
+
+			// Emit UploadsResumed event
+			Self::deposit_event(Event::UploadsResumed);
+
+			Ok(())
+		}
+
+		/// Propose new Uploader, replacing any pending proposal
+		/// Callable by AdminOrigin
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::propose_uploader())]
+		pub fn propose_uploader(origin: OriginFor<T>, uploader: T::AccountId) -> DispatchResult {
+			// Verify origin
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			// Store proposal
+			let accept_from =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::HandoverDelay::get());
+			PendingUploader::<T>::put(PendingHandover { uploader: uploader.clone(), accept_from });
+
+			// Emit UploaderProposed event
+			Self::deposit_event(Event::UploaderProposed { uploader, accept_from });
+
+			Ok(())
+		}
+
+		/// Accept pending Uploader handover
+		/// Only callable by the proposed Uploader, once the handover delay has passed
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::accept_uploader())]
+		pub fn accept_uploader(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			// Only callable by the proposed Uploader after the delay
+			let pending = PendingUploader::<T>::get().ok_or(Error::<T>::NoPendingHandover)?;
+			ensure!(sender == pending.uploader, Error::<T>::CallableByProposedUploaderOnly);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= pending.accept_from,
+				Error::<T>::HandoverDelayNotPassed
+			);
+
+			// Update Uploader
+			PendingUploader::<T>::kill();
+			Uploader::<T>::put(&sender);
+
+			// Emit UploaderHandoverAccepted event
+			Self::deposit_event(Event::UploaderHandoverAccepted { uploader: sender });
+
+			Ok(())
+		}
+
+		/// Cancel pending Uploader handover
+		/// Callable by AdminOrigin, or by the proposed Uploader to decline
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::cancel_uploader_handover())]
+		pub fn cancel_uploader_handover(origin: OriginFor<T>) -> DispatchResult {
+			let pending = PendingUploader::<T>::get().ok_or(Error::<T>::NoPendingHandover)?;
+
+			// Verify origin
+			if let Err(origin) = T::AdminOrigin::try_origin(origin) {
+				let sender = ensure_signed(origin)?;
+				ensure!(sender == pending.uploader, DispatchError::BadOrigin);
+			}
+
+			// Drop proposal
+			PendingUploader::<T>::kill();
+
+			// Emit UploaderHandoverCanceled event
+			Self::deposit_event(Event::UploaderHandoverCanceled { uploader: pending.uploader });
+
+			Ok(())
+		}
+
+		/// Upload several Blobs at once, storing all of them or none
+		/// Only callable by Uploader
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::upload_blobs(
+			blobs.len() as u32,
+			blobs.iter().fold(0u32, |bytes, blob| bytes.saturating_add(blob.len() as u32)),
+		))]
+		pub fn upload_blobs(origin: OriginFor<T>, blobs: Vec<Vec<u8>>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!blobs.is_empty(), Error::<T>::EmptyBatch);
+
+			Self::store_blobs(sender, None, blobs.into_iter().map(|blob| (blob, None)).collect())
+		}
+
+		/// Upload new Blob along with its metadata
+		/// Only callable by Uploader
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::upload_blob_with_meta(blob.len() as u32))]
+		pub fn upload_blob_with_meta(
+			origin: OriginFor<T>,
+			blob: Vec<u8>,
+			meta: BlobMetaFor<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::store_blobs(sender, None, alloc::vec![(blob, Some(meta))])
+		}
+
+		/// Create new channel owned by `owner`
+		/// Callable by AdminOrigin
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::create_channel())]
+		pub fn create_channel(origin: OriginFor<T>, owner: T::AccountId) -> DispatchResult {
+			// Verify origin
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			// Allocate channel identifier
+			let channel = NextChannelId::<T>::get();
+			let next = channel.checked_add(1).ok_or(Error::<T>::ChannelIdOverflow)?;
+			NextChannelId::<T>::put(next);
+
+			// Create channel
+			Channels::<T>::insert(
+				channel,
+				Channel { owner: owner.clone(), sequence: 0, accumulator: T::Hash::default() },
+			);
+
+			// Emit ChannelCreated event
+			Self::deposit_event(Event::ChannelCreated { channel, owner });
+
+			Ok(())
+		}
+
+		/// Upload new Blob and append it to channel `channel`
+		/// Only callable by the owner of the channel
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::upload_to_channel(blob.len() as u32))]
+		pub fn upload_to_channel(
+			origin: OriginFor<T>,
+			channel: ChannelId,
+			blob: Vec<u8>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::store_blobs(sender, Some(channel), alloc::vec![(blob, None)])
+		}
+
+		/// Attest that an authority holds the blobs of a block
+		/// Only callable through unsigned transactions, carrying an attestation signed by the
+		/// authority
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::attest_availability())]
+		pub fn attest_availability(
+			origin: OriginFor<T>,
+			attestation: AvailabilityAttestation<BlockNumberFor<T>>,
+			// Verified in `validate_unsigned`
+			_signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
+		) -> DispatchResult {
+			// Verify origin
+			ensure_none(origin)?;
+
+			Self::check_attestation(&attestation)?;
+			let AvailabilityAttestation { block_number, authority_index } = attestation;
+
+			// Record attestation
+			let attestations = Availability::<T>::try_mutate(block_number, |attested| {
+				attested
+					.try_push(authority_index)
+					.map_err(|_| Error::<T>::TooManyAttestations)?;
+				Ok::<_, Error<T>>(attested.len() as u32)
+			})?;
+
+			// Emit AvailabilityAttested event
+			Self::deposit_event(Event::AvailabilityAttested { block_number, authority_index });
+
+			// Emit BlobsAvailable event, once when reaching the quorum
+			if attestations >= Self::availability_quorum() &&
+				!AvailableBlocks::<T>::contains_key(block_number)
+			{
+				AvailableBlocks::<T>::insert(block_number, ());
+				Self::deposit_event(Event::BlobsAvailable { block_number, attestations });
 			}
 
+			Ok(())
+		}
+
+		/// Set the authorities attesting the availability of blobs
+		/// Callable by AdminOrigin, attestations already recorded keep counting
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_authorities(authorities.len() as u32))]
+		pub fn set_authorities(
+			origin: OriginFor<T>,
+			authorities: BoundedVec<T::AuthorityId, T::MaxAuthorities>,
+		) -> DispatchResult {
+			// Verify origin
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			// Update Authorities
+			let count = authorities.len() as u32;
+			Authorities::<T>::put(authorities);
+
+			// Emit AuthoritiesSet event
+			Self::deposit_event(Event::AuthoritiesSet { authorities: count });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Store `blobs` uploaded by `sender` in the current block, along with their metadata if
+		/// any, all of them or none, appending them to `channel` if given
+		fn store_blobs(
+			sender: T::AccountId,
+			channel: Option<ChannelId>,
+			blobs: Vec<(Vec<u8>, Option<BlobMetaFor<T>>)>,
+		) -> DispatchResult {
+			// Not callable while paused
+			ensure!(!Paused::<T>::get(), Error::<T>::UploadsPaused);
+
+			// Only callable by Uploader, or by the owner when uploading to a channel
+			let mut channel = match channel {
+				Some(id) => {
+					let channel = Channels::<T>::get(id).ok_or(Error::<T>::ChannelNotFound)?;
+					ensure!(sender == channel.owner, Error::<T>::CallableByChannelOwnerOnly);
+					Some((id, channel))
+				},
+				None => {
+					let uploader = Uploader::<T>::get().ok_or(Error::<T>::UploaderNotSet)?;
+					ensure!(sender == uploader, Error::<T>::CallableByUploaderOnly);
+					None
+				},
+			};
+
+			// Check live limits, the bounded vectors only enforce the ceilings
+			let limits = Limits::<T>::get();
+			ensure!(
+				blobs.iter().all(|(blob, _)| blob.len() <= limits.max_blob_size as usize),
+				Error::<T>::ExceededMaxBlobSize
+			);
+
+			// Check payloads
+			ensure!(
+				blobs.iter().all(|(blob, _)| T::BlobValidator::validate(&sender, blob)),
+				Error::<T>::InvalidBlob
+			);
+
 			// Get current block number
 			let block_number = <frame_system::Pallet<T>>::block_number();
 
+			// Check metadata
+			for (blob, meta) in &blobs {
+				if let Some(meta) = meta {
+					ensure!(meta.version == BLOB_META_VERSION, Error::<T>::UnsupportedMetaVersion);
+					ensure!(
+						meta.codec.is_compressed() || meta.uncompressed_size as usize == blob.len(),
+						Error::<T>::UncompressedSizeMismatch
+					);
+					ensure!(
+						meta.expires_at.map_or(true, |expires_at| expires_at > block_number),
+						Error::<T>::ExpiryInPast
+					);
+				}
+			}
+
 			// Get vector for current block from storage
 			let mut blobs_outer_vec = Blobs::<T>::get(block_number);
+			ensure!(
+				blobs_outer_vec.len().saturating_add(blobs.len()) <=
+					limits.max_blobs_per_block as usize,
+				Error::<T>::ExceededMaxBlobsPerBlock
+			);
+
+			let mut hashes = BlobHashes::<T>::get(block_number);
+			let mut stored = Vec::with_capacity(blobs.len());
+			for (blob, meta) in blobs {
+				// Push new blob
+				let index = blobs_outer_vec.len() as u32;
+				let hash = T::Hashing::hash(&blob);
+				let size = blob.len() as u32;
+				let chunks = blob_chunk_commitment::<T::Hashing>(&blob);
+				blobs_outer_vec
+					.try_push(blob.try_into().map_err(|_| Error::<T>::ExceededMaxBlobSize)?)
+					.map_err(|_| Error::<T>::ExceededMaxBlobsPerBlock)?;
+				hashes.try_push(hash).map_err(|_| Error::<T>::ExceededMaxBlobsPerBlock)?;
 
-			// Push new blob
-			blobs_outer_vec
-				.try_push(blob.try_into().map_err(|_| Error::<T>::ExceededMaxBlobSize)?)
-				.map_err(|_| Error::<T>::ExceededMaxBlobsPerBlock)?;
+				// Index blob for offchain access
+				if let Some(blob) = blobs_outer_vec.last() {
+					sp_io::offchain_index::set(
+						&offchain_key_by_location(block_number, index),
+						blob,
+					);
+				}
+				sp_io::offchain_index::set(
+					&offchain_key_by_hash(hash),
+					&(block_number, index).encode(),
+				);
+				stored.push((index, hash, size));
+
+				// Store chunk commitment
+				BlobChunkCommitments::<T>::insert(block_number, index, chunks);
+
+				// Store metadata
+				if let Some(meta) = meta {
+					BlobMetas::<T>::insert(block_number, index, meta);
+				}
+			}
 
 			// Store Blobs
 			Blobs::<T>::insert(block_number, blobs_outer_vec);
+			BlobHashes::<T>::insert(block_number, hashes);
+
+			for (index, hash, size) in stored {
+				// Notify handler
+				T::OnBlobStored::on_blob_stored(&sender, block_number, index, hash, size);
+
+				// Emit BlobStored event
+				Self::deposit_event(Event::BlobStored { block_number, index, hash });
+
+				// Append to channel
+				if let Some((id, channel)) = &mut channel {
+					channel.sequence.saturating_inc();
+					channel.accumulator = channel_accumulator::<T::Hashing>(
+						channel.accumulator,
+						channel.sequence,
+						hash,
+					);
+					ChannelMessages::<T>::insert(
+						*id,
+						channel.sequence,
+						ChannelMessage { block_number, index, hash },
+					);
 
-			// Emit BlobStored event
-			Self::deposit_event(Event::BlobStored);
+					// Emit ChannelMessageAppended event
+					Self::deposit_event(Event::ChannelMessageAppended {
+						channel: *id,
+						sequence: channel.sequence,
+						block_number,
+						index,
+						hash,
+						accumulator: channel.accumulator,
+					});
+				}
+			}
+
+			// Store channel
+			if let Some((id, channel)) = channel {
+				Channels::<T>::insert(id, channel);
+			}
 
 			Ok(())
 		}
+
+		/// Metadata of the blob stored at `index` in block `block_number`, if it was uploaded with
+		/// any
+		pub fn blob_meta(block_number: BlockNumberFor<T>, index: u32) -> Option<BlobMetaFor<T>> {
+			BlobMetas::<T>::get(block_number, index)
+		}
+
+		/// Metadata of the blobs stored in block `block_number`, ordered by index
+		pub fn blob_metas(block_number: BlockNumberFor<T>) -> Vec<(u32, BlobMetaFor<T>)> {
+			let mut metas = BlobMetas::<T>::iter_prefix(block_number).collect::<Vec<_>>();
+			metas.sort_by_key(|(index, _)| *index);
+			metas
+		}
+
+		/// Commitment to the blobs stored in block `block_number`, see [`blob_commitment_root`]
+		pub fn blob_root(block_number: BlockNumberFor<T>) -> T::Hash {
+			blob_commitment_root::<T::Hashing>(&BlobHashes::<T>::get(block_number))
+		}
+
+		/// Messages of channel `channel` from sequence number `from` on, at most `count` of them
+		pub fn channel_messages(
+			channel: ChannelId,
+			from: u64,
+			count: u32,
+		) -> Vec<(u64, ChannelMessage<BlockNumberFor<T>, T::Hash>)> {
+			(from..from.saturating_add(count.into()))
+				.map_while(|sequence| {
+					ChannelMessages::<T>::get(channel, sequence).map(|message| (sequence, message))
+				})
+				.collect()
+		}
+
+		/// Number of attestations making the blobs of a block available, at least one
+		pub fn availability_quorum() -> u32 {
+			let authorities = Authorities::<T>::decode_len().unwrap_or_default() as u32;
+			T::AvailabilityQuorum::get().mul_ceil(authorities).max(1)
+		}
+
+		/// Whether the blobs of block `block_number` reached the availability quorum, known until
+		/// the attestation window of the block is over
+		pub fn is_available(block_number: BlockNumberFor<T>) -> bool {
+			AvailableBlocks::<T>::contains_key(block_number)
+		}
+
+		/// Drop the attestations of the block whose attestation window ended with the previous
+		/// block
+		pub(crate) fn prune_availability(now: BlockNumberFor<T>) {
+			if now <= T::AttestationWindow::get() {
+				return
+			}
+			let expired =
+				now.saturating_sub(T::AttestationWindow::get()).saturating_sub(One::one());
+			Availability::<T>::remove(expired);
+			AvailableBlocks::<T>::remove(expired);
+		}
+
+		/// Check that `attestation` can be recorded in the current block, returning the key of the
+		/// attesting authority
+		fn check_attestation(
+			attestation: &AvailabilityAttestation<BlockNumberFor<T>>,
+		) -> Result<T::AuthorityId, Error<T>> {
+			// Only blocks in the past, within the attestation window
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				attestation.block_number < now &&
+					now <= attestation.block_number.saturating_add(T::AttestationWindow::get()),
+				Error::<T>::AttestationOutsideWindow
+			);
+			ensure!(
+				BlobHashes::<T>::decode_len(attestation.block_number).unwrap_or_default() > 0,
+				Error::<T>::NoBlobsToAttest
+			);
+
+			// Only current authorities, once per block
+			let authority = Authorities::<T>::get()
+				.get(attestation.authority_index as usize)
+				.cloned()
+				.ok_or(Error::<T>::UnknownAuthority)?;
+			ensure!(
+				!Availability::<T>::get(attestation.block_number)
+					.contains(&attestation.authority_index),
+				Error::<T>::AlreadyAttested
+			);
+
+			Ok(authority)
+		}
+
+		/// Attest the blobs of block `block_number` with a local authority key, if the offchain
+		/// database holds all of them
+		///
+		/// Only called from the offchain worker.
+		fn attest_held_blobs(block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
+			let hashes = BlobHashes::<T>::get(block_number);
+			if hashes.is_empty() {
+				return Ok(())
+			}
+
+			// Find a local authority key
+			let authorities = Authorities::<T>::get();
+			let (authority_index, key) = T::AuthorityId::all()
+				.into_iter()
+				.find_map(|key| {
+					let index = authorities.iter().position(|authority| *authority == key)?;
+					Some((index as u32, key))
+				})
+				.ok_or("no local authority key")?;
+			if Availability::<T>::get(block_number).contains(&authority_index) {
+				return Ok(())
+			}
+
+			// Check that every blob is held, and is the one stored
+			for (index, hash) in hashes.into_iter().enumerate() {
+				let blob = sp_io::offchain::local_storage_get(
+					StorageKind::PERSISTENT,
+					&offchain_key_by_location(block_number, index as u32),
+				)
+				.ok_or("blob missing from the offchain database")?;
+				if T::Hashing::hash(&blob) != hash {
+					return Err("blob in the offchain database doesn't match its hash")
+				}
+			}
+
+			// Sign and submit attestation
+			let attestation = AvailabilityAttestation { block_number, authority_index };
+			let signature = attestation
+				.using_encoded(|payload| key.sign(&payload))
+				.ok_or("signing failed")?;
+			let call = Call::attest_availability { attestation, signature };
+			SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+				.map_err(|()| "submitting attestation failed")
+		}
+
+		/// Cancel the scheduled resumption of uploads, if any
+		fn cancel_resume() {
+			// Fails if nothing is scheduled, or if the resumption is being dispatched
+			let _ = T::Scheduler::cancel_named(RESUME_TASK_NAME);
+		}
+	}
+
+	impl<T: Config> BlobProvider<BlockNumberFor<T>, T::Hash> for Pallet<T> {
+		fn blob_count(block_number: BlockNumberFor<T>) -> u32 {
+			Blobs::<T>::decode_len(block_number).unwrap_or_default() as u32
+		}
+
+		fn blob(block_number: BlockNumberFor<T>, index: u32) -> Option<Vec<u8>> {
+			Blobs::<T>::get(block_number).into_iter().nth(index as usize).map(Into::into)
+		}
+
+		fn blob_hash(block_number: BlockNumberFor<T>, index: u32) -> Option<T::Hash> {
+			BlobHashes::<T>::get(block_number).get(index as usize).copied()
+		}
+
+		fn blob_meta(block_number: BlockNumberFor<T>, index: u32) -> Option<BlobMetaFor<T>> {
+			BlobMetas::<T>::get(block_number, index)
+		}
+
+		fn chunk_commitment(
+			block_number: BlockNumberFor<T>,
+			index: u32,
+		) -> Option<ChunkCommitment<T::Hash>> {
+			BlobChunkCommitments::<T>::get(block_number, index)
+		}
+	}
+
+	impl<T: Config> BoundToRuntimeAppPublic for Pallet<T> {
+		type Public = T::AuthorityId;
 	}
 }
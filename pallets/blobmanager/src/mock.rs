@@ -1,14 +1,27 @@
 #![cfg(test)]
 use super::*;
 use crate as pallet_blobmanager;
-use frame_support::{derive_impl, sp_runtime::BuildStorage, traits::ConstU32};
+use frame_support::{
+	derive_impl, parameter_types,
+	sp_runtime::BuildStorage,
+	traits::{ConstU32, ConstU64, EqualPrivilegeOnly},
+	weights::Weight,
+};
 use frame_system::EnsureRoot;
+use sp_runtime::{
+	testing::{TestXt, UintAuthorityId, H256},
+	Perbill,
+};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
 frame_support::construct_runtime!(
 pub enum Test {
 	System: frame_system,
+	Balances: pallet_balances,
+	Preimage: pallet_preimage,
+	Scheduler: pallet_scheduler,
+	Utility: pallet_utility,
 	BlobManager: pallet_blobmanager,
 }
 );
@@ -16,6 +29,58 @@ pub enum Test {
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
 	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<Self::AccountId>;
+	type Consideration = ();
+}
+
+parameter_types! {
+	pub const MaximumSchedulerWeight: Weight = Weight::MAX;
+	/// Blobs reported to [`RecordStoredBlobs`], as `(uploader, block_number, index, hash, size)`
+	pub static StoredBlobs: Vec<(u64, u64, u32, H256, u32)> = Vec::new();
+	/// Two of the three authorities
+	pub static AvailabilityQuorum: Perbill = Perbill::from_percent(66);
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl pallet_scheduler::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<Self::AccountId>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type WeightInfo = ();
+	type Preimages = Preimage;
+}
+
+impl pallet_utility::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
 }
 
 impl Config for Test {
@@ -27,13 +92,66 @@ impl Config for Test {
 	#[cfg(feature = "runtime-benchmarks")]
 	type MaxBlobSize = ConstU32<5242880>; // 5 MB
 	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type PauseOrigin = EnsureRoot<Self::AccountId>;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type HandoverDelay = ConstU64<3>;
+	type BlobValidator = RejectMarkedBlobs;
+	type WrappedCalls = UtilityCalls;
+	type OnBlobStored = RecordStoredBlobs;
+	type AuthorityId = UintAuthorityId;
+	type MaxAuthorities = ConstU32<4>;
+	type AvailabilityQuorum = AvailabilityQuorum;
+	type AttestationDelay = ConstU64<2>;
+	type AttestationWindow = ConstU64<10>;
+	type UnsignedPriority = ConstU64<100>;
+}
+
+/// First byte of the blobs rejected by [`RejectMarkedBlobs`]
+pub const INVALID_MARKER: u8 = 0xff;
+
+/// Blob validator rejecting blobs starting with [`INVALID_MARKER`]
+pub struct RejectMarkedBlobs;
+
+impl ValidateBlob<u64> for RejectMarkedBlobs {
+	fn validate(_who: &u64, blob: &[u8]) -> bool {
+		blob.first() != Some(&INVALID_MARKER)
+	}
+}
+
+/// The calls of `pallet_utility` wrapping other calls
+pub struct UtilityCalls;
+
+impl WrappedCalls<RuntimeCall> for UtilityCalls {
+	fn wrapped(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } | pallet_utility::Call::batch_all { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) => vec![&**call],
+			_ => Vec::new(),
+		}
+	}
+}
+
+/// Handler recording stored blobs in [`StoredBlobs`]
+pub struct RecordStoredBlobs;
+
+impl OnBlobStored<u64, u64, H256> for RecordStoredBlobs {
+	fn on_blob_stored(uploader: &u64, block_number: u64, index: u32, hash: H256, size: u32) {
+		StoredBlobs::mutate(|stored| stored.push((*uploader, block_number, index, hash, size)));
+	}
 }
 
 // Build genesis storage according to the mock runtime
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
-	// Set Admin to Account 1, Uploader Account 2
-	let genesis = pallet_blobmanager::GenesisConfig::<Test> { uploader: Some(2) };
+	// Set Admin to Account 1, Uploader Account 2, Authorities 10 to 12
+	let genesis = pallet_blobmanager::GenesisConfig::<Test> {
+		uploader: Some(2),
+		authorities: vec![UintAuthorityId(10), UintAuthorityId(11), UintAuthorityId(12)],
+	};
 	genesis.assimilate_storage(&mut t).unwrap();
 	t.into()
 }
@@ -1,7 +1,28 @@
 #![cfg(test)]
 use super::*;
-use crate::{mock::*, Blobs, Error, Event, Uploader};
-use frame_support::{assert_noop, assert_ok, pallet_prelude::DispatchError, traits::Get};
+use crate::{
+	blob_chunk_commitment, blob_commitment_root, mock::*, Authorities, Availability,
+	AvailabilityAttestation, AvailableBlocks, BlobCodec, BlobHashes, BlobLimits, BlobMeta,
+	BlobMetas, Blobs, Channel, ChannelMessage, ChannelMessages, Channels, ChunkCommitment, Error,
+	Event, Limits, NextChannelId, Paused, PendingHandover, PendingUploader, Uploader,
+	BLOB_CHUNK_SIZE, BLOB_META_VERSION,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchInfo,
+	pallet_prelude::{DispatchError, InvalidTransaction, TransactionSource},
+	traits::{schedule::v3::Named as ScheduleNamed, Get, OffchainWorker, OnInitialize},
+};
+use sp_core::offchain::{
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_runtime::{
+	offchain::StorageKind,
+	testing::{TestSignature, UintAuthorityId, H256},
+	traits::{BlakeTwo256, Dispatchable, Hash, SignedExtension, ValidateUnsigned},
+	Perbill, RuntimeAppPublic,
+};
 
 #[test]
 fn genesis_config_uploader() {
@@ -39,7 +60,9 @@ fn upload_blob_works() {
 		// Store blob
 		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), blob.clone()));
 		// Make sure event was deposited
-		System::assert_last_event(Event::BlobStored.into());
+		System::assert_last_event(
+			Event::BlobStored { block_number: 1, index: 0, hash: BlakeTwo256::hash(&blob) }.into(),
+		);
 		// Verify storage
 		assert_eq!(
 			Blobs::<Test>::iter_values().next().expect("No blobs stored").first(),
@@ -83,9 +106,11 @@ fn upload_blob_exceeds_blobs_per_block() {
 		let blob = vec![0u8; max_blob_size as usize];
 		// Store maximum number of blobs per block
 		let maxblobs: u32 = <Test as Config>::MaxBlobsPerBlock::get();
-		for _ in 0..maxblobs {
+		for index in 0..maxblobs {
 			assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), blob.clone()));
-			System::assert_last_event(Event::BlobStored.into());
+			System::assert_last_event(
+				Event::BlobStored { block_number: 1, index, hash: BlakeTwo256::hash(&blob) }.into(),
+			);
 		}
 		// Storing one more blob should fail
 		assert_noop!(
@@ -106,3 +131,1036 @@ fn upload_blob_exceeds_max_blob_size() {
 		);
 	})
 }
+
+#[test]
+fn upload_blob_indexes_offchain() {
+	let mut ext = new_test_ext();
+	let blob = vec![7u8; 32];
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), blob.clone()));
+	});
+	ext.persist_offchain_overlay();
+
+	let offchain_db = ext.offchain_db();
+	// Blob is indexed by its location
+	assert_eq!(offchain_db.get(&offchain_key_by_location(1u64, 1)), Some(blob.clone()));
+	// Hash resolves to the location
+	assert_eq!(
+		offchain_db.get(&offchain_key_by_hash(BlakeTwo256::hash(&blob))),
+		Some((1u64, 1u32).encode())
+	);
+}
+
+#[test]
+fn limits_default_to_ceilings() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			Limits::<Test>::get(),
+			BlobLimits {
+				max_blobs_per_block: <Test as Config>::MaxBlobsPerBlock::get(),
+				max_blob_size: <Test as Config>::MaxBlobSize::get(),
+			}
+		);
+	})
+}
+
+#[test]
+fn set_limits_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		let limits = BlobLimits { max_blobs_per_block: 2, max_blob_size: 64 };
+		assert_ok!(BlobManager::set_limits(RuntimeOrigin::root(), limits));
+		assert_eq!(Limits::<Test>::get(), limits);
+		System::assert_last_event(Event::LimitsSet { limits }.into());
+	})
+}
+
+#[test]
+fn set_limits_wrong_origin() {
+	new_test_ext().execute_with(|| {
+		let limits = BlobLimits { max_blobs_per_block: 2, max_blob_size: 64 };
+		assert_noop!(
+			BlobManager::set_limits(RuntimeOrigin::signed(1), limits),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn set_limits_above_ceiling() {
+	new_test_ext().execute_with(|| {
+		let max_blobs_per_block: u32 = <Test as Config>::MaxBlobsPerBlock::get();
+		let max_blob_size: u32 = <Test as Config>::MaxBlobSize::get();
+		assert_noop!(
+			BlobManager::set_limits(
+				RuntimeOrigin::root(),
+				BlobLimits { max_blobs_per_block: max_blobs_per_block + 1, max_blob_size }
+			),
+			Error::<Test>::LimitsAboveCeiling
+		);
+		assert_noop!(
+			BlobManager::set_limits(
+				RuntimeOrigin::root(),
+				BlobLimits { max_blobs_per_block, max_blob_size: max_blob_size + 1 }
+			),
+			Error::<Test>::LimitsAboveCeiling
+		);
+	})
+}
+
+#[test]
+fn upload_blob_respects_live_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::set_limits(
+			RuntimeOrigin::root(),
+			BlobLimits { max_blobs_per_block: 1, max_blob_size: 32 }
+		));
+		// Blob above the live size limit, but below the ceiling
+		assert_noop!(
+			BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 33]),
+			Error::<Test>::ExceededMaxBlobSize
+		);
+		// Second blob above the live count limit, but below the ceiling
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 32]));
+		assert_noop!(
+			BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 32]),
+			Error::<Test>::ExceededMaxBlobsPerBlock
+		);
+	})
+}
+
+#[test]
+fn pause_uploads_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_ok!(BlobManager::pause_uploads(RuntimeOrigin::root(), None));
+		assert!(Paused::<Test>::get());
+		System::assert_last_event(Event::UploadsPaused { resume_at: None }.into());
+		// Uploads fail while paused
+		assert_noop!(
+			BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 32]),
+			Error::<Test>::UploadsPaused
+		);
+	})
+}
+
+#[test]
+fn pause_uploads_wrong_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::pause_uploads(RuntimeOrigin::signed(1), None),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn pause_uploads_resume_in_past() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(
+			BlobManager::pause_uploads(RuntimeOrigin::root(), Some(5)),
+			Error::<Test>::ResumeInPast
+		);
+	})
+}
+
+#[test]
+fn resume_uploads_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_ok!(BlobManager::pause_uploads(RuntimeOrigin::root(), Some(3)));
+		assert_ok!(BlobManager::resume_uploads(RuntimeOrigin::root()));
+		assert!(!Paused::<Test>::get());
+		System::assert_last_event(Event::UploadsResumed.into());
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 32]));
+		// Scheduled resumption got canceled
+		assert!(<Scheduler as ScheduleNamed<u64, RuntimeCall, OriginCaller>>::next_dispatch_time(
+			RESUME_TASK_NAME
+		)
+		.is_err());
+	})
+}
+
+#[test]
+fn resume_uploads_not_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::resume_uploads(RuntimeOrigin::root()),
+			Error::<Test>::UploadsNotPaused
+		);
+	})
+}
+
+#[test]
+fn uploads_resume_at_scheduled_block() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_ok!(BlobManager::pause_uploads(RuntimeOrigin::root(), Some(3)));
+		// Still paused before the scheduled block
+		System::set_block_number(2);
+		Scheduler::on_initialize(2);
+		assert!(Paused::<Test>::get());
+		// Resumed at the scheduled block
+		System::set_block_number(3);
+		Scheduler::on_initialize(3);
+		assert!(!Paused::<Test>::get());
+		System::assert_has_event(Event::UploadsResumed.into());
+	})
+}
+
+#[test]
+fn check_blob_uploads_rejects_uploads_while_paused() {
+	new_test_ext().execute_with(|| {
+		let upload: RuntimeCall = Call::<Test>::upload_blob { blob: vec![0u8; 32] }.into();
+		let other: RuntimeCall = Call::<Test>::set_uploader { uploader: 3 }.into();
+		let info = DispatchInfo::default();
+		let check = CheckBlobUploads::<Test>::new();
+
+		assert_ok!(check.validate(&2, &upload, &info, 0));
+		assert_ok!(BlobManager::pause_uploads(RuntimeOrigin::root(), None));
+		assert_eq!(
+			check.validate(&2, &upload, &info, 0),
+			Err(InvalidTransaction::Custom(UPLOADS_PAUSED).into())
+		);
+		assert_ok!(check.validate(&2, &other, &info, 0));
+	})
+}
+
+#[test]
+fn propose_uploader_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_ok!(BlobManager::propose_uploader(RuntimeOrigin::root(), 3));
+		assert_eq!(
+			PendingUploader::<Test>::get(),
+			Some(PendingHandover { uploader: 3, accept_from: 4 })
+		);
+		System::assert_last_event(Event::UploaderProposed { uploader: 3, accept_from: 4 }.into());
+		// Uploader doesn't change until the handover is accepted
+		assert_eq!(Uploader::<Test>::get(), Some(2));
+	})
+}
+
+#[test]
+fn propose_uploader_wrong_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::propose_uploader(RuntimeOrigin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn accept_uploader_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		assert_ok!(BlobManager::propose_uploader(RuntimeOrigin::root(), 3));
+		// Handover delay not passed yet
+		System::set_block_number(3);
+		assert_noop!(
+			BlobManager::accept_uploader(RuntimeOrigin::signed(3)),
+			Error::<Test>::HandoverDelayNotPassed
+		);
+		// Handover delay passed
+		System::set_block_number(4);
+		assert_ok!(BlobManager::accept_uploader(RuntimeOrigin::signed(3)));
+		assert_eq!(Uploader::<Test>::get(), Some(3));
+		assert_eq!(PendingUploader::<Test>::get(), None);
+		System::assert_last_event(Event::UploaderHandoverAccepted { uploader: 3 }.into());
+	})
+}
+
+#[test]
+fn accept_uploader_wrong_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::propose_uploader(RuntimeOrigin::root(), 3));
+		System::set_block_number(4);
+		assert_noop!(
+			BlobManager::accept_uploader(RuntimeOrigin::signed(4)),
+			Error::<Test>::CallableByProposedUploaderOnly
+		);
+	})
+}
+
+#[test]
+fn accept_uploader_no_pending_handover() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::accept_uploader(RuntimeOrigin::signed(3)),
+			Error::<Test>::NoPendingHandover
+		);
+	})
+}
+
+#[test]
+fn cancel_uploader_handover_works() {
+	new_test_ext().execute_with(|| {
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		// Canceled by AdminOrigin
+		assert_ok!(BlobManager::propose_uploader(RuntimeOrigin::root(), 3));
+		assert_ok!(BlobManager::cancel_uploader_handover(RuntimeOrigin::root()));
+		assert_eq!(PendingUploader::<Test>::get(), None);
+		System::assert_last_event(Event::UploaderHandoverCanceled { uploader: 3 }.into());
+		// Declined by the proposed Uploader
+		assert_ok!(BlobManager::propose_uploader(RuntimeOrigin::root(), 3));
+		assert_ok!(BlobManager::cancel_uploader_handover(RuntimeOrigin::signed(3)));
+		assert_eq!(PendingUploader::<Test>::get(), None);
+		// Nothing left to accept
+		System::set_block_number(4);
+		assert_noop!(
+			BlobManager::accept_uploader(RuntimeOrigin::signed(3)),
+			Error::<Test>::NoPendingHandover
+		);
+	})
+}
+
+#[test]
+fn cancel_uploader_handover_wrong_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::propose_uploader(RuntimeOrigin::root(), 3));
+		assert_noop!(
+			BlobManager::cancel_uploader_handover(RuntimeOrigin::signed(2)),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn upload_blobs_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let blobs = vec![vec![1u8; 32], vec![2u8; 64]];
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 16]));
+		assert_ok!(BlobManager::upload_blobs(RuntimeOrigin::signed(2), blobs.clone()));
+
+		let stored = Blobs::<Test>::get(1);
+		assert_eq!(stored.len(), 3);
+		for (index, blob) in blobs.iter().enumerate() {
+			let index = index as u32 + 1;
+			assert_eq!(stored[index as usize].to_vec(), *blob);
+			System::assert_has_event(
+				Event::BlobStored { block_number: 1, index, hash: BlakeTwo256::hash(blob) }.into(),
+			);
+		}
+	})
+}
+
+#[test]
+fn upload_blobs_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::set_limits(
+			RuntimeOrigin::root(),
+			BlobLimits { max_blobs_per_block: 3, max_blob_size: 32 }
+		));
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 32]));
+		// One blob too many for the block
+		assert_noop!(
+			BlobManager::upload_blobs(RuntimeOrigin::signed(2), vec![vec![0u8; 32]; 3]),
+			Error::<Test>::ExceededMaxBlobsPerBlock
+		);
+		// Last blob above the size limit
+		assert_noop!(
+			BlobManager::upload_blobs(RuntimeOrigin::signed(2), vec![vec![0u8; 32], vec![0u8; 33]]),
+			Error::<Test>::ExceededMaxBlobSize
+		);
+		assert_eq!(Blobs::<Test>::get(System::block_number()).len(), 1);
+	})
+}
+
+#[test]
+fn upload_blobs_empty_batch() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::upload_blobs(RuntimeOrigin::signed(2), vec![]),
+			Error::<Test>::EmptyBatch
+		);
+	})
+}
+
+#[test]
+fn upload_blobs_wrong_uploader() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::upload_blobs(RuntimeOrigin::signed(1), vec![vec![0u8; 32]]),
+			Error::<Test>::CallableByUploaderOnly
+		);
+	})
+}
+
+#[test]
+fn upload_blobs_while_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::pause_uploads(RuntimeOrigin::root(), None));
+		assert_noop!(
+			BlobManager::upload_blobs(RuntimeOrigin::signed(2), vec![vec![0u8; 32]]),
+			Error::<Test>::UploadsPaused
+		);
+	})
+}
+
+fn meta(codec: BlobCodec, uncompressed_size: u32, expires_at: Option<u64>) -> BlobMeta<u64> {
+	BlobMeta {
+		version: BLOB_META_VERSION,
+		codec,
+		uncompressed_size,
+		expires_at,
+		tag: b"tag".to_vec().try_into().unwrap(),
+	}
+}
+
+#[test]
+fn upload_blob_with_meta_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let raw = meta(BlobCodec::Raw, 32, Some(10));
+		let zstd = meta(BlobCodec::Zstd, 1024, None);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 16]));
+		assert_ok!(BlobManager::upload_blob_with_meta(
+			RuntimeOrigin::signed(2),
+			vec![1u8; 32],
+			raw.clone()
+		));
+		assert_ok!(BlobManager::upload_blob_with_meta(
+			RuntimeOrigin::signed(2),
+			vec![2u8; 64],
+			zstd.clone()
+		));
+
+		assert_eq!(Blobs::<Test>::get(1).len(), 3);
+		assert_eq!(BlobMetas::<Test>::get(1, 1), Some(raw.clone()));
+		assert_eq!(BlobManager::blob_meta(1, 0), None);
+		assert_eq!(BlobManager::blob_metas(1), vec![(1, raw), (2, zstd)]);
+		System::assert_has_event(
+			Event::BlobStored { block_number: 1, index: 2, hash: BlakeTwo256::hash(&[2u8; 64]) }
+				.into(),
+		);
+	})
+}
+
+#[test]
+fn upload_blob_with_meta_invalid_meta() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		let mut unsupported = meta(BlobCodec::Json, 32, None);
+		unsupported.version = BLOB_META_VERSION + 1;
+		assert_noop!(
+			BlobManager::upload_blob_with_meta(
+				RuntimeOrigin::signed(2),
+				vec![0u8; 32],
+				unsupported
+			),
+			Error::<Test>::UnsupportedMetaVersion
+		);
+		assert_noop!(
+			BlobManager::upload_blob_with_meta(
+				RuntimeOrigin::signed(2),
+				vec![0u8; 32],
+				meta(BlobCodec::Scale, 33, None)
+			),
+			Error::<Test>::UncompressedSizeMismatch
+		);
+		assert_noop!(
+			BlobManager::upload_blob_with_meta(
+				RuntimeOrigin::signed(2),
+				vec![0u8; 32],
+				meta(BlobCodec::Raw, 32, Some(5))
+			),
+			Error::<Test>::ExpiryInPast
+		);
+	})
+}
+
+#[test]
+fn upload_blob_with_meta_wrong_uploader() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::upload_blob_with_meta(
+				RuntimeOrigin::signed(1),
+				vec![0u8; 32],
+				meta(BlobCodec::Raw, 32, None)
+			),
+			Error::<Test>::CallableByUploaderOnly
+		);
+	})
+}
+
+#[test]
+fn upload_rejects_invalid_blobs() {
+	new_test_ext().execute_with(|| {
+		let invalid = vec![INVALID_MARKER, 0, 0, 0];
+		assert_noop!(
+			BlobManager::upload_blob(RuntimeOrigin::signed(2), invalid.clone()),
+			Error::<Test>::InvalidBlob
+		);
+		assert_noop!(
+			BlobManager::upload_blobs(
+				RuntimeOrigin::signed(2),
+				vec![vec![0u8; 32], invalid.clone()]
+			),
+			Error::<Test>::InvalidBlob
+		);
+		assert_noop!(
+			BlobManager::upload_blob_with_meta(
+				RuntimeOrigin::signed(2),
+				invalid,
+				meta(BlobCodec::Raw, 4, None)
+			),
+			Error::<Test>::InvalidBlob
+		);
+	})
+}
+
+#[test]
+fn check_blob_uploads_rejects_invalid_blobs() {
+	new_test_ext().execute_with(|| {
+		let valid: RuntimeCall = Call::<Test>::upload_blob { blob: vec![0u8; 32] }.into();
+		let invalid: RuntimeCall =
+			Call::<Test>::upload_blobs { blobs: vec![vec![0u8; 32], vec![INVALID_MARKER]] }.into();
+		let info = DispatchInfo::default();
+		let check = CheckBlobUploads::<Test>::new();
+
+		assert_ok!(check.validate(&2, &valid, &info, 0));
+		assert_eq!(
+			check.validate(&2, &invalid, &info, 0),
+			Err(InvalidTransaction::Custom(INVALID_BLOB).into())
+		);
+	})
+}
+
+#[test]
+fn check_blob_uploads_checks_nested_uploads() {
+	new_test_ext().execute_with(|| {
+		let upload = |blob: Vec<u8>| -> RuntimeCall { Call::<Test>::upload_blob { blob }.into() };
+		let batch: RuntimeCall = pallet_utility::Call::<Test>::batch {
+			calls: vec![
+				Call::<Test>::set_uploader { uploader: 3 }.into(),
+				pallet_utility::Call::<Test>::as_derivative {
+					index: 0,
+					call: Box::new(upload(vec![1u8; 32])),
+				}
+				.into(),
+				upload(vec![2u8; 16]),
+			],
+		}
+		.into();
+		let invalid: RuntimeCall =
+			pallet_utility::Call::<Test>::batch_all { calls: vec![upload(vec![INVALID_MARKER])] }
+				.into();
+		let info = DispatchInfo::default();
+		let check = CheckBlobUploads::<Test>::new();
+
+		// Nested uploads are found in dispatch order
+		assert_eq!(uploaded_blobs::<Test>(&batch), vec![&[1u8; 32][..], &[2u8; 16][..]]);
+		assert_ok!(check.validate(&2, &batch, &info, 0));
+		assert_eq!(
+			check.validate(&2, &invalid, &info, 0),
+			Err(InvalidTransaction::Custom(INVALID_BLOB).into())
+		);
+
+		assert_ok!(BlobManager::pause_uploads(RuntimeOrigin::root(), None));
+		assert_eq!(
+			check.validate(&2, &batch, &info, 0),
+			Err(InvalidTransaction::Custom(UPLOADS_PAUSED).into())
+		);
+	})
+}
+
+#[test]
+fn on_blob_stored_called_for_every_blob() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let blobs = vec![vec![1u8; 32], vec![2u8; 64]];
+		assert_ok!(BlobManager::upload_blobs(RuntimeOrigin::signed(2), blobs.clone()));
+		// Failed uploads aren't reported
+		assert_noop!(
+			BlobManager::upload_blob(RuntimeOrigin::signed(1), vec![3u8; 16]),
+			Error::<Test>::CallableByUploaderOnly
+		);
+
+		assert_eq!(
+			StoredBlobs::get(),
+			vec![
+				(2, 1, 0, BlakeTwo256::hash(&blobs[0]), 32),
+				(2, 1, 1, BlakeTwo256::hash(&blobs[1]), 64),
+			]
+		);
+	})
+}
+
+#[test]
+fn blob_provider_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let raw = meta(BlobCodec::Raw, 32, None);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 16]));
+		assert_ok!(BlobManager::upload_blob_with_meta(
+			RuntimeOrigin::signed(2),
+			vec![2u8; 32],
+			raw.clone()
+		));
+
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::blob_count(1), 2);
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::blob_count(2), 0);
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::blob(1, 1), Some(vec![2u8; 32]));
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::blob(1, 2), None);
+		assert_eq!(
+			<BlobManager as BlobProvider<_, _>>::blob_hash(1, 0),
+			Some(BlakeTwo256::hash(&[1u8; 16]))
+		);
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::blob_meta(1, 0), None);
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::blob_meta(1, 1), Some(raw));
+		assert_eq!(
+			<BlobManager as BlobProvider<_, _>>::chunk_commitment(1, 1),
+			Some(blob_chunk_commitment::<BlakeTwo256>(&[2u8; 32]))
+		);
+		assert_eq!(<BlobManager as BlobProvider<_, _>>::chunk_commitment(1, 2), None);
+	})
+}
+
+#[test]
+fn blob_chunk_commitment_works() {
+	let blob = (0..BLOB_CHUNK_SIZE * 2 + 10).map(|i| i as u8).collect::<Vec<_>>();
+	let chunks = blob.chunks(BLOB_CHUNK_SIZE as usize).map(<[u8]>::to_vec).collect::<Vec<_>>();
+	assert_eq!(chunks.len(), 3);
+	assert_eq!(
+		blob_chunk_commitment::<BlakeTwo256>(&blob),
+		ChunkCommitment {
+			root: BlakeTwo256::ordered_trie_root(chunks, StateVersion::V0),
+			count: 3
+		}
+	);
+
+	// An empty blob has no chunks
+	assert_eq!(blob_chunk_commitment::<BlakeTwo256>(&[]).count, 0);
+}
+
+#[test]
+fn create_channel_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::create_channel(RuntimeOrigin::root(), 3));
+		assert_ok!(BlobManager::create_channel(RuntimeOrigin::root(), 4));
+
+		assert_eq!(NextChannelId::<Test>::get(), 2);
+		assert_eq!(
+			Channels::<Test>::get(1),
+			Some(Channel { owner: 4, sequence: 0, accumulator: H256::default() })
+		);
+		System::assert_has_event(Event::ChannelCreated { channel: 0, owner: 3 }.into());
+	})
+}
+
+#[test]
+fn create_channel_wrong_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BlobManager::create_channel(RuntimeOrigin::signed(3), 3),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn upload_to_channel_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::create_channel(RuntimeOrigin::root(), 3));
+		let blobs = [vec![1u8; 32], vec![2u8; 16], vec![3u8; 64]];
+
+		// Messages spread over two blocks, the channel owner doesn't need to be the Uploader
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 8]));
+		assert_ok!(BlobManager::upload_to_channel(RuntimeOrigin::signed(3), 0, blobs[0].clone()));
+		assert_ok!(BlobManager::upload_to_channel(RuntimeOrigin::signed(3), 0, blobs[1].clone()));
+		System::set_block_number(2);
+		assert_ok!(BlobManager::upload_to_channel(RuntimeOrigin::signed(3), 0, blobs[2].clone()));
+
+		let hashes = blobs.iter().map(|blob| BlakeTwo256::hash(blob)).collect::<Vec<_>>();
+		let mut accumulator = H256::default();
+		for (sequence, hash) in (1..).zip(&hashes) {
+			accumulator = channel_accumulator::<BlakeTwo256>(accumulator, sequence, *hash);
+		}
+		assert_eq!(Channels::<Test>::get(0), Some(Channel { owner: 3, sequence: 3, accumulator }));
+		assert_eq!(
+			ChannelMessages::<Test>::get(0, 2),
+			Some(ChannelMessage { block_number: 1, index: 2, hash: hashes[1] })
+		);
+		assert_eq!(
+			BlobManager::channel_messages(0, 2, 10),
+			vec![
+				(2, ChannelMessage { block_number: 1, index: 2, hash: hashes[1] }),
+				(3, ChannelMessage { block_number: 2, index: 0, hash: hashes[2] }),
+			]
+		);
+		System::assert_has_event(
+			Event::ChannelMessageAppended {
+				channel: 0,
+				sequence: 3,
+				block_number: 2,
+				index: 0,
+				hash: hashes[2],
+				accumulator,
+			}
+			.into(),
+		);
+
+		// Clients verify the history from the start, or from a checkpoint
+		assert_eq!(
+			blobchain_verifier::verify_channel_history::<BlakeTwo256>(
+				(0, H256::default()),
+				&hashes,
+				(3, accumulator)
+			),
+			Ok(())
+		);
+	})
+}
+
+#[test]
+fn upload_to_channel_wrong_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::create_channel(RuntimeOrigin::root(), 3));
+		// Not even the Uploader can append to a channel it doesn't own
+		assert_noop!(
+			BlobManager::upload_to_channel(RuntimeOrigin::signed(2), 0, vec![0u8; 32]),
+			Error::<Test>::CallableByChannelOwnerOnly
+		);
+		assert_noop!(
+			BlobManager::upload_to_channel(RuntimeOrigin::signed(3), 1, vec![0u8; 32]),
+			Error::<Test>::ChannelNotFound
+		);
+	})
+}
+
+#[test]
+fn upload_to_channel_respects_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlobManager::create_channel(RuntimeOrigin::root(), 3));
+		assert_ok!(BlobManager::set_limits(
+			RuntimeOrigin::root(),
+			BlobLimits { max_blobs_per_block: 1, max_blob_size: 32 }
+		));
+		assert_noop!(
+			BlobManager::upload_to_channel(RuntimeOrigin::signed(3), 0, vec![0u8; 33]),
+			Error::<Test>::ExceededMaxBlobSize
+		);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![0u8; 32]));
+		// A failed upload leaves no gap in the sequence numbers
+		assert_noop!(
+			BlobManager::upload_to_channel(RuntimeOrigin::signed(3), 0, vec![0u8; 32]),
+			Error::<Test>::ExceededMaxBlobsPerBlock
+		);
+		assert_eq!(Channels::<Test>::get(0).map(|channel| channel.sequence), Some(0));
+	})
+}
+
+#[test]
+fn blob_root_commits_to_blob_hashes() {
+	new_test_ext().execute_with(|| {
+		let empty_root = BlobManager::blob_root(1);
+		assert_eq!(empty_root, blob_commitment_root::<BlakeTwo256>(&[]));
+
+		let blobs = vec![vec![1u8; 32], vec![2u8; 64], vec![3u8; 16]];
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), blobs[0].clone()));
+		assert_ok!(BlobManager::upload_blobs(RuntimeOrigin::signed(2), blobs[1..].to_vec()));
+
+		let hashes = blobs.iter().map(|blob| BlakeTwo256::hash(blob)).collect::<Vec<_>>();
+		assert_eq!(BlobHashes::<Test>::get(1).to_vec(), hashes);
+		assert_eq!(BlobManager::blob_root(1), blob_commitment_root::<BlakeTwo256>(&hashes));
+		assert_ne!(BlobManager::blob_root(1), empty_root);
+		// Other blocks are unaffected
+		assert_eq!(BlobManager::blob_root(2), empty_root);
+	})
+}
+
+// Attestation of authority `authority_index` for block `block_number`, signed by `key`
+fn sign_attestation(
+	block_number: u64,
+	authority_index: u32,
+	key: u64,
+) -> (AvailabilityAttestation<u64>, TestSignature) {
+	let attestation = AvailabilityAttestation { block_number, authority_index };
+	let signature = UintAuthorityId(key).sign(&attestation.encode()).unwrap();
+	(attestation, signature)
+}
+
+#[test]
+fn attest_availability_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+		System::set_block_number(3);
+		assert_eq!(BlobManager::availability_quorum(), 2);
+
+		let (attestation, signature) = sign_attestation(1, 0, 10);
+		assert_ok!(BlobManager::attest_availability(RuntimeOrigin::none(), attestation, signature));
+		System::assert_last_event(
+			Event::AvailabilityAttested { block_number: 1, authority_index: 0 }.into(),
+		);
+		assert_eq!(Availability::<Test>::get(1).to_vec(), vec![0]);
+		assert!(!BlobManager::is_available(1));
+
+		// The second attestation reaches the quorum
+		let (attestation, signature) = sign_attestation(1, 2, 12);
+		assert_ok!(BlobManager::attest_availability(RuntimeOrigin::none(), attestation, signature));
+		System::assert_has_event(
+			Event::AvailabilityAttested { block_number: 1, authority_index: 2 }.into(),
+		);
+		System::assert_last_event(
+			Event::BlobsAvailable { block_number: 1, attestations: 2 }.into(),
+		);
+		assert_eq!(Availability::<Test>::get(1).to_vec(), vec![0, 2]);
+		assert!(BlobManager::is_available(1));
+
+		// Further attestations are recorded, without announcing the blobs again
+		let (attestation, signature) = sign_attestation(1, 1, 11);
+		assert_ok!(BlobManager::attest_availability(RuntimeOrigin::none(), attestation, signature));
+		System::assert_last_event(
+			Event::AvailabilityAttested { block_number: 1, authority_index: 1 }.into(),
+		);
+	})
+}
+
+#[test]
+fn attest_availability_announces_quorum_passed_at_once() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+		System::set_block_number(3);
+		AvailabilityQuorum::set(Perbill::from_percent(100));
+		for (authority_index, key) in [(0, 10), (1, 11)] {
+			let (attestation, signature) = sign_attestation(1, authority_index, key);
+			assert_ok!(BlobManager::attest_availability(
+				RuntimeOrigin::none(),
+				attestation,
+				signature
+			));
+		}
+		assert!(!BlobManager::is_available(1));
+
+		// The quorum drops below the attestations recorded so far
+		AvailabilityQuorum::set(Perbill::from_percent(34));
+		assert_eq!(BlobManager::availability_quorum(), 2);
+		let (attestation, signature) = sign_attestation(1, 2, 12);
+		assert_ok!(BlobManager::attest_availability(RuntimeOrigin::none(), attestation, signature));
+		System::assert_last_event(
+			Event::BlobsAvailable { block_number: 1, attestations: 3 }.into(),
+		);
+		assert!(BlobManager::is_available(1));
+	})
+}
+
+#[test]
+fn attestations_are_pruned_after_the_window() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+		System::set_block_number(3);
+		for (authority_index, key) in [(0, 10), (1, 11)] {
+			let (attestation, signature) = sign_attestation(1, authority_index, key);
+			assert_ok!(BlobManager::attest_availability(
+				RuntimeOrigin::none(),
+				attestation,
+				signature
+			));
+		}
+		assert!(BlobManager::is_available(1));
+
+		// Kept while the block can still be attested
+		System::set_block_number(11);
+		BlobManager::on_initialize(11);
+		assert_eq!(Availability::<Test>::get(1).to_vec(), vec![0, 1]);
+
+		System::set_block_number(12);
+		BlobManager::on_initialize(12);
+		assert!(Availability::<Test>::get(1).is_empty());
+		assert_eq!(AvailableBlocks::<Test>::get(1), None);
+		assert!(!BlobManager::is_available(1));
+	})
+}
+
+#[test]
+fn set_authorities_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Authorities::<Test>::get().len(), 3);
+		let authorities = BoundedVec::truncate_from(vec![UintAuthorityId(13), UintAuthorityId(14)]);
+		assert_noop!(
+			BlobManager::set_authorities(RuntimeOrigin::signed(1), authorities.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(BlobManager::set_authorities(RuntimeOrigin::root(), authorities.clone()));
+		assert_eq!(Authorities::<Test>::get(), authorities);
+		System::assert_last_event(Event::AuthoritiesSet { authorities: 2 }.into());
+		assert_eq!(BlobManager::availability_quorum(), 2);
+
+		// Attestations are signed by the new authorities
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+		System::set_block_number(3);
+		let (attestation, signature) = sign_attestation(1, 1, 14);
+		assert_ok!(BlobManager::attest_availability(RuntimeOrigin::none(), attestation, signature));
+		let (attestation, signature) = sign_attestation(1, 2, 12);
+		assert_noop!(
+			BlobManager::attest_availability(RuntimeOrigin::none(), attestation, signature),
+			Error::<Test>::UnknownAuthority
+		);
+	})
+}
+
+#[test]
+fn attest_availability_invalid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+
+		let (valid, signature) = sign_attestation(1, 0, 10);
+		assert_noop!(
+			BlobManager::attest_availability(
+				RuntimeOrigin::signed(2),
+				valid.clone(),
+				signature.clone()
+			),
+			DispatchError::BadOrigin
+		);
+		// Not before the block is over
+		assert_noop!(
+			BlobManager::attest_availability(
+				RuntimeOrigin::none(),
+				valid.clone(),
+				signature.clone()
+			),
+			Error::<Test>::AttestationOutsideWindow
+		);
+
+		System::set_block_number(3);
+		let (other, other_signature) = sign_attestation(2, 0, 10);
+		assert_noop!(
+			BlobManager::attest_availability(RuntimeOrigin::none(), other, other_signature),
+			Error::<Test>::NoBlobsToAttest
+		);
+		let (other, other_signature) = sign_attestation(1, 3, 13);
+		assert_noop!(
+			BlobManager::attest_availability(RuntimeOrigin::none(), other, other_signature),
+			Error::<Test>::UnknownAuthority
+		);
+		assert_ok!(BlobManager::attest_availability(
+			RuntimeOrigin::none(),
+			valid.clone(),
+			signature.clone()
+		));
+		assert_noop!(
+			BlobManager::attest_availability(
+				RuntimeOrigin::none(),
+				valid.clone(),
+				signature.clone()
+			),
+			Error::<Test>::AlreadyAttested
+		);
+
+		// Not after the attestation window
+		System::set_block_number(12);
+		let (other, other_signature) = sign_attestation(1, 1, 11);
+		assert_noop!(
+			BlobManager::attest_availability(RuntimeOrigin::none(), other, other_signature),
+			Error::<Test>::AttestationOutsideWindow
+		);
+	})
+}
+
+#[test]
+fn validate_unsigned_checks_attestations() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), vec![1u8; 32]));
+		let validate = |attestation, signature| {
+			BlobManager::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::attest_availability { attestation, signature },
+			)
+		};
+
+		let (valid, signature) = sign_attestation(1, 0, 10);
+		assert_eq!(validate(valid.clone(), signature.clone()), InvalidTransaction::Future.into());
+
+		System::set_block_number(3);
+		let validity = validate(valid.clone(), signature.clone()).unwrap();
+		assert_eq!(validity.priority, 100);
+		assert_eq!(validity.longevity, 10);
+		assert!(validity.propagate);
+
+		// Signed by another authority
+		let (_, forged) = sign_attestation(1, 0, 11);
+		assert_eq!(validate(valid.clone(), forged), InvalidTransaction::BadProof.into());
+		let (other, other_signature) = sign_attestation(1, 3, 13);
+		assert_eq!(validate(other, other_signature), InvalidTransaction::BadSigner.into());
+		let (other, other_signature) = sign_attestation(2, 0, 10);
+		assert_eq!(validate(other, other_signature), InvalidTransaction::Stale.into());
+
+		assert_ok!(BlobManager::attest_availability(
+			RuntimeOrigin::none(),
+			valid.clone(),
+			signature.clone()
+		));
+		assert_eq!(validate(valid, signature), InvalidTransaction::Stale.into());
+	})
+}
+
+#[test]
+fn offchain_worker_attests_held_blobs() {
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let blob = vec![7u8; 32];
+		assert_ok!(BlobManager::upload_blob(RuntimeOrigin::signed(2), blob.clone()));
+		System::set_block_number(3);
+
+		// No local authority key
+		BlobManager::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// Blob missing from the offchain database, offchain indexing doesn't reach it in tests
+		UintAuthorityId::set_all_keys(vec![11u64]);
+		BlobManager::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// Wrong blob in the offchain database
+		let key = crate::offchain_key_by_location(1u64, 0);
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &key, &[8u8; 32]);
+		BlobManager::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &key, &blob);
+		BlobManager::offchain_worker(3);
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let transaction = Extrinsic::decode(&mut &*transaction).unwrap();
+		assert_eq!(transaction.signature, None);
+		let (attestation, signature) = sign_attestation(1, 1, 11);
+		assert_eq!(
+			transaction.call,
+			RuntimeCall::BlobManager(crate::Call::attest_availability { attestation, signature })
+		);
+
+		// Once attested, the block isn't attested again
+		assert_ok!(transaction.call.dispatch(RuntimeOrigin::none()));
+		BlobManager::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+	})
+}
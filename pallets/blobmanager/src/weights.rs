@@ -1,13 +1,23 @@
-
-//! Autogenerated weights for `pallet_blobmanager`
+//! Weights for `pallet_blobmanager`
+//!
+//! Only `set_uploader` and the cost of storing blob bytes come from a benchmark run (Substrate
+//! benchmark CLI version 42.0.0, 2024-09-27, STEPS: `20`, REPEAT: `50`, CPU: `AMD Ryzen
+//! Threadripper PRO 5965WX 24-Cores`), back when `upload_blob` did nothing but store the blob. The
+//! other weights are derived by hand from the storage accesses listed above each of them, until
+//! the benchmarks in `benchmarking.rs` are run again on reference hardware.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 42.0.0
-//! DATE: 2024-09-27, STEPS: `20`, REPEAT: `50`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `noop`, CPU: `AMD Ryzen Threadripper PRO 5965WX 24-Cores`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: `1024`
+//! Uploads scale with the number of blob bytes `b`, at 2_303 picoseconds per byte:
+//! - 101 for storing the blob, as `upload_blob` took 531_452_000 picoseconds for a blob of
+//!   5_242_880 bytes in that run,
+//! - 101 for each of the two further copies, into the offchain index and into the chunks,
+//! - 1_000 for each of the two blake2-256 passes, over the blob and over its chunks, estimated at
+//!   1 GB/s through the hashing host function.
+//!
+//! Per upload call, 10_000_000 picoseconds are estimated for checking origin, pause and limits,
+//! and 5_000_000 per blob for indexing it, building its chunk trie, notifying `OnBlobStored` and
+//! emitting its event.
 
-// Executed Command:
+// Command to regenerate:
 // target/release/blobchain-node
 // benchmark
 // pallet
@@ -38,7 +48,21 @@ use core::marker::PhantomData;
 /// Weight functions needed for `pallet_blobmanager`.
 pub trait WeightInfo {
 	fn set_uploader() -> Weight;
-	fn upload_blob() -> Weight;
+	fn upload_blob(b: u32, ) -> Weight;
+	fn set_limits() -> Weight;
+	fn pause_uploads() -> Weight;
+	fn resume_uploads() -> Weight;
+	fn propose_uploader() -> Weight;
+	fn accept_uploader() -> Weight;
+	fn cancel_uploader_handover() -> Weight;
+	fn upload_blobs(n: u32, b: u32, ) -> Weight;
+	fn upload_blob_with_meta(b: u32, ) -> Weight;
+	fn create_channel() -> Weight;
+	fn upload_to_channel(b: u32, ) -> Weight;
+	fn attest_availability() -> Weight;
+	fn blob_root(n: u32, ) -> Weight;
+	fn prune_availability() -> Weight;
+	fn set_authorities(n: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_blobmanager` using the Substrate node and recommended hardware.
@@ -54,17 +78,191 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(2_460_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
 	/// Storage: `BlobManager::Uploader` (r:1 w:0)
 	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
 	/// Storage: `BlobManager::Blobs` (r:1 w:1)
 	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
-	fn upload_blob() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `37`
-		//  Estimated: `5246386`
-		// Minimum execution time: 510_171_000 picoseconds.
-		Weight::from_parts(531_452_000, 5246386)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:1)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_blob(b: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 5249010)
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `BlobManager::Limits` (r:0 w:1)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn set_limits() -> Weight {
+		Weight::from_parts(2_730_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(10463), added: 12938, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Paused` (r:0 w:1)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	fn pause_uploads() -> Weight {
+		Weight::from_parts(17_410_000, 13928)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:1)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(10463), added: 12938, mode: `MaxEncodedLen`)
+	fn resume_uploads() -> Weight {
+		Weight::from_parts(15_530_000, 13928)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `BlobManager::PendingUploader` (r:0 w:1)
+	/// Proof: `BlobManager::PendingUploader` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	fn propose_uploader() -> Weight {
+		Weight::from_parts(5_340_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BlobManager::PendingUploader` (r:1 w:1)
+	/// Proof: `BlobManager::PendingUploader` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Uploader` (r:0 w:1)
+	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn accept_uploader() -> Weight {
+		Weight::from_parts(8_790_000, 1521)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::PendingUploader` (r:1 w:1)
+	/// Proof: `BlobManager::PendingUploader` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	fn cancel_uploader_handover() -> Weight {
+		Weight::from_parts(7_480_000, 1521)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Uploader` (r:1 w:0)
+	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Blobs` (r:1 w:1)
+	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:5)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 5]`.
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_blobs(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 5249010)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Uploader` (r:1 w:0)
+	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Blobs` (r:1 w:1)
+	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:1)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobMetas` (r:0 w:1)
+	/// Proof: `BlobManager::BlobMetas` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_blob_with_meta(b: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 5249010)
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobManager::NextChannelId` (r:1 w:1)
+	/// Proof: `BlobManager::NextChannelId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Channels` (r:0 w:1)
+	/// Proof: `BlobManager::Channels` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	fn create_channel() -> Weight {
+		Weight::from_parts(6_120_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Channels` (r:1 w:1)
+	/// Proof: `BlobManager::Channels` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Blobs` (r:1 w:1)
+	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:1)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::ChannelMessages` (r:0 w:1)
+	/// Proof: `BlobManager::ChannelMessages` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_to_channel(b: u32, ) -> Weight {
+		Weight::from_parts(17_000_000, 5249010)
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:0)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Authorities` (r:1 w:0)
+	/// Proof: `BlobManager::Authorities` (`max_values`: Some(1), `max_size`: Some(1025), added: 1520, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Availability` (r:1 w:1)
+	/// Proof: `BlobManager::Availability` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::AvailableBlocks` (r:1 w:1)
+	/// Proof: `BlobManager::AvailableBlocks` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn attest_availability() -> Weight {
+		Weight::from_parts(24_310_000, 3614)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:0)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 5]`.
+	fn blob_root(n: u32, ) -> Weight {
+		Weight::from_parts(3_480_000, 3614)
+			.saturating_add(Weight::from_parts(4_212_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: `BlobManager::Availability` (r:0 w:1)
+	/// Proof: `BlobManager::Availability` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::AvailableBlocks` (r:0 w:1)
+	/// Proof: `BlobManager::AvailableBlocks` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn prune_availability() -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::Authorities` (r:0 w:1)
+	/// Proof: `BlobManager::Authorities` (`max_values`: Some(1), `max_size`: Some(1025), added: 1520, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn set_authorities(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			.saturating_add(Weight::from_parts(20_000, 0).saturating_mul(n.into()))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
@@ -81,17 +279,191 @@ impl WeightInfo for () {
 		Weight::from_parts(2_460_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
 	/// Storage: `BlobManager::Uploader` (r:1 w:0)
 	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
 	/// Storage: `BlobManager::Blobs` (r:1 w:1)
 	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
-	fn upload_blob() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `37`
-		//  Estimated: `5246386`
-		// Minimum execution time: 510_171_000 picoseconds.
-		Weight::from_parts(531_452_000, 5246386)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:1)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_blob(b: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 5249010)
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `BlobManager::Limits` (r:0 w:1)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn set_limits() -> Weight {
+		Weight::from_parts(2_730_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(10463), added: 12938, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Paused` (r:0 w:1)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	fn pause_uploads() -> Weight {
+		Weight::from_parts(17_410_000, 13928)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:1)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(10463), added: 12938, mode: `MaxEncodedLen`)
+	fn resume_uploads() -> Weight {
+		Weight::from_parts(15_530_000, 13928)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `BlobManager::PendingUploader` (r:0 w:1)
+	/// Proof: `BlobManager::PendingUploader` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	fn propose_uploader() -> Weight {
+		Weight::from_parts(5_340_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BlobManager::PendingUploader` (r:1 w:1)
+	/// Proof: `BlobManager::PendingUploader` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Uploader` (r:0 w:1)
+	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn accept_uploader() -> Weight {
+		Weight::from_parts(8_790_000, 1521)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::PendingUploader` (r:1 w:1)
+	/// Proof: `BlobManager::PendingUploader` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	fn cancel_uploader_handover() -> Weight {
+		Weight::from_parts(7_480_000, 1521)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Uploader` (r:1 w:0)
+	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Blobs` (r:1 w:1)
+	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:5)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 5]`.
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_blobs(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 5249010)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Uploader` (r:1 w:0)
+	/// Proof: `BlobManager::Uploader` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Blobs` (r:1 w:1)
+	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:1)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobMetas` (r:0 w:1)
+	/// Proof: `BlobManager::BlobMetas` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_blob_with_meta(b: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 5249010)
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `BlobManager::NextChannelId` (r:1 w:1)
+	/// Proof: `BlobManager::NextChannelId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Channels` (r:0 w:1)
+	/// Proof: `BlobManager::Channels` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	fn create_channel() -> Weight {
+		Weight::from_parts(6_120_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::Paused` (r:1 w:0)
+	/// Proof: `BlobManager::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Channels` (r:1 w:1)
+	/// Proof: `BlobManager::Channels` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Limits` (r:1 w:0)
+	/// Proof: `BlobManager::Limits` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Blobs` (r:1 w:1)
+	/// Proof: `BlobManager::Blobs` (`max_values`: None, `max_size`: Some(5242921), added: 5245396, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:1)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:0 w:1)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::PendingBlobs` (r:1 w:1)
+	/// Proof: `StorageProvider::PendingBlobs` (`max_values`: Some(1), `max_size`: Some(61), added: 556, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::ChannelMessages` (r:0 w:1)
+	/// Proof: `BlobManager::ChannelMessages` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5242880]`.
+	fn upload_to_channel(b: u32, ) -> Weight {
+		Weight::from_parts(17_000_000, 5249010)
+			.saturating_add(Weight::from_parts(2_303, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:0)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Authorities` (r:1 w:0)
+	/// Proof: `BlobManager::Authorities` (`max_values`: Some(1), `max_size`: Some(1025), added: 1520, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::Availability` (r:1 w:1)
+	/// Proof: `BlobManager::Availability` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::AvailableBlocks` (r:1 w:1)
+	/// Proof: `BlobManager::AvailableBlocks` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn attest_availability() -> Weight {
+		Weight::from_parts(24_310_000, 3614)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::BlobHashes` (r:1 w:0)
+	/// Proof: `BlobManager::BlobHashes` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 5]`.
+	fn blob_root(n: u32, ) -> Weight {
+		Weight::from_parts(3_480_000, 3614)
+			.saturating_add(Weight::from_parts(4_212_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: `BlobManager::Availability` (r:0 w:1)
+	/// Proof: `BlobManager::Availability` (`max_values`: None, `max_size`: Some(149), added: 2624, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::AvailableBlocks` (r:0 w:1)
+	/// Proof: `BlobManager::AvailableBlocks` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn prune_availability() -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `BlobManager::Authorities` (r:0 w:1)
+	/// Proof: `BlobManager::Authorities` (`max_values`: Some(1), `max_size`: Some(1025), added: 1520, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 32]`.
+	fn set_authorities(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			.saturating_add(Weight::from_parts(20_000, 0).saturating_mul(n.into()))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 }
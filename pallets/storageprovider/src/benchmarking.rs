@@ -0,0 +1,228 @@
+//! Benchmarking setup for pallet-storageprovider
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as StorageProvider;
+use alloc::vec;
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{
+		fungible::{Inspect, Unbalanced},
+		tokens::Precision,
+		Get,
+	},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use pallet_blobmanager::BLOB_CHUNK_SIZE;
+use sp_runtime::Saturating;
+
+/// Funds `who` with enough to bond a few times the minimum bond
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::MinBond::get()
+		.saturating_mul(10u32.into())
+		.saturating_add(T::Currency::minimum_balance());
+	T::Currency::increase_balance(who, amount, Precision::Exact).expect("funding failed");
+}
+
+/// Registers provider `i` with the minimum bond and unlimited capacity
+fn register_provider<T: Config>(i: u32) -> T::AccountId {
+	let provider: T::AccountId = account("provider", i, 0);
+	fund::<T>(&provider);
+	StorageProvider::<T>::register(
+		RawOrigin::Signed(provider.clone()).into(),
+		T::MinBond::get(),
+		u64::MAX,
+	)
+	.expect("register() fail");
+	provider
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register() {
+		// Setup code
+		for i in 1..T::MaxProviders::get() {
+			register_provider::<T>(i);
+		}
+		let provider: T::AccountId = whitelisted_caller();
+		fund::<T>(&provider);
+
+		#[extrinsic_call]
+		register(RawOrigin::Signed(provider.clone()), T::MinBond::get(), u64::MAX);
+
+		// Verification code
+		assert!(Providers::<T>::contains_key(provider));
+	}
+
+	#[benchmark]
+	fn set_capacity() {
+		// Setup code
+		let provider = register_provider::<T>(0);
+
+		#[extrinsic_call]
+		set_capacity(RawOrigin::Signed(provider.clone()), 0);
+
+		// Verification code
+		assert_eq!(Providers::<T>::get(provider).expect("provider exists").capacity, 0);
+	}
+
+	#[benchmark]
+	fn bond_extra() {
+		// Setup code
+		let provider = register_provider::<T>(0);
+		let amount = T::MinBond::get();
+
+		#[extrinsic_call]
+		bond_extra(RawOrigin::Signed(provider.clone()), amount);
+
+		// Verification code
+		assert_eq!(
+			Providers::<T>::get(provider).expect("provider exists").bond,
+			amount.saturating_mul(2u32.into())
+		);
+	}
+
+	#[benchmark]
+	fn deregister() {
+		// Setup code
+		for i in 1..T::MaxProviders::get() {
+			register_provider::<T>(i);
+		}
+		let provider = register_provider::<T>(0);
+
+		#[extrinsic_call]
+		deregister(RawOrigin::Signed(provider.clone()));
+
+		// Verification code
+		assert!(!Providers::<T>::contains_key(provider));
+	}
+
+	#[benchmark]
+	fn submit_proof(b: Linear<1, BLOB_CHUNK_SIZE>) {
+		// Setup code
+		let blob = vec![0u8; b as usize];
+		let (block_number, index) = T::BenchmarkHelper::store_blob(blob.clone());
+		let provider = register_provider::<T>(0);
+		fund::<T>(&StorageProvider::<T>::account_id());
+		ProofChallenges::<T>::insert(
+			&provider,
+			ProofChallenge { block_number, index, chunk: 0, deadline: block_number },
+		);
+		let (chunk, proof) =
+			pallet_blobchallenge::chunk_proof::<T::Hashing>(&blob, 0).expect("chunk exists");
+
+		#[extrinsic_call]
+		submit_proof(RawOrigin::Signed(provider.clone()), chunk, proof);
+
+		// Verification code
+		assert!(!ProofChallenges::<T>::contains_key(provider));
+	}
+
+	#[benchmark]
+	fn assign_blob(p: Linear<1, { T::MaxProviders::get() }>) {
+		// Setup code, only `ReplicationFactor - 1` providers have free capacity so that the walk
+		// goes through all of them, and those hold all but one of their assignments
+		let (block_number, index) = T::BenchmarkHelper::store_blob(vec![0u8; 32]);
+		let blob = Assignment { block_number, index, size: 32 };
+		let held = (T::MaxAssignments::get() as usize).saturating_sub(1);
+		let assignments = BoundedVec::<_, T::MaxAssignments>::truncate_from(vec![blob; held]);
+		let free = T::ReplicationFactor::get().saturating_sub(1);
+		for i in 0..p {
+			let provider = register_provider::<T>(i);
+			if i < free {
+				Assignments::<T>::insert(&provider, &assignments);
+			} else {
+				Providers::<T>::mutate(&provider, |info| {
+					info.as_mut().expect("provider exists").capacity = 0
+				});
+			}
+		}
+
+		#[block]
+		{
+			StorageProvider::<T>::assign(blob);
+		}
+
+		// Verification code
+		assert_eq!(BlobProviders::<T>::get(block_number, index).len() as u32, free.min(p));
+	}
+
+	#[benchmark]
+	fn expire_blobs(b: Linear<0, { T::MaxPendingBlobs::get() }>) {
+		// Setup code, every blob is assigned to its own providers holding the maximum number of
+		// assignments
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let max_assignments = T::MaxAssignments::get() as usize;
+		let replication_factor = T::ReplicationFactor::get();
+		for index in 0..b {
+			let blob = Assignment { block_number, index, size: 32 };
+			let assignments =
+				BoundedVec::<_, T::MaxAssignments>::truncate_from(vec![blob; max_assignments]);
+			let providers = (0..replication_factor)
+				.map(|i| {
+					let provider: T::AccountId =
+						account("provider", index * replication_factor + i, 0);
+					Providers::<T>::insert(
+						&provider,
+						ProviderInfo {
+							bond: T::MinBond::get(),
+							capacity: u64::MAX,
+							used: 32 * max_assignments as u64,
+						},
+					);
+					Assignments::<T>::insert(&provider, &assignments);
+					provider
+				})
+				.collect::<Vec<_>>();
+			BlobProviders::<T>::insert(
+				block_number,
+				index,
+				BoundedVec::<_, T::ReplicationFactor>::truncate_from(providers),
+			);
+		}
+
+		#[block]
+		{
+			StorageProvider::<T>::expire(block_number);
+		}
+
+		// Verification code
+		assert_eq!(BlobProviders::<T>::iter_prefix(block_number).count(), 0);
+	}
+
+	#[benchmark]
+	fn challenge_providers(p: Linear<1, { T::MaxProviders::get() }>) {
+		// Setup code
+		let (block_number, index) = T::BenchmarkHelper::store_blob(vec![0u8; 32]);
+		let blob = Assignment { block_number, index, size: 32 };
+		let max_assignments = T::MaxAssignments::get() as usize;
+		let assignments =
+			BoundedVec::<_, T::MaxAssignments>::truncate_from(vec![blob; max_assignments]);
+		let challenge = ProofChallenge { block_number, index, chunk: 0, deadline: block_number };
+		fund::<T>(&StorageProvider::<T>::account_id());
+		let providers = (0..p)
+			.map(|i| {
+				let provider = register_provider::<T>(i);
+				Assignments::<T>::insert(&provider, &assignments);
+				ProofChallenges::<T>::insert(&provider, &challenge);
+				provider
+			})
+			.collect::<Vec<_>>();
+
+		#[block]
+		{
+			StorageProvider::<T>::challenge_providers(block_number);
+		}
+
+		// Verification code
+		for provider in providers {
+			assert!(ProofChallenges::<T>::contains_key(provider));
+		}
+	}
+
+	impl_benchmark_test_suite!(StorageProvider, crate::mock::new_test_ext(), crate::mock::Test);
+}
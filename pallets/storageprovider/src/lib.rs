@@ -0,0 +1,639 @@
+// SPDX-License-Identifier: Unlicense
+
+//! # StorageProvider Pallet
+//! A pallet for bonded storage providers replicating stored blobs
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! # Overview
+//!
+//! Blobs stored by pallet-blobmanager are kept by the nodes of the validator set. This pallet lets
+//! storage providers beyond that set keep replicas of them, backed by a bond.
+//!
+//! Providers register with [`Pallet::register`], bonding at least [`Config::MinBond`] and declaring
+//! their capacity (in bytes). Every blob reported through the [`OnBlobStored`] implementation of
+//! [`Pallet`] is assigned in the next block to up to [`Config::ReplicationFactor`] providers with
+//! enough free capacity, starting from a provider picked through [`Config::Randomness`]. Providers
+//! keep their assigned blobs for [`Config::StoragePeriod`] blocks, after which the assignments of
+//! the blobs of a block expire all at once, in a single block.
+//!
+//! Every [`Config::ChallengePeriod`] blocks, every provider is challenged to prove a random chunk
+//! of a random blob assigned to it, answered with [`Pallet::submit_proof`] before the next round.
+//! Accepted proofs are rewarded with [`Config::ProofReward`] from the reward pot of the pallet,
+//! missed ones are slashed by [`Config::SlashAmount`] from the bond into the pot. Runtimes fund the
+//! pot with blob fees through [`RewardPot`].
+//!
+//! The providers of blobs found unavailable through pallet-blobchallenge are slashed as well,
+//! through the [`OnBlobUnavailable`] implementation of [`Pallet`], paying
+//! [`Config::ChallengerReward`] of each slash to the challenger instead of the pot.
+
+// Ensure we're 'no_std' when compiling for WebAssembly.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::traits::{
+	fungible::{Balanced, Credit},
+	OnUnbalanced,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_blobchallenge::{verify_chunk_proof, ChallengeTarget, OnBlobUnavailable};
+use pallet_blobmanager::OnBlobStored;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+pub use pallet::*;
+
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+/// Subject of the randomness picking assigned providers and challenged chunks.
+const RANDOMNESS_SUBJECT: &[u8] = b"storageprovider";
+
+/// Registered storage provider.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct ProviderInfo<Balance> {
+	/// The bond held from the provider
+	pub bond: Balance,
+	/// The declared capacity (in bytes)
+	pub capacity: u64,
+	/// The capacity taken by assigned blobs (in bytes)
+	pub used: u64,
+}
+
+/// Blob assigned to providers.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct Assignment<BlockNumber> {
+	/// The block the blob is stored in
+	pub block_number: BlockNumber,
+	/// The index of the blob in the block
+	pub index: u32,
+	/// The size of the blob (in bytes)
+	pub size: u32,
+}
+
+/// Storage proof a provider is challenged to submit.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct ProofChallenge<BlockNumber> {
+	/// The block the blob is stored in
+	pub block_number: BlockNumber,
+	/// The index of the blob in the block
+	pub index: u32,
+	/// The chunk of the blob to prove
+	pub chunk: u32,
+	/// The last block at which the proof can be submitted
+	pub deadline: BlockNumber,
+}
+
+/// Helper setting up blobs to assign in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<BlockNumber> {
+	/// Stores `blob` in the current block through [`Config::Blobs`], returning its block number
+	/// and index.
+	fn store_blob(blob: Vec<u8>) -> (BlockNumber, u32);
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, Mutate, MutateHold},
+			tokens::{Fortitude, Precision, Preservation, Restriction},
+			Randomness,
+		},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_blobmanager::BlobProvider;
+	use sp_runtime::{
+		traits::{AccountIdConversion, CheckedSub, One, TrailingZeroInput, Zero},
+		Perbill, Saturating,
+	};
+
+	/// Balance of [`Config::Currency`].
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching hold reason
+		type RuntimeHoldReason: From<HoldReason>;
+		type WeightInfo: WeightInfo;
+		/// Currency holding bonds and paying rewards
+		type Currency: Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ Balanced<Self::AccountId>;
+		/// Provider of the assigned blobs, typically pallet-blobmanager
+		type Blobs: BlobProvider<BlockNumberFor<Self>, Self::Hash>;
+		/// Source of randomness picking assigned providers and challenged chunks
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+		/// Identifier of the pallet, deriving the account of the reward pot
+		type PalletId: Get<PalletId>;
+		/// The minimum bond of a provider to get blobs assigned
+		type MinBond: Get<BalanceOf<Self>>;
+		/// The maximum number of registered providers
+		type MaxProviders: Get<u32>;
+		/// The number of providers every blob is assigned to, if enough have free capacity
+		type ReplicationFactor: Get<u32>;
+		/// The maximum number of blobs assigned to a single provider at once
+		type MaxAssignments: Get<u32>;
+		/// The maximum number of blobs stored per block waiting to be assigned
+		type MaxPendingBlobs: Get<u32>;
+		/// The number of blocks after storing a blob during which its providers keep it
+		type StoragePeriod: Get<BlockNumberFor<Self>>;
+		/// The number of blocks between storage proof rounds, must not be zero
+		type ChallengePeriod: Get<BlockNumberFor<Self>>;
+		/// The reward of an accepted storage proof, paid from the reward pot
+		type ProofReward: Get<BalanceOf<Self>>;
+		/// The amount slashed from the bond for a missed storage proof or an unavailable blob
+		type SlashAmount: Get<BalanceOf<Self>>;
+		/// The share of the slash of an unavailable blob paid to the challenger who found it
+		type ChallengerReward: Get<Perbill>;
+		/// Helper setting up blobs to assign in benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<BlockNumberFor<Self>>;
+	}
+
+	/// Reasons for holding funds
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Bond of a storage provider
+		ProviderBond,
+	}
+
+	#[pallet::storage]
+	pub type Providers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ProviderInfo<BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type ProviderList<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxProviders>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Assignments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<Assignment<BlockNumberFor<T>>, T::MaxAssignments>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	pub type BlobProviders<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		u32,
+		BoundedVec<T::AccountId, T::ReplicationFactor>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	pub type PendingBlobs<T: Config> =
+		StorageValue<_, BoundedVec<Assignment<BlockNumberFor<T>>, T::MaxPendingBlobs>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type ProofChallenges<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		ProofChallenge<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	// Errors that can be returned by this pallet
+	#[pallet::error]
+	pub enum Error<T> {
+		// Account is already a registered provider
+		AlreadyRegistered,
+		// Account isn't a registered provider
+		NotRegistered,
+		// Bond is below the minimum bond
+		BondTooLow,
+		// Maximum number of providers reached
+		TooManyProviders,
+		// Provider still has blobs assigned
+		HasAssignments,
+		// Provider isn't challenged to submit a proof
+		NoProofChallenge,
+		// Challenged blob doesn't exist
+		BlobNotFound,
+		// Proof doesn't match the challenged chunk
+		InvalidProof,
+	}
+
+	// Events that can be emitted
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Provider registered with a bond and a capacity (in bytes)
+		ProviderRegistered { provider: T::AccountId, bond: BalanceOf<T>, capacity: u64 },
+		/// Provider declared a new capacity (in bytes)
+		CapacitySet { provider: T::AccountId, capacity: u64 },
+		/// Provider added to its bond
+		Bonded { provider: T::AccountId, amount: BalanceOf<T> },
+		/// Provider deregistered, its bond is released
+		ProviderDeregistered { provider: T::AccountId },
+		/// Blob assigned to providers
+		BlobAssigned { block_number: BlockNumberFor<T>, index: u32, providers: Vec<T::AccountId> },
+		/// Provider challenged to submit a storage proof
+		ProofChallenged { provider: T::AccountId, challenge: ProofChallenge<BlockNumberFor<T>> },
+		/// Storage proof accepted, the provider got rewarded from the reward pot
+		ProofAccepted { provider: T::AccountId, reward: BalanceOf<T> },
+		/// Bond of a provider slashed into the reward pot, less the reward of a challenger
+		Slashed { provider: T::AccountId, amount: BalanceOf<T> },
+		/// Challenger of an unavailable blob rewarded from the slash of one of its providers
+		ChallengerRewarded { challenger: T::AccountId, reward: BalanceOf<T> },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let providers = ProviderList::<T>::decode_len().unwrap_or(0) as u32;
+
+			// Assign blobs stored in the previous block, each walking up to every provider
+			let pending = PendingBlobs::<T>::take();
+			let mut weight = T::DbWeight::get().reads_writes(2, 1).saturating_add(
+				T::WeightInfo::assign_blob(providers).saturating_mul(pending.len() as u64),
+			);
+			for blob in pending {
+				Self::assign(blob);
+			}
+
+			// Expire blobs stored in the block that just left the storage period
+			if let Some(expired) =
+				now.checked_sub(&T::StoragePeriod::get().saturating_add(One::one()))
+			{
+				let blobs = Self::expire(expired);
+				weight.saturating_accrue(T::WeightInfo::expire_blobs(blobs));
+			}
+
+			// Start storage proof round
+			if (now % T::ChallengePeriod::get()).is_zero() {
+				Self::challenge_providers(now);
+				weight.saturating_accrue(T::WeightInfo::challenge_providers(providers));
+			}
+
+			weight
+		}
+
+		fn integrity_test() {
+			assert!(!T::ChallengePeriod::get().is_zero(), "ChallengePeriod must not be zero");
+		}
+	}
+
+	// Dispatchable functions
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register as storage provider, holding `bond` and declaring `capacity` (in bytes)
+		/// Callable by anyone not registered yet
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register())]
+		pub fn register(origin: OriginFor<T>, bond: BalanceOf<T>, capacity: u64) -> DispatchResult {
+			let provider = ensure_signed(origin)?;
+			ensure!(!Providers::<T>::contains_key(&provider), Error::<T>::AlreadyRegistered);
+			ensure!(bond >= T::MinBond::get(), Error::<T>::BondTooLow);
+
+			// Hold bond
+			T::Currency::hold(&HoldReason::ProviderBond.into(), &provider, bond)?;
+
+			// Register provider
+			ProviderList::<T>::try_append(&provider).map_err(|_| Error::<T>::TooManyProviders)?;
+			Providers::<T>::insert(&provider, ProviderInfo { bond, capacity, used: 0 });
+
+			// Emit ProviderRegistered event
+			Self::deposit_event(Event::ProviderRegistered { provider, bond, capacity });
+
+			Ok(())
+		}
+
+		/// Declare a new capacity (in bytes), only affecting future assignments
+		/// Callable by registered providers
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_capacity())]
+		pub fn set_capacity(origin: OriginFor<T>, capacity: u64) -> DispatchResult {
+			let provider = ensure_signed(origin)?;
+			Providers::<T>::try_mutate(&provider, |info| {
+				let info = info.as_mut().ok_or(Error::<T>::NotRegistered)?;
+				info.capacity = capacity;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			// Emit CapacitySet event
+			Self::deposit_event(Event::CapacitySet { provider, capacity });
+
+			Ok(())
+		}
+
+		/// Add `amount` to the bond, e.g. to get back above the minimum bond after slashes
+		/// Callable by registered providers
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::bond_extra())]
+		pub fn bond_extra(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let provider = ensure_signed(origin)?;
+			Providers::<T>::try_mutate(&provider, |info| {
+				let info = info.as_mut().ok_or(Error::<T>::NotRegistered)?;
+				T::Currency::hold(&HoldReason::ProviderBond.into(), &provider, amount)?;
+				info.bond = info.bond.saturating_add(amount);
+				Ok::<_, DispatchError>(())
+			})?;
+
+			// Emit Bonded event
+			Self::deposit_event(Event::Bonded { provider, amount });
+
+			Ok(())
+		}
+
+		/// Deregister, releasing the bond
+		/// Callable by registered providers without assigned blobs, which expire after the
+		/// storage period once the capacity is set to zero
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::deregister())]
+		pub fn deregister(origin: OriginFor<T>) -> DispatchResult {
+			let provider = ensure_signed(origin)?;
+			let info = Providers::<T>::get(&provider).ok_or(Error::<T>::NotRegistered)?;
+			ensure!(
+				Assignments::<T>::decode_len(&provider).unwrap_or(0) == 0,
+				Error::<T>::HasAssignments
+			);
+
+			// Release bond
+			T::Currency::release(
+				&HoldReason::ProviderBond.into(),
+				&provider,
+				info.bond,
+				Precision::BestEffort,
+			)?;
+
+			// Deregister provider
+			Providers::<T>::remove(&provider);
+			ProviderList::<T>::mutate(|providers| providers.retain(|p| p != &provider));
+
+			// Emit ProviderDeregistered event
+			Self::deposit_event(Event::ProviderDeregistered { provider });
+
+			Ok(())
+		}
+
+		/// Submit the challenged chunk and its proof against the chunk commitment of the blob,
+		/// getting rewarded from the reward pot
+		/// Callable by challenged providers until the next storage proof round
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::submit_proof(
+			proof.iter().fold(chunk.len(), |len, node| len.saturating_add(node.len())) as u32
+		))]
+		pub fn submit_proof(
+			origin: OriginFor<T>,
+			chunk: Vec<u8>,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let provider = ensure_signed(origin)?;
+			let challenge =
+				ProofChallenges::<T>::get(&provider).ok_or(Error::<T>::NoProofChallenge)?;
+
+			// Verify proof
+			let commitment = T::Blobs::chunk_commitment(challenge.block_number, challenge.index)
+				.ok_or(Error::<T>::BlobNotFound)?;
+			ensure!(
+				verify_chunk_proof::<T::Hashing>(&commitment, challenge.chunk, &chunk, &proof),
+				Error::<T>::InvalidProof
+			);
+			ProofChallenges::<T>::remove(&provider);
+
+			// Pay reward, as far as the pot holds enough
+			let pot = Self::account_id();
+			let reward = T::ProofReward::get().min(T::Currency::reducible_balance(
+				&pot,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			));
+			if !reward.is_zero() {
+				T::Currency::transfer(&pot, &provider, reward, Preservation::Preserve)?;
+			}
+
+			// Emit ProofAccepted event
+			Self::deposit_event(Event::ProofAccepted { provider, reward });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account of the reward pot
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Random pair of numbers for `subject`
+		fn random(subject: impl Encode) -> (u32, u32) {
+			let (seed, _) = T::Randomness::random(&(RANDOMNESS_SUBJECT, subject).encode());
+			Decode::decode(&mut TrailingZeroInput::new(seed.as_ref()))
+				.expect("input is padded with zeroes; qed")
+		}
+
+		/// Assign `blob` to up to [`Config::ReplicationFactor`] providers with enough free
+		/// capacity, walking the providers from a random one
+		pub(crate) fn assign(blob: Assignment<BlockNumberFor<T>>) {
+			let providers = ProviderList::<T>::get();
+			if providers.is_empty() {
+				return;
+			}
+
+			let (start, _) = Self::random((blob.block_number, blob.index));
+			let mut assigned = BoundedVec::<T::AccountId, T::ReplicationFactor>::new();
+			for i in 0..providers.len() {
+				let provider = &providers[(start as usize).wrapping_add(i) % providers.len()];
+				if assigned.len() as u32 >= T::ReplicationFactor::get() {
+					break;
+				}
+				if Self::try_assign(provider, blob) {
+					let _ = assigned.try_push(provider.clone());
+				}
+			}
+			if assigned.is_empty() {
+				return;
+			}
+
+			BlobProviders::<T>::insert(blob.block_number, blob.index, &assigned);
+
+			// Emit BlobAssigned event
+			Self::deposit_event(Event::BlobAssigned {
+				block_number: blob.block_number,
+				index: blob.index,
+				providers: assigned.into_inner(),
+			});
+		}
+
+		/// Assign `blob` to `provider`, if it is bonded and has enough free capacity
+		fn try_assign(provider: &T::AccountId, blob: Assignment<BlockNumberFor<T>>) -> bool {
+			Providers::<T>::try_mutate(provider, |info| {
+				let info = info.as_mut().ok_or(())?;
+				let used = info.used.saturating_add(blob.size.into());
+				if info.bond < T::MinBond::get() || used > info.capacity {
+					return Err(());
+				}
+				Assignments::<T>::try_append(provider, blob)?;
+				info.used = used;
+				Ok(())
+			})
+			.is_ok()
+		}
+
+		/// Drop the assignments of the blobs stored in block `block_number`, returning the number
+		/// of blobs they were for
+		pub(crate) fn expire(block_number: BlockNumberFor<T>) -> u32 {
+			let mut blobs = 0;
+			for (_, providers) in BlobProviders::<T>::drain_prefix(block_number) {
+				blobs += 1;
+				for provider in providers {
+					let mut freed = 0u64;
+					Assignments::<T>::mutate(&provider, |assignments| {
+						assignments.retain(|blob| {
+							let expired = blob.block_number <= block_number;
+							if expired {
+								freed = freed.saturating_add(blob.size.into());
+							}
+							!expired
+						})
+					});
+					if freed > 0 {
+						Providers::<T>::mutate(&provider, |info| {
+							if let Some(info) = info {
+								info.used = info.used.saturating_sub(freed);
+							}
+						});
+					}
+				}
+			}
+			blobs
+		}
+
+		/// Start a storage proof round: slash providers that missed the proof of the previous
+		/// round and challenge every provider to prove a random chunk of a random blob assigned to
+		/// it
+		pub(crate) fn challenge_providers(now: BlockNumberFor<T>) {
+			let deadline = now.saturating_add(T::ChallengePeriod::get()).saturating_sub(One::one());
+
+			for provider in ProviderList::<T>::get() {
+				// Slash missed proof
+				if ProofChallenges::<T>::take(&provider).is_some() {
+					Self::slash(&provider, None);
+				}
+
+				// Challenge random chunk of random assignment
+				let assignments = Assignments::<T>::get(&provider);
+				if assignments.is_empty() {
+					continue;
+				}
+				let (blob, chunk) = Self::random((now, &provider));
+				let blob = assignments[blob as usize % assignments.len()];
+				let Some(commitment) = T::Blobs::chunk_commitment(blob.block_number, blob.index)
+					.filter(|commitment| commitment.count > 0)
+				else {
+					continue;
+				};
+				let challenge = ProofChallenge {
+					block_number: blob.block_number,
+					index: blob.index,
+					chunk: chunk % commitment.count,
+					deadline,
+				};
+				ProofChallenges::<T>::insert(&provider, &challenge);
+
+				// Emit ProofChallenged event
+				Self::deposit_event(Event::ProofChallenged { provider, challenge });
+			}
+		}
+
+		/// Slash [`Config::SlashAmount`] from the bond of `provider` into the reward pot, paying
+		/// [`Config::ChallengerReward`] of it to `challenger` instead, if any
+		pub(crate) fn slash(provider: &T::AccountId, challenger: Option<&T::AccountId>) {
+			let Some(mut info) = Providers::<T>::get(provider) else { return };
+			let amount = T::SlashAmount::get().min(info.bond);
+
+			// Fails if the challenger would stay below the existential deposit, paying nothing
+			let reward = challenger.map_or_else(Zero::zero, |challenger| {
+				T::Currency::transfer_on_hold(
+					&HoldReason::ProviderBond.into(),
+					provider,
+					challenger,
+					T::ChallengerReward::get().mul_floor(amount),
+					Precision::BestEffort,
+					Restriction::Free,
+					Fortitude::Force,
+				)
+				.unwrap_or_default()
+			});
+
+			// Fails if the pot would stay below the existential deposit, slashing nothing more
+			let slashed = T::Currency::transfer_on_hold(
+				&HoldReason::ProviderBond.into(),
+				provider,
+				&Self::account_id(),
+				amount.saturating_sub(reward),
+				Precision::BestEffort,
+				Restriction::Free,
+				Fortitude::Force,
+			)
+			.unwrap_or_default();
+			let amount = reward.saturating_add(slashed);
+			info.bond = info.bond.saturating_sub(amount);
+			Providers::<T>::insert(provider, info);
+
+			// Emit Slashed event
+			Self::deposit_event(Event::Slashed { provider: provider.clone(), amount });
+
+			// Emit ChallengerRewarded event
+			if let Some(challenger) = challenger.filter(|_| !reward.is_zero()) {
+				Self::deposit_event(Event::ChallengerRewarded {
+					challenger: challenger.clone(),
+					reward,
+				});
+			}
+		}
+	}
+}
+
+impl<T: Config> OnBlobStored<T::AccountId, BlockNumberFor<T>, T::Hash> for Pallet<T> {
+	fn on_blob_stored(
+		_uploader: &T::AccountId,
+		block_number: BlockNumberFor<T>,
+		index: u32,
+		_hash: T::Hash,
+		size: u32,
+	) {
+		// Assigned in the next block, blobs beyond `MaxPendingBlobs` don't get replicated
+		let _ = PendingBlobs::<T>::try_append(Assignment { block_number, index, size });
+	}
+}
+
+impl<T: Config> OnBlobUnavailable<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+	fn on_blob_unavailable(challenger: &T::AccountId, target: &ChallengeTarget<BlockNumberFor<T>>) {
+		for provider in BlobProviders::<T>::get(target.block_number, target.index) {
+			Self::slash(&provider, Some(challenger));
+		}
+	}
+}
+
+/// Handler depositing imbalances, e.g. the fees of blob uploads, into the reward pot.
+pub struct RewardPot<T>(PhantomData<T>);
+
+impl<T: Config> OnUnbalanced<Credit<T::AccountId, T::Currency>> for RewardPot<T> {
+	fn on_nonzero_unbalanced(amount: Credit<T::AccountId, T::Currency>) {
+		// Dropped, and so burned, if the pot would stay below the existential deposit
+		let _ = T::Currency::resolve(&Pallet::<T>::account_id(), amount);
+	}
+}
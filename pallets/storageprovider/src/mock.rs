@@ -0,0 +1,146 @@
+#![cfg(test)]
+use super::*;
+use crate as pallet_storageprovider;
+use frame_support::{
+	derive_impl, parameter_types,
+	sp_runtime::BuildStorage,
+	traits::{ConstU32, ConstU64, Randomness},
+	PalletId,
+};
+use pallet_blobmanager::{blob_chunk_commitment, BlobMeta, BlobProvider, ChunkCommitment};
+use sp_runtime::{
+	testing::H256,
+	traits::{BlakeTwo256, Hash as _},
+	Perbill,
+};
+use std::collections::BTreeMap;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+pub enum Test {
+	System: frame_system,
+	Balances: pallet_balances,
+	StorageProvider: pallet_storageprovider,
+}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// Minimum bond of providers
+pub const MIN_BOND: u64 = 50;
+/// Reward of accepted proofs
+pub const REWARD: u64 = 5;
+/// Amount slashed for missed proofs
+pub const SLASH: u64 = 20;
+
+parameter_types! {
+	pub const StorageProviderPalletId: PalletId = PalletId(*b"py/stprv");
+	pub const ChallengerReward: Perbill = Perbill::from_percent(50);
+	/// Blobs served by [`MockBlobs`], by `(block_number, index)`
+	pub static StoredBlobs: BTreeMap<(u64, u32), Vec<u8>> = BTreeMap::new();
+	/// Value returned by [`MockRandomness`]
+	pub static RandomValue: H256 = H256::zero();
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type Blobs = MockBlobs;
+	type Randomness = MockRandomness;
+	type PalletId = StorageProviderPalletId;
+	type MinBond = ConstU64<MIN_BOND>;
+	type MaxProviders = ConstU32<3>;
+	type ReplicationFactor = ConstU32<2>;
+	type MaxAssignments = ConstU32<4>;
+	type MaxPendingBlobs = ConstU32<4>;
+	type StoragePeriod = ConstU64<20>;
+	type ChallengePeriod = ConstU64<10>;
+	type ProofReward = ConstU64<REWARD>;
+	type SlashAmount = ConstU64<SLASH>;
+	type ChallengerReward = ChallengerReward;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBlobs;
+}
+
+/// Stores `blob` at `index` in block `block_number` of [`MockBlobs`] and reports it to the
+/// pallet
+pub fn store_blob(block_number: u64, index: u32, blob: Vec<u8>) {
+	let hash = BlakeTwo256::hash(&blob);
+	let size = blob.len() as u32;
+	StoredBlobs::mutate(|blobs| blobs.insert((block_number, index), blob));
+	StorageProvider::on_blob_stored(&0, block_number, index, hash, size);
+}
+
+/// Blob provider serving [`StoredBlobs`]
+pub struct MockBlobs;
+
+impl BlobProvider<u64, H256> for MockBlobs {
+	fn blob_count(block_number: u64) -> u32 {
+		StoredBlobs::get().keys().filter(|(number, _)| *number == block_number).count() as u32
+	}
+
+	fn blob(block_number: u64, index: u32) -> Option<Vec<u8>> {
+		StoredBlobs::get().get(&(block_number, index)).cloned()
+	}
+
+	fn blob_hash(block_number: u64, index: u32) -> Option<H256> {
+		Self::blob(block_number, index).map(|blob| BlakeTwo256::hash(&blob))
+	}
+
+	fn blob_meta(_block_number: u64, _index: u32) -> Option<BlobMeta<u64>> {
+		None
+	}
+
+	fn chunk_commitment(block_number: u64, index: u32) -> Option<ChunkCommitment<H256>> {
+		Self::blob(block_number, index).map(|blob| blob_chunk_commitment::<BlakeTwo256>(&blob))
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<u64> for MockBlobs {
+	fn store_blob(blob: Vec<u8>) -> (u64, u32) {
+		let block_number = System::block_number();
+		let index = Self::blob_count(block_number);
+		store_blob(block_number, index, blob);
+		(block_number, index)
+	}
+}
+
+/// Randomness returning [`RandomValue`] for every subject
+pub struct MockRandomness;
+
+impl Randomness<H256, u64> for MockRandomness {
+	fn random(_subject: &[u8]) -> (H256, u64) {
+		(RandomValue::get(), System::block_number())
+	}
+}
+
+// Build genesis storage according to the mock runtime
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	// Fund Providers Accounts 1 to 4, Reward Pot
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(1, 100),
+			(2, 100),
+			(3, 100),
+			(4, 100),
+			(StorageProvider::account_id(), 100),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	t.into()
+}
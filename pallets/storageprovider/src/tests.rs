@@ -0,0 +1,331 @@
+#![cfg(test)]
+use super::*;
+use crate::{
+	mock::*, Assignment, Assignments, BlobProviders, Error, Event, PendingBlobs, ProofChallenge,
+	ProofChallenges, ProviderInfo, ProviderList, Providers,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungible::Balanced, Hooks, OnUnbalanced},
+};
+use pallet_blobchallenge::chunk_proof;
+use pallet_blobmanager::BLOB_CHUNK_SIZE;
+use sp_runtime::{testing::H256, traits::BlakeTwo256};
+
+/// Registers `provider` with the minimum bond and `capacity`
+fn register(provider: u64, capacity: u64) {
+	assert_ok!(StorageProvider::register(RuntimeOrigin::signed(provider), MIN_BOND, capacity));
+}
+
+/// Runs the hooks of every block up to `n`
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		StorageProvider::on_initialize(System::block_number());
+	}
+}
+
+/// Random value making the pallet start walking the providers at `start`
+fn random_start(start: u8) -> H256 {
+	let mut value = [0u8; 32];
+	value[0] = start;
+	H256(value)
+}
+
+fn info(provider: u64) -> ProviderInfo<u64> {
+	Providers::<Test>::get(provider).unwrap()
+}
+
+#[test]
+fn register_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register(1, 1000);
+
+		assert_eq!(info(1), ProviderInfo { bond: MIN_BOND, capacity: 1000, used: 0 });
+		assert_eq!(ProviderList::<Test>::get().into_inner(), vec![1]);
+		assert_eq!(Balances::reserved_balance(1), MIN_BOND);
+		System::assert_last_event(
+			Event::ProviderRegistered { provider: 1, bond: MIN_BOND, capacity: 1000 }.into(),
+		);
+	})
+}
+
+#[test]
+fn register_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			StorageProvider::register(RuntimeOrigin::signed(1), MIN_BOND - 1, 1000),
+			Error::<Test>::BondTooLow
+		);
+
+		register(1, 1000);
+		assert_noop!(
+			StorageProvider::register(RuntimeOrigin::signed(1), MIN_BOND, 1000),
+			Error::<Test>::AlreadyRegistered
+		);
+
+		register(2, 1000);
+		register(3, 1000);
+		assert_noop!(
+			StorageProvider::register(RuntimeOrigin::signed(4), MIN_BOND, 1000),
+			Error::<Test>::TooManyProviders
+		);
+	})
+}
+
+#[test]
+fn set_capacity_and_bond_extra_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StorageProvider::set_capacity(RuntimeOrigin::signed(1), 500),
+			Error::<Test>::NotRegistered
+		);
+		assert_noop!(
+			StorageProvider::bond_extra(RuntimeOrigin::signed(1), 10),
+			Error::<Test>::NotRegistered
+		);
+
+		register(1, 1000);
+		assert_ok!(StorageProvider::set_capacity(RuntimeOrigin::signed(1), 500));
+		System::assert_last_event(Event::CapacitySet { provider: 1, capacity: 500 }.into());
+		assert_ok!(StorageProvider::bond_extra(RuntimeOrigin::signed(1), 10));
+		System::assert_last_event(Event::Bonded { provider: 1, amount: 10 }.into());
+
+		assert_eq!(info(1), ProviderInfo { bond: MIN_BOND + 10, capacity: 500, used: 0 });
+		assert_eq!(Balances::reserved_balance(1), MIN_BOND + 10);
+	})
+}
+
+#[test]
+fn blobs_are_assigned_in_next_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 1000);
+		register(2, 1000);
+		register(3, 1000);
+
+		store_blob(1, 0, vec![1u8; 100]);
+		assert_eq!(PendingBlobs::<Test>::get().len(), 1);
+
+		// Walk starts at provider 1
+		run_to_block(2);
+		assert!(PendingBlobs::<Test>::get().is_empty());
+		assert_eq!(BlobProviders::<Test>::get(1, 0).into_inner(), vec![1, 2]);
+		assert_eq!(
+			Assignments::<Test>::get(1).into_inner(),
+			vec![Assignment { block_number: 1, index: 0, size: 100 }]
+		);
+		assert_eq!(info(1).used, 100);
+		assert_eq!(info(3).used, 0);
+		System::assert_last_event(
+			Event::BlobAssigned { block_number: 1, index: 0, providers: vec![1, 2] }.into(),
+		);
+
+		// Walk starts at provider 3
+		RandomValue::set(random_start(2));
+		store_blob(2, 0, vec![2u8; 100]);
+		run_to_block(3);
+		assert_eq!(BlobProviders::<Test>::get(2, 0).into_inner(), vec![3, 1]);
+	})
+}
+
+#[test]
+fn assignment_skips_full_and_underbonded_providers() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 50);
+		register(2, 1000);
+		register(3, 1000);
+		Providers::<Test>::mutate(3, |info| info.as_mut().unwrap().bond = MIN_BOND - 1);
+
+		store_blob(1, 0, vec![1u8; 100]);
+		run_to_block(2);
+		assert_eq!(BlobProviders::<Test>::get(1, 0).into_inner(), vec![2]);
+
+		// No provider with free capacity left
+		assert_ok!(StorageProvider::set_capacity(RuntimeOrigin::signed(2), 100));
+		store_blob(2, 0, vec![2u8; 100]);
+		run_to_block(3);
+		assert!(BlobProviders::<Test>::get(2, 0).is_empty());
+		assert!(Assignments::<Test>::get(1).is_empty());
+	})
+}
+
+#[test]
+fn assignments_expire_after_storage_period() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 1000);
+		register(2, 1000);
+		store_blob(1, 0, vec![1u8; 100]);
+		store_blob(1, 1, vec![2u8; 200]);
+		run_to_block(2);
+		store_blob(2, 0, vec![3u8; 50]);
+		run_to_block(3);
+		assert_eq!(info(1).used, 350);
+
+		// Kept for the storage period
+		run_to_block(21);
+		assert_eq!(Assignments::<Test>::get(1).len(), 3);
+		assert_eq!(BlobProviders::<Test>::get(1, 1).into_inner(), vec![1, 2]);
+
+		// Blobs of block 1 expire together, those of block 2 in the next block
+		run_to_block(22);
+		assert_eq!(
+			Assignments::<Test>::get(1).into_inner(),
+			vec![Assignment { block_number: 2, index: 0, size: 50 }]
+		);
+		assert!(BlobProviders::<Test>::get(1, 0).is_empty());
+		assert!(BlobProviders::<Test>::get(1, 1).is_empty());
+		assert_eq!(info(1).used, 50);
+		assert_eq!(info(2).used, 50);
+
+		run_to_block(23);
+		assert!(Assignments::<Test>::get(1).is_empty());
+		assert!(Assignments::<Test>::get(2).is_empty());
+		assert_eq!(info(1).used, 0);
+	})
+}
+
+#[test]
+fn submit_proof_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 1_000_000);
+		let blob = (0..BLOB_CHUNK_SIZE + 100).map(|i| i as u8).collect::<Vec<_>>();
+		store_blob(1, 0, blob.clone());
+
+		// Challenged in the next round
+		run_to_block(10);
+		let challenge = ProofChallenge { block_number: 1, index: 0, chunk: 0, deadline: 19 };
+		assert_eq!(ProofChallenges::<Test>::get(1), Some(challenge.clone()));
+		System::assert_last_event(Event::ProofChallenged { provider: 1, challenge }.into());
+
+		let (chunk, proof) = chunk_proof::<BlakeTwo256>(&blob, 0).unwrap();
+		assert_ok!(StorageProvider::submit_proof(RuntimeOrigin::signed(1), chunk, proof));
+
+		assert_eq!(ProofChallenges::<Test>::get(1), None);
+		assert_eq!(Balances::free_balance(1), 100 - MIN_BOND + REWARD);
+		assert_eq!(Balances::free_balance(StorageProvider::account_id()), 100 - REWARD);
+		System::assert_last_event(Event::ProofAccepted { provider: 1, reward: REWARD }.into());
+
+		// Not slashed in the next round
+		run_to_block(20);
+		assert_eq!(info(1).bond, MIN_BOND);
+	})
+}
+
+#[test]
+fn submit_proof_fails() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 1_000_000);
+		let blob = (0..BLOB_CHUNK_SIZE + 100).map(|i| i as u8).collect::<Vec<_>>();
+		store_blob(1, 0, blob.clone());
+
+		let (chunk, proof) = chunk_proof::<BlakeTwo256>(&blob, 1).unwrap();
+		assert_noop!(
+			StorageProvider::submit_proof(RuntimeOrigin::signed(1), chunk.clone(), proof.clone()),
+			Error::<Test>::NoProofChallenge
+		);
+
+		// Challenged for chunk 0
+		run_to_block(10);
+		assert_noop!(
+			StorageProvider::submit_proof(RuntimeOrigin::signed(1), chunk, proof),
+			Error::<Test>::InvalidProof
+		);
+	})
+}
+
+#[test]
+fn missed_proofs_are_slashed() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 1000);
+		store_blob(1, 0, vec![1u8; 100]);
+		run_to_block(10);
+
+		run_to_block(20);
+		assert_eq!(info(1).bond, MIN_BOND - SLASH);
+		assert_eq!(Balances::reserved_balance(1), MIN_BOND - SLASH);
+		assert_eq!(Balances::free_balance(StorageProvider::account_id()), 100 + SLASH);
+		System::assert_has_event(Event::Slashed { provider: 1, amount: SLASH }.into());
+
+		// Expired assignments are dropped and no longer challenged
+		run_to_block(30);
+		assert_eq!(info(1), ProviderInfo { bond: MIN_BOND - 2 * SLASH, capacity: 1000, used: 0 });
+		assert!(Assignments::<Test>::get(1).is_empty());
+		assert!(BlobProviders::<Test>::get(1, 0).is_empty());
+		assert_eq!(ProofChallenges::<Test>::get(1), None);
+	})
+}
+
+#[test]
+fn deregister_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_noop!(
+			StorageProvider::deregister(RuntimeOrigin::signed(1)),
+			Error::<Test>::NotRegistered
+		);
+
+		register(1, 1000);
+		store_blob(1, 0, vec![1u8; 100]);
+		run_to_block(2);
+		assert_noop!(
+			StorageProvider::deregister(RuntimeOrigin::signed(1)),
+			Error::<Test>::HasAssignments
+		);
+
+		// Assignment expires at block 22, after the storage period, the missed proofs of rounds 10
+		// and 20 are slashed
+		assert_ok!(StorageProvider::set_capacity(RuntimeOrigin::signed(1), 0));
+		run_to_block(30);
+		assert_ok!(StorageProvider::deregister(RuntimeOrigin::signed(1)));
+
+		assert_eq!(Providers::<Test>::get(1), None);
+		assert!(ProviderList::<Test>::get().is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100 - 2 * SLASH);
+		System::assert_last_event(Event::ProviderDeregistered { provider: 1 }.into());
+	})
+}
+
+#[test]
+fn unavailable_blobs_slash_their_providers() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		register(1, 1000);
+		register(2, 1000);
+		register(3, 50);
+		store_blob(1, 0, vec![1u8; 100]);
+		run_to_block(2);
+
+		StorageProvider::on_blob_unavailable(
+			&4,
+			&ChallengeTarget { block_number: 1, index: 0, chunk: None },
+		);
+
+		assert_eq!(info(1).bond, MIN_BOND - SLASH);
+		assert_eq!(info(2).bond, MIN_BOND - SLASH);
+		assert_eq!(info(3).bond, MIN_BOND);
+
+		// Half of each slash goes to the challenger, the rest into the pot
+		assert_eq!(Balances::free_balance(4), 100 + SLASH);
+		assert_eq!(Balances::free_balance(StorageProvider::account_id()), 100 + SLASH);
+		System::assert_has_event(
+			Event::ChallengerRewarded { challenger: 4, reward: SLASH / 2 }.into(),
+		);
+	})
+}
+
+#[test]
+fn reward_pot_takes_imbalances() {
+	new_test_ext().execute_with(|| {
+		RewardPot::<Test>::on_unbalanced(Balances::issue(10));
+		assert_eq!(Balances::free_balance(StorageProvider::account_id()), 110);
+	})
+}
@@ -0,0 +1,295 @@
+//! Weights for `pallet_storageprovider`
+//!
+//! None of these weights come from a benchmark run yet. They are estimated by hand from the
+//! storage accesses listed above each of them, until the benchmarks in `benchmarking.rs` are run
+//! on reference hardware. `submit_proof` scales with the proof size `b` for hashing the proof
+//! nodes. `assign_blob` and `challenge_providers` scale with the number of providers `p` they go
+//! through, and `expire_blobs` with the number of blobs `b` whose assignments expire, each
+//! assigned to 3 providers as the runtime configures it.
+
+// Command to regenerate:
+// target/release/blobchain-node
+// benchmark
+// pallet
+// --runtime
+// target/release/wbuild/blobchain-runtime/blobchain_runtime.compact.compressed.wasm
+// --genesis-builder=runtime
+// --pallet
+// pallet_storageprovider
+// --extrinsic
+// *
+// --template
+// ../../frame-weight-template.hbs
+// --output
+// pallets/storageprovider/src/weights.rs
+// --steps
+// 20
+// --repeat
+// 50
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_storageprovider`.
+pub trait WeightInfo {
+	fn register() -> Weight;
+	fn set_capacity() -> Weight;
+	fn bond_extra() -> Weight;
+	fn deregister() -> Weight;
+	fn submit_proof(b: u32, ) -> Weight;
+	fn assign_blob(p: u32, ) -> Weight;
+	fn expire_blobs(b: u32, ) -> Weight;
+	fn challenge_providers(p: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_storageprovider` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:1)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	fn register() -> Weight {
+		Weight::from_parts(49_870_000, 4687)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn set_capacity() -> Weight {
+		Weight::from_parts(11_610_000, 3545)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn bond_extra() -> Weight {
+		Weight::from_parts(42_550_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:1 w:0)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:1)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	fn deregister() -> Weight {
+		Weight::from_parts(47_260_000, 15515)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `StorageProvider::ProofChallenges` (r:1 w:1)
+	/// Proof: `StorageProvider::ProofChallenges` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:1 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[1, 270336]`.
+	fn submit_proof(b: u32, ) -> Weight {
+		Weight::from_parts(55_482_000, 6196)
+			.saturating_add(Weight::from_parts(1_291, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:0)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// Storage: `RandomnessCollectiveFlip::RandomMaterial` (r:1 w:0)
+	/// Proof: `RandomnessCollectiveFlip::RandomMaterial` (`max_values`: Some(1), `max_size`: Some(2594), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:100 w:2)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:2 w:2)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::BlobProviders` (r:0 w:1)
+	/// Proof: `StorageProvider::BlobProviders` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 100]`.
+	fn assign_blob(p: u32, ) -> Weight {
+		Weight::from_parts(31_204_000, 29040)
+			.saturating_add(Weight::from_parts(2_317_480, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(p.into()))
+	}
+	/// Storage: `StorageProvider::BlobProviders` (r:6 w:5)
+	/// Proof: `StorageProvider::BlobProviders` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:15 w:15)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:15 w:15)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5]`.
+	fn expire_blobs(b: u32, ) -> Weight {
+		Weight::from_parts(3_021_000, 3594)
+			.saturating_add(Weight::from_parts(47_862_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 53844).saturating_mul(b.into()))
+	}
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:0)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// Storage: `RandomnessCollectiveFlip::RandomMaterial` (r:1 w:0)
+	/// Proof: `RandomnessCollectiveFlip::RandomMaterial` (`max_values`: Some(1), `max_size`: Some(2594), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::ProofChallenges` (r:100 w:100)
+	/// Proof: `StorageProvider::ProofChallenges` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:100 w:100)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:100 w:100)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:101 w:101)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:100 w:0)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:100 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 100]`.
+	fn challenge_providers(p: u32, ) -> Weight {
+		Weight::from_parts(8_903_000, 4687)
+			.saturating_add(Weight::from_parts(68_214_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 27361).saturating_mul(p.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:1)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	fn register() -> Weight {
+		Weight::from_parts(49_870_000, 4687)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn set_capacity() -> Weight {
+		Weight::from_parts(11_610_000, 3545)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn bond_extra() -> Weight {
+		Weight::from_parts(42_550_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `StorageProvider::Providers` (r:1 w:1)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:1 w:0)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:1)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	fn deregister() -> Weight {
+		Weight::from_parts(47_260_000, 15515)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `StorageProvider::ProofChallenges` (r:1 w:1)
+	/// Proof: `StorageProvider::ProofChallenges` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:1 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[1, 270336]`.
+	fn submit_proof(b: u32, ) -> Weight {
+		Weight::from_parts(55_482_000, 6196)
+			.saturating_add(Weight::from_parts(1_291, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:0)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// Storage: `RandomnessCollectiveFlip::RandomMaterial` (r:1 w:0)
+	/// Proof: `RandomnessCollectiveFlip::RandomMaterial` (`max_values`: Some(1), `max_size`: Some(2594), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:100 w:2)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:2 w:2)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::BlobProviders` (r:0 w:1)
+	/// Proof: `StorageProvider::BlobProviders` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 100]`.
+	fn assign_blob(p: u32, ) -> Weight {
+		Weight::from_parts(31_204_000, 29040)
+			.saturating_add(Weight::from_parts(2_317_480, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(p.into()))
+	}
+	/// Storage: `StorageProvider::BlobProviders` (r:6 w:5)
+	/// Proof: `StorageProvider::BlobProviders` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:15 w:15)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:15 w:15)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// The range of component `b` is `[0, 5]`.
+	fn expire_blobs(b: u32, ) -> Weight {
+		Weight::from_parts(3_021_000, 3594)
+			.saturating_add(Weight::from_parts(47_862_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 53844).saturating_mul(b.into()))
+	}
+	/// Storage: `StorageProvider::ProviderList` (r:1 w:0)
+	/// Proof: `StorageProvider::ProviderList` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
+	/// Storage: `RandomnessCollectiveFlip::RandomMaterial` (r:1 w:0)
+	/// Proof: `RandomnessCollectiveFlip::RandomMaterial` (`max_values`: Some(1), `max_size`: Some(2594), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::ProofChallenges` (r:100 w:100)
+	/// Proof: `StorageProvider::ProofChallenges` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Providers` (r:100 w:100)
+	/// Proof: `StorageProvider::Providers` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:100 w:100)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:101 w:101)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StorageProvider::Assignments` (r:100 w:0)
+	/// Proof: `StorageProvider::Assignments` (`max_values`: None, `max_size`: Some(12050), added: 14525, mode: `MaxEncodedLen`)
+	/// Storage: `BlobManager::BlobChunkCommitments` (r:100 w:0)
+	/// Proof: `BlobManager::BlobChunkCommitments` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 100]`.
+	fn challenge_providers(p: u32, ) -> Weight {
+		Weight::from_parts(8_903_000, 4687)
+			.saturating_add(Weight::from_parts(68_214_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 27361).saturating_mul(p.into()))
+	}
+}
@@ -0,0 +1,256 @@
+//! # Blobchain Verifier
+//!
+//! Verification of the blob proofs served by blobchain nodes, for light clients that only
+//! follow headers.
+//!
+//! A storage proof, as returned by the `blob_getBlobWithProof` RPC method, is a compact trie
+//! proof of the `BlobManager::Blobs` entry of a block against the state root of that block's
+//! header. [`verify_blob_storage_proof`] checks it and returns the proven blob.
+//!
+//! An inclusion proof, as returned by the `blob_getInclusionProof` RPC method, is a trie proof of
+//! an `upload_blob` extrinsic against the extrinsics root of the header of the block that
+//! contains it. It stays available after the state of the block has been pruned.
+//! [`verify_blob_inclusion_proof`] checks it and recovers the blob from the extrinsic.
+//!
+//! Every header carries the root of a Merkle Mountain Range over the blobs of all blocks before
+//! it. With a single trusted recent header, [`verify_mmr_leaves_proof`] checks the MMR leaves of
+//! older blocks returned by the `mmr_generateProof` RPC method, and
+//! [`verify_blob_commitment_proof`] checks a blob hash returned by the `blob_getCommitmentProof`
+//! RPC method against the blob root of such a leaf, without any state or older header.
+//!
+//! Every channel keeps a hash chain over its messages. Starting from a trusted checkpoint, the
+//! sequence number and accumulator of a channel at some block, [`verify_channel_history`] checks
+//! that a list of message blob hashes is the complete history of the channel up to a later head.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+use codec::{Compact, Decode, Encode};
+use sp_crypto_hashing::{blake2_128, twox_128};
+use sp_mmr_primitives::{
+	mmr_lib, utils::NodesUtils, DataOrHash, EncodableOpaqueLeaf, LeafProof, OpaqueLeaf,
+};
+use sp_runtime::{
+	traits::{Hash as HashT, Header as HeaderT},
+	ConsensusEngineId,
+};
+use sp_trie::{CompactProof, LayoutV0, LayoutV1};
+
+mod tests;
+
+/// Name of the blob manager pallet in the runtime.
+pub const PALLET_NAME: &[u8] = b"BlobManager";
+
+/// Name of the storage map holding the blobs of each block.
+pub const BLOBS_STORAGE_NAME: &[u8] = b"Blobs";
+
+/// Engine id of the header digest item carrying the MMR root, as deposited by the runtime.
+pub const MMR_ROOT_ENGINE_ID: ConsensusEngineId = *b"bmmr";
+
+/// Error verifying a proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+	/// The proof doesn't match the root it is checked against, or misses nodes.
+	InvalidProof,
+	/// The proven value couldn't be decoded.
+	InvalidValue,
+	/// The proof shows there is no blob at the requested index, or the proven extrinsic doesn't
+	/// upload a blob.
+	BlobNotFound,
+	/// The channel messages don't lead from the checkpoint to the head.
+	IncompleteHistory,
+	/// The header doesn't carry an MMR root.
+	MmrRootNotFound,
+}
+
+/// Storage key of the `Blobs` entry of block `block_number`.
+pub fn blobs_storage_key<BlockNumber: Encode>(block_number: BlockNumber) -> Vec<u8> {
+	let encoded = block_number.encode();
+	[
+		&twox_128(PALLET_NAME)[..],
+		&twox_128(BLOBS_STORAGE_NAME)[..],
+		&blake2_128(&encoded)[..],
+		&encoded[..],
+	]
+	.concat()
+}
+
+/// Checks `proof` against the state root of `header` and returns the blob stored at `index` of
+/// the block.
+pub fn verify_blob_storage_proof<Header: HeaderT>(
+	header: &Header,
+	index: u32,
+	proof: &CompactProof,
+) -> Result<Vec<u8>, Error> {
+	let root = *header.state_root();
+	let (db, _) = proof
+		.to_memory_db::<Header::Hashing>(Some(&root))
+		.map_err(|_| Error::InvalidProof)?;
+	let value = sp_trie::read_trie_value::<LayoutV1<Header::Hashing>, _>(
+		&db,
+		&root,
+		&blobs_storage_key(*header.number()),
+		None,
+		None,
+	)
+	.map_err(|_| Error::InvalidProof)?;
+
+	let blobs = value
+		.map(|value| Vec::<Vec<u8>>::decode(&mut &value[..]))
+		.transpose()
+		.map_err(|_| Error::InvalidValue)?
+		.unwrap_or_default();
+	blobs.into_iter().nth(index as usize).ok_or(Error::BlobNotFound)
+}
+
+/// Key of extrinsic `index` in the extrinsics trie of a block.
+pub fn extrinsic_key(index: u32) -> Vec<u8> {
+	Compact(index).encode()
+}
+
+/// Checks that `extrinsic` is the encoded extrinsic at `index` of the block of `header` using
+/// `proof`, then decodes it and returns the blob it uploads.
+///
+/// `blob_of` returns the payload of the extrinsic if it is an `upload_blob` extrinsic. With the
+/// runtime types at hand, it is typically:
+///
+/// ```ignore
+/// |xt: UncheckedExtrinsic| match xt.function {
+/// 	RuntimeCall::BlobManager(pallet_blobmanager::Call::upload_blob { blob }) => Some(blob),
+/// 	_ => None,
+/// }
+/// ```
+pub fn verify_blob_inclusion_proof<Header: HeaderT, Extrinsic: Decode>(
+	header: &Header,
+	index: u32,
+	extrinsic: &[u8],
+	proof: &[Vec<u8>],
+	blob_of: impl FnOnce(Extrinsic) -> Option<Vec<u8>>,
+) -> Result<Vec<u8>, Error> {
+	// The extrinsics root is always built with the V0 layout.
+	sp_trie::verify_trie_proof::<LayoutV0<Header::Hashing>, _, _, _>(
+		header.extrinsics_root(),
+		proof,
+		&[(extrinsic_key(index), Some(extrinsic))],
+	)
+	.map_err(|_| Error::InvalidProof)?;
+
+	let extrinsic = Extrinsic::decode(&mut &extrinsic[..]).map_err(|_| Error::InvalidValue)?;
+	blob_of(extrinsic).ok_or(Error::BlobNotFound)
+}
+
+/// Accumulator of a channel after appending message `sequence`, the blob with hash `hash`, to a
+/// channel with accumulator `accumulator`.
+///
+/// Same as `pallet_blobmanager::channel_accumulator`.
+pub fn channel_accumulator<Hashing: HashT>(
+	accumulator: Hashing::Output,
+	sequence: u64,
+	hash: Hashing::Output,
+) -> Hashing::Output {
+	Hashing::hash_of(&(accumulator, sequence, hash))
+}
+
+/// Checks that `hashes`, the blob hashes of the messages following the `(sequence, accumulator)`
+/// checkpoint of a channel in order, are all messages up to the `(sequence, accumulator)` head
+/// of the channel.
+pub fn verify_channel_history<Hashing: HashT>(
+	checkpoint: (u64, Hashing::Output),
+	hashes: &[Hashing::Output],
+	head: (u64, Hashing::Output),
+) -> Result<(), Error> {
+	let (mut sequence, mut accumulator) = checkpoint;
+	for hash in hashes {
+		sequence = sequence.checked_add(1).ok_or(Error::IncompleteHistory)?;
+		accumulator = channel_accumulator::<Hashing>(accumulator, sequence, *hash);
+	}
+
+	if (sequence, accumulator) == head {
+		Ok(())
+	} else {
+		Err(Error::IncompleteHistory)
+	}
+}
+
+/// Checks that `blob_hash` is the hash of the blob at `index` of a block with blob root
+/// `blob_root` using `proof`.
+///
+/// The blob root is the root of the ordered trie of the blob hashes of the block, like the
+/// extrinsics root, as computed by `pallet_blobmanager::blob_commitment_root`.
+pub fn verify_blob_commitment_proof<Hashing: HashT>(
+	blob_root: Hashing::Output,
+	index: u32,
+	blob_hash: Hashing::Output,
+	proof: &[Vec<u8>],
+) -> Result<(), Error> {
+	sp_trie::verify_trie_proof::<LayoutV0<Hashing>, _, _, _>(
+		&blob_root,
+		proof,
+		&[(extrinsic_key(index), Some(blob_hash.as_ref()))],
+	)
+	.map_err(|_| Error::InvalidProof)
+}
+
+/// Returns the MMR root carried by the digest of `header`, if any.
+pub fn mmr_root<Header: HeaderT>(header: &Header) -> Option<Header::Hash> {
+	header.digest().convert_first(|item| item.consensus_try_to(&MMR_ROOT_ENGINE_ID))
+}
+
+/// Checks the SCALE encoded MMR `leaves` of older blocks against the MMR root of `header` using
+/// the SCALE encoded `proof`, both as returned by the `mmr_generateProof` RPC method, then
+/// decodes the leaves.
+///
+/// With the runtime types at hand, `Leaf` is `pallet_blobmanager::BlobMmrLeaf<BlockNumber, Hash>`.
+pub fn verify_mmr_leaves_proof<Header: HeaderT, Leaf: Decode>(
+	header: &Header,
+	leaves: &[u8],
+	proof: &[u8],
+) -> Result<Vec<Leaf>, Error> {
+	let root = mmr_root(header).ok_or(Error::MmrRootNotFound)?;
+	let leaves = Vec::<EncodableOpaqueLeaf>::decode(&mut &leaves[..])
+		.map_err(|_| Error::InvalidValue)?
+		.into_iter()
+		.map(EncodableOpaqueLeaf::into_opaque_leaf)
+		.collect::<Vec<_>>();
+	let proof =
+		LeafProof::<Header::Hash>::decode(&mut &proof[..]).map_err(|_| Error::InvalidProof)?;
+	if leaves.len() != proof.leaf_indices.len() || proof.leaf_count < leaves.len() as u64 {
+		return Err(Error::InvalidProof);
+	}
+
+	let size = NodesUtils::new(proof.leaf_count).size();
+	let positioned_leaves = proof
+		.leaf_indices
+		.iter()
+		.map(|index| mmr_lib::leaf_index_to_pos(*index))
+		.zip(leaves.iter().cloned().map(DataOrHash::Data))
+		.collect();
+	let valid = mmr_lib::MerkleProof::<_, MmrHasher<Header::Hashing>>::new(
+		size,
+		proof.items.into_iter().map(DataOrHash::Hash).collect(),
+	)
+	.verify(DataOrHash::Hash(root), positioned_leaves)
+	.map_err(|_| Error::InvalidProof)?;
+	if !valid {
+		return Err(Error::InvalidProof);
+	}
+
+	leaves
+		.into_iter()
+		.map(|leaf| leaf.try_decode().ok_or(Error::InvalidValue))
+		.collect()
+}
+
+/// Merges MMR nodes like `pallet-mmr` does, hashing the concatenated hashes of both.
+struct MmrHasher<Hashing>(core::marker::PhantomData<Hashing>);
+
+impl<Hashing: HashT> mmr_lib::Merge for MmrHasher<Hashing> {
+	type Item = DataOrHash<Hashing, OpaqueLeaf>;
+
+	fn merge(left: &Self::Item, right: &Self::Item) -> mmr_lib::Result<Self::Item> {
+		let mut concat = left.hash().as_ref().to_vec();
+		concat.extend_from_slice(right.hash().as_ref());
+		Ok(DataOrHash::Hash(Hashing::hash(&concat)))
+	}
+}
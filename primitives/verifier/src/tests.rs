@@ -0,0 +1,292 @@
+#![cfg(test)]
+use super::*;
+use sp_core::storage::{StateVersion, Storage};
+use sp_core::H256;
+use sp_runtime::{
+	generic,
+	traits::{BlakeTwo256, Hash},
+	DigestItem,
+};
+use sp_state_machine::{prove_read, InMemoryBackend};
+use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+type Header = generic::Header<u32, BlakeTwo256>;
+
+/// Returns a header of block `number` whose state holds `blobs`, along with a proof of them.
+fn prove_blobs(number: u32, blobs: Option<Vec<Vec<u8>>>) -> (Header, CompactProof) {
+	let mut storage = Storage::default();
+	storage.top.insert(b"other".to_vec(), vec![1, 2, 3]);
+	if let Some(blobs) = blobs {
+		storage.top.insert(blobs_storage_key(number), blobs.encode());
+	}
+	let backend = InMemoryBackend::<BlakeTwo256>::from((storage, StateVersion::V1));
+	let root = *backend.root();
+	let proof = prove_read(backend, [blobs_storage_key(number)])
+		.unwrap()
+		.into_compact_proof::<BlakeTwo256>(root)
+		.unwrap();
+	let header =
+		Header::new(number, Default::default(), root, Default::default(), Default::default());
+
+	(header, proof)
+}
+
+#[test]
+fn blobs_storage_key_matches_pallet() {
+	// `twox_128("BlobManager") ++ twox_128("Blobs") ++ blake2_128_concat(7u32)`
+	let key = blobs_storage_key(7u32);
+	assert_eq!(key.len(), 16 + 16 + 16 + 4);
+	assert_eq!(&key[..16], &twox_128(b"BlobManager"));
+	assert_eq!(&key[16..32], &twox_128(b"Blobs"));
+	assert_eq!(&key[32..48], &blake2_128(&7u32.encode()));
+	assert_eq!(&key[48..], &7u32.encode()[..]);
+}
+
+#[test]
+fn verify_blob_storage_proof_works() {
+	let blobs = vec![vec![42u8; 100], vec![7u8; 10]];
+	let (header, proof) = prove_blobs(5, Some(blobs.clone()));
+
+	assert_eq!(verify_blob_storage_proof(&header, 0, &proof), Ok(blobs[0].clone()));
+	assert_eq!(verify_blob_storage_proof(&header, 1, &proof), Ok(blobs[1].clone()));
+	assert_eq!(verify_blob_storage_proof(&header, 2, &proof), Err(Error::BlobNotFound));
+}
+
+#[test]
+fn verify_blob_storage_proof_of_absence() {
+	let (header, proof) = prove_blobs(5, None);
+
+	assert_eq!(verify_blob_storage_proof(&header, 0, &proof), Err(Error::BlobNotFound));
+}
+
+#[test]
+fn verify_blob_storage_proof_wrong_root() {
+	let (mut header, proof) = prove_blobs(5, Some(vec![vec![42u8; 100]]));
+	header.state_root = BlakeTwo256::hash(b"wrong");
+
+	assert_eq!(verify_blob_storage_proof(&header, 0, &proof), Err(Error::InvalidProof));
+}
+
+/// Extrinsic standing in for the runtime's, uploading a blob if `.0` is `1`.
+type TestExtrinsic = (u8, Vec<u8>);
+
+fn blob_of((kind, blob): TestExtrinsic) -> Option<Vec<u8>> {
+	(kind == 1).then_some(blob)
+}
+
+fn encode_all(extrinsics: &[TestExtrinsic]) -> Vec<Vec<u8>> {
+	extrinsics.iter().map(Encode::encode).collect()
+}
+
+/// Returns a header of a block with the `encoded` extrinsics, along with a proof of extrinsic
+/// `index`.
+fn prove_extrinsic(encoded: &[Vec<u8>], index: u32) -> (Header, Vec<Vec<u8>>) {
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut root = Default::default();
+	{
+		let mut trie = TrieDBMutBuilder::<LayoutV0<BlakeTwo256>>::new(&mut db, &mut root).build();
+		for (i, extrinsic) in encoded.iter().enumerate() {
+			trie.insert(&extrinsic_key(i as u32), extrinsic).unwrap();
+		}
+	}
+	let proof = sp_trie::generate_trie_proof::<LayoutV0<BlakeTwo256>, _, _, _>(
+		&db,
+		root,
+		&[extrinsic_key(index)],
+	)
+	.unwrap();
+	let header = Header::new(1, root, Default::default(), Default::default(), Default::default());
+
+	(header, proof)
+}
+
+#[test]
+fn extrinsics_trie_matches_ordered_trie_root() {
+	let encoded = encode_all(&[(0, vec![1]), (1, vec![42; 100]), (1, vec![7; 10])]);
+	let (header, _) = prove_extrinsic(&encoded, 0);
+
+	assert_eq!(header.extrinsics_root, BlakeTwo256::ordered_trie_root(encoded, StateVersion::V0));
+}
+
+#[test]
+fn verify_blob_inclusion_proof_works() {
+	let extrinsics: [TestExtrinsic; 3] = [(0, vec![1]), (1, vec![42; 100]), (1, vec![7; 10])];
+	let encoded = encode_all(&extrinsics);
+
+	for index in 1..3 {
+		let (header, proof) = prove_extrinsic(&encoded, index);
+		assert_eq!(
+			verify_blob_inclusion_proof(&header, index, &encoded[index as usize], &proof, blob_of),
+			Ok(extrinsics[index as usize].1.clone())
+		);
+	}
+}
+
+#[test]
+fn verify_blob_inclusion_proof_not_an_upload() {
+	let encoded = encode_all(&[(0, vec![1]), (1, vec![42; 100])]);
+	let (header, proof) = prove_extrinsic(&encoded, 0);
+
+	assert_eq!(
+		verify_blob_inclusion_proof(&header, 0, &encoded[0], &proof, blob_of),
+		Err(Error::BlobNotFound)
+	);
+}
+
+#[test]
+fn verify_blob_inclusion_proof_wrong_extrinsic() {
+	let encoded = encode_all(&[(0, vec![1]), (1, vec![42; 100])]);
+	let (header, proof) = prove_extrinsic(&encoded, 1);
+
+	// Claiming a different payload at the proven index.
+	let forged = (1u8, vec![43u8; 100]).encode();
+	assert_eq!(
+		verify_blob_inclusion_proof(&header, 1, &forged, &proof, blob_of),
+		Err(Error::InvalidProof)
+	);
+	// Claiming the proven extrinsic at another index.
+	assert_eq!(
+		verify_blob_inclusion_proof(&header, 0, &encoded[1], &proof, blob_of),
+		Err(Error::InvalidProof)
+	);
+}
+
+#[test]
+fn verify_blob_inclusion_proof_undecodable() {
+	let mut encoded = encode_all(&[(1, vec![42; 100])]);
+	encoded[0].truncate(10);
+	let (header, proof) = prove_extrinsic(&encoded, 0);
+
+	assert_eq!(
+		verify_blob_inclusion_proof(&header, 0, &encoded[0], &proof, blob_of),
+		Err(Error::InvalidValue)
+	);
+}
+
+#[test]
+fn verify_channel_history_works() {
+	let hashes = (0u8..5).map(|i| BlakeTwo256::hash(&[i])).collect::<Vec<_>>();
+	let mut chain = vec![(0, Default::default())];
+	for (i, hash) in hashes.iter().enumerate() {
+		let (sequence, accumulator) = chain[i];
+		chain.push((
+			sequence + 1,
+			channel_accumulator::<BlakeTwo256>(accumulator, sequence + 1, *hash),
+		));
+	}
+
+	// From the start and from a checkpoint
+	assert_eq!(verify_channel_history::<BlakeTwo256>(chain[0], &hashes, chain[5]), Ok(()));
+	assert_eq!(verify_channel_history::<BlakeTwo256>(chain[2], &hashes[2..], chain[5]), Ok(()));
+	assert_eq!(verify_channel_history::<BlakeTwo256>(chain[3], &[], chain[3]), Ok(()));
+}
+
+#[test]
+fn verify_channel_history_incomplete() {
+	let hashes = (0u8..3).map(|i| BlakeTwo256::hash(&[i])).collect::<Vec<_>>();
+	let mut head = (0, Default::default());
+	for hash in &hashes {
+		head = (head.0 + 1, channel_accumulator::<BlakeTwo256>(head.1, head.0 + 1, *hash));
+	}
+	let start = (0, Default::default());
+
+	// Missing, reordered and replaced messages
+	assert_eq!(
+		verify_channel_history::<BlakeTwo256>(start, &[hashes[0], hashes[2]], head),
+		Err(Error::IncompleteHistory)
+	);
+	assert_eq!(
+		verify_channel_history::<BlakeTwo256>(start, &[hashes[1], hashes[0], hashes[2]], head),
+		Err(Error::IncompleteHistory)
+	);
+	assert_eq!(
+		verify_channel_history::<BlakeTwo256>(
+			start,
+			&[hashes[0], hashes[1], BlakeTwo256::hash(b"other")],
+			head
+		),
+		Err(Error::IncompleteHistory)
+	);
+}
+
+#[test]
+fn verify_blob_commitment_proof_works() {
+	let hashes = (0u8..3).map(|i| BlakeTwo256::hash(&[i])).collect::<Vec<_>>();
+	let values = hashes.iter().map(|hash| hash.as_bytes().to_vec()).collect::<Vec<_>>();
+
+	for index in 0..3 {
+		let (header, proof) = prove_extrinsic(&values, index);
+		let blob_root = header.extrinsics_root;
+		assert_eq!(
+			verify_blob_commitment_proof::<BlakeTwo256>(
+				blob_root,
+				index,
+				hashes[index as usize],
+				&proof
+			),
+			Ok(())
+		);
+		// Claiming another blob at the proven index
+		assert_eq!(
+			verify_blob_commitment_proof::<BlakeTwo256>(
+				blob_root,
+				index,
+				BlakeTwo256::hash(b"other"),
+				&proof
+			),
+			Err(Error::InvalidProof)
+		);
+	}
+}
+
+/// MMR leaf standing in for the runtime's.
+type TestLeaf = (u32, H256, H256);
+
+fn leaf(number: u32) -> TestLeaf {
+	(number, BlakeTwo256::hash(&number.encode()), BlakeTwo256::hash(b"blobs"))
+}
+
+fn encode_leaves(leaves: &[TestLeaf]) -> Vec<u8> {
+	leaves.iter().map(EncodableOpaqueLeaf::from_leaf).collect::<Vec<_>>().encode()
+}
+
+/// Returns a header carrying `mmr_root` in its digest, if any.
+fn header_with_mmr_root(mmr_root: Option<H256>) -> Header {
+	let mut header = Header::new(2, H256::zero(), H256::zero(), H256::zero(), Default::default());
+	if let Some(mmr_root) = mmr_root {
+		header.digest.push(DigestItem::Consensus(MMR_ROOT_ENGINE_ID, mmr_root.encode()));
+	}
+	header
+}
+
+#[test]
+fn verify_mmr_leaves_proof_works() {
+	// An MMR of two leaves has a single peak merging both leaf hashes
+	let hashes = [leaf(0), leaf(1)].map(|leaf| BlakeTwo256::hash(&leaf.encode()));
+	let root = BlakeTwo256::hash(&[hashes[0].as_bytes(), hashes[1].as_bytes()].concat());
+	let header = header_with_mmr_root(Some(root));
+	let proof = LeafProof { leaf_indices: vec![1], leaf_count: 2, items: vec![hashes[0]] }.encode();
+
+	assert_eq!(mmr_root(&header), Some(root));
+	assert_eq!(
+		verify_mmr_leaves_proof::<_, TestLeaf>(&header, &encode_leaves(&[leaf(1)]), &proof),
+		Ok(vec![leaf(1)])
+	);
+	// Claiming another leaf at the proven index
+	assert_eq!(
+		verify_mmr_leaves_proof::<_, TestLeaf>(&header, &encode_leaves(&[leaf(2)]), &proof),
+		Err(Error::InvalidProof)
+	);
+}
+
+#[test]
+fn verify_mmr_leaves_proof_without_mmr_root() {
+	let header = header_with_mmr_root(None);
+	let proof = LeafProof::<H256> { leaf_indices: vec![0], leaf_count: 1, items: vec![] }.encode();
+
+	assert_eq!(mmr_root(&header), None);
+	assert_eq!(
+		verify_mmr_leaves_proof::<_, TestLeaf>(&header, &encode_leaves(&[leaf(0)]), &proof),
+		Err(Error::MmrRootNotFound)
+	);
+}
@@ -1,8 +1,56 @@
-use crate::{Balance, BlockNumber};
+use crate::{Balance, BlockNumber, Perbill};
 
 pub mod blobmanager {
+	use super::*;
+	// Ceilings of the blob limits. The live limits are set on-chain with `set_limits`.
 	pub const MAX_BLOBS_PER_BLOCK: u32 = 5;
 	pub const MAX_BLOB_SIZE: u32 = 1024 * 1024; // 1 MB
+	// Blocks between proposing a new uploader and it accepting the handover.
+	pub const HANDOVER_DELAY: BlockNumber = 10;
+	// Blocks authorities wait before attesting the blobs of a block, enough for it to be
+	// finalized.
+	pub const ATTESTATION_DELAY: BlockNumber = 5;
+	// Blocks after storing blobs during which their availability can be attested.
+	pub const ATTESTATION_WINDOW: BlockNumber = 50;
+	// Share of the authorities whose attestations make the blobs of a block available.
+	pub const AVAILABILITY_QUORUM: Perbill = Perbill::from_percent(67);
+}
+
+pub mod blobchallenge {
+	use super::*;
+	// Deposit held from challengers, going to the responder of an answered challenge.
+	pub const CHALLENGE_DEPOSIT: Balance = 10_000_000_000;
+	// Blocks after storing a blob during which it can be challenged.
+	pub const CHALLENGE_WINDOW: BlockNumber = 100;
+	// Blocks after opening a challenge during which it can be answered.
+	pub const RESPONSE_PERIOD: BlockNumber = 20;
+}
+
+pub mod storageprovider {
+	use super::*;
+	// Minimum bond of a storage provider to get blobs assigned.
+	pub const MIN_BOND: Balance = 100_000_000_000;
+	// Maximum number of registered storage providers.
+	pub const MAX_PROVIDERS: u32 = 100;
+	// Number of storage providers every blob is assigned to.
+	pub const REPLICATION_FACTOR: u32 = 3;
+	// Maximum number of blobs assigned to a single storage provider at once.
+	pub const MAX_ASSIGNMENTS: u32 = 1_000;
+	// Blocks after storing a blob during which its storage providers keep it.
+	pub const STORAGE_PERIOD: BlockNumber = 14_400;
+	// Blocks between storage proof rounds, the time storage providers have to submit a proof.
+	pub const CHALLENGE_PERIOD: BlockNumber = 100;
+	// Reward of an accepted storage proof.
+	pub const PROOF_REWARD: Balance = 1_000_000_000;
+	// Amount slashed from the bond for a missed storage proof or an unavailable blob.
+	pub const SLASH_AMOUNT: Balance = 10_000_000_000;
+	// Share of the slash of an unavailable blob paid to the challenger who found it.
+	pub const CHALLENGER_REWARD: Perbill = Perbill::from_percent(50);
+}
+
+pub mod mmr {
+	// Engine id of the header digest item carrying the MMR root.
+	pub const ROOT_ENGINE_ID: [u8; 4] = *b"bmmr";
 }
 
 pub mod scheduler {
@@ -22,6 +70,38 @@ pub mod referenda {
 	// storage reads/writes needed for smaller voters, but also result in delays to the automatic
 	// referendum status changes. Explicit servicing instructions are unaffected.
 	pub const ALARM_INTERVAL: BlockNumber = 1;
+
+	pub mod blob_admin_track {
+		use super::*;
+		// Maximum number of referenda of the track being decided at once.
+		pub const MAX_DECIDING: u32 = 5;
+		// Amount that must be placed on deposit before a referendum of the track can be decided.
+		pub const DECISION_DEPOSIT: Balance = 10_000_000_000;
+		// Minimum period after submission before a referendum of the track can be decided.
+		pub const PREPARE_PERIOD: BlockNumber = 5;
+		// Period of the decision of a referendum of the track.
+		pub const DECISION_PERIOD: BlockNumber = 10;
+		// Period a referendum of the track must keep passing for before it is approved.
+		pub const CONFIRM_PERIOD: BlockNumber = 3;
+		// Minimum period between the approval of a referendum of the track and its enactment.
+		pub const MIN_ENACTMENT_PERIOD: BlockNumber = 2;
+	}
+
+	pub mod blob_emergency_track {
+		use super::*;
+		// Maximum number of referenda of the track being decided at once.
+		pub const MAX_DECIDING: u32 = 1;
+		// Amount that must be placed on deposit before a referendum of the track can be decided.
+		pub const DECISION_DEPOSIT: Balance = 50_000_000_000;
+		// Minimum period after submission before a referendum of the track can be decided.
+		pub const PREPARE_PERIOD: BlockNumber = 1;
+		// Period of the decision of a referendum of the track.
+		pub const DECISION_PERIOD: BlockNumber = 5;
+		// Period a referendum of the track must keep passing for before it is approved.
+		pub const CONFIRM_PERIOD: BlockNumber = 1;
+		// Minimum period between the approval of a referendum of the track and its enactment.
+		pub const MIN_ENACTMENT_PERIOD: BlockNumber = 1;
+	}
 }
 
 pub mod conviction_voting {
@@ -5,15 +5,21 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 extern crate alloc;
 use alloc::{vec, vec::Vec};
+use codec::Encode;
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	traits::{
+		BlakeTwo256, Block as BlockT, DispatchInfoOf, IdentifyAccount, NumberFor, One,
+		PostDispatchInfoOf, Verify,
+	},
+	transaction_validity::{
+		TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
+	},
+	ApplyExtrinsicResult, DigestItem, MultiSignature,
 };
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -22,8 +28,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, derive_impl, parameter_types,
 	traits::{
-		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse, KeyOwnerProofSystem,
-		OriginTrait, Randomness, StorageInfo, TotalIssuanceOf,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, EitherOf, EitherOfDiverse,
+		KeyOwnerProofSystem, OriginTrait, Randomness, StorageInfo, TotalIssuanceOf,
 	},
 	weights::{
 		constants::{
@@ -36,17 +42,22 @@ pub use frame_support::{
 use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
 	traits::{EqualPrivilegeOnly, VariantCountOf},
+	PalletId,
 };
 pub use frame_system::{Call as SystemCall, EnsureRoot, EnsureSigned};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_blobmanager;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::{ConstFeeMultiplier, FungibleAdapter, Multiplier};
+use pallet_transaction_payment::{
+	ConstFeeMultiplier, FungibleAdapter, Multiplier, OnChargeTransaction,
+};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
 mod constants;
+mod origins;
+pub use origins::pallet_custom_origins;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -87,6 +98,7 @@ pub mod opaque {
 		pub struct SessionKeys {
 			pub aura: Aura,
 			pub grandpa: Grandpa,
+			pub blob_manager: BlobManager,
 		}
 	}
 }
@@ -103,10 +115,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 112,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -234,13 +246,80 @@ parameter_types! {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = FungibleAdapter<Balances, ()>;
+	type OnChargeTransaction = BlobFeeAdapter;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<Balance>;
 	type LengthToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
 }
 
+/// Fee handling of other transactions: the fees are burned.
+type BurnFees = FungibleAdapter<Balances, ()>;
+
+/// Fee handling of blob uploads: the fees go to the reward pot of the storage providers.
+type RewardPotFees = FungibleAdapter<Balances, pallet_storageprovider::RewardPot<Runtime>>;
+
+/// Charges transaction fees like [`FungibleAdapter`], paying those of blob uploads into the
+/// reward pot of the storage providers and burning all others.
+///
+/// Only direct upload calls count as blob uploads, not uploads wrapped in e.g. a batch.
+pub struct BlobFeeAdapter;
+
+impl OnChargeTransaction<Runtime> for BlobFeeAdapter {
+	type Balance = Balance;
+	// The withdrawn fee, and whether it goes to the reward pot
+	type LiquidityInfo = (<BurnFees as OnChargeTransaction<Runtime>>::LiquidityInfo, bool);
+
+	fn withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let is_upload = matches!(
+			call,
+			RuntimeCall::BlobManager(
+				pallet_blobmanager::Call::upload_blob { .. } |
+					pallet_blobmanager::Call::upload_blobs { .. } |
+					pallet_blobmanager::Call::upload_blob_with_meta { .. } |
+					pallet_blobmanager::Call::upload_to_channel { .. }
+			)
+		);
+
+		Ok((BurnFees::withdraw_fee(who, call, dispatch_info, fee, tip)?, is_upload))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: Balance,
+		tip: Balance,
+		(already_withdrawn, is_upload): Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		if is_upload {
+			RewardPotFees::correct_and_deposit_fee(
+				who,
+				dispatch_info,
+				post_info,
+				corrected_fee,
+				tip,
+				already_withdrawn,
+			)
+		} else {
+			BurnFees::correct_and_deposit_fee(
+				who,
+				dispatch_info,
+				post_info,
+				corrected_fee,
+				tip,
+				already_withdrawn,
+			)
+		}
+	}
+}
+
 impl pallet_sudo::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -276,6 +355,42 @@ impl pallet_utility::Config for Runtime {
 parameter_types! {
 	pub const MaxBlobsPerBlock: u32 = constants::blobmanager::MAX_BLOBS_PER_BLOCK;
 	pub const MaxBlobSize: u32 = constants::blobmanager::MAX_BLOB_SIZE;
+	pub const HandoverDelay: BlockNumber = constants::blobmanager::HANDOVER_DELAY;
+	pub const AvailabilityQuorum: Perbill = constants::blobmanager::AVAILABILITY_QUORUM;
+	pub const AttestationDelay: BlockNumber = constants::blobmanager::ATTESTATION_DELAY;
+	pub const AttestationWindow: BlockNumber = constants::blobmanager::ATTESTATION_WINDOW;
+	pub const AttestationPriority: TransactionPriority = TransactionPriority::MAX / 2;
+}
+
+/// Calls of the runtime dispatching other calls, so blob uploads nested in them are still checked
+/// by the transaction pool and counted by the block author.
+pub struct WrappedCalls;
+
+impl pallet_blobmanager::WrappedCalls<RuntimeCall> for WrappedCalls {
+	fn wrapped(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. } |
+				pallet_utility::Call::dispatch_as { call, .. } |
+				pallet_utility::Call::with_weight { call, .. },
+			) |
+			RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi_threshold_1 { call, .. } |
+				pallet_multisig::Call::as_multi { call, .. },
+			) |
+			RuntimeCall::Sudo(
+				pallet_sudo::Call::sudo { call } |
+				pallet_sudo::Call::sudo_unchecked_weight { call, .. } |
+				pallet_sudo::Call::sudo_as { call, .. },
+			) => vec![&**call],
+			_ => Vec::new(),
+		}
+	}
 }
 
 impl pallet_blobmanager::Config for Runtime {
@@ -283,9 +398,191 @@ impl pallet_blobmanager::Config for Runtime {
 	type WeightInfo = pallet_blobmanager::weights::SubstrateWeight<Runtime>;
 	type MaxBlobsPerBlock = MaxBlobsPerBlock;
 	type MaxBlobSize = MaxBlobSize;
-	type AdminOrigin = EnsureRoot<AccountId>;
+	type AdminOrigin = EitherOf<EnsureRoot<AccountId>, pallet_custom_origins::BlobAdmin>;
+	type PauseOrigin = EitherOf<
+		EnsureRoot<AccountId>,
+		EitherOf<pallet_custom_origins::BlobAdmin, pallet_custom_origins::BlobEmergency>,
+	>;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type HandoverDelay = HandoverDelay;
+	type BlobValidator = ();
+	type WrappedCalls = WrappedCalls;
+	type OnBlobStored = StorageProvider;
+	type AuthorityId = pallet_blobmanager::AuthorityId;
+	type MaxAuthorities = <Runtime as pallet_aura::Config>::MaxAuthorities;
+	type AvailabilityQuorum = AvailabilityQuorum;
+	type AttestationDelay = AttestationDelay;
+	type AttestationWindow = AttestationWindow;
+	type UnsignedPriority = AttestationPriority;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+parameter_types! {
+	pub const ChallengeDeposit: Balance = constants::blobchallenge::CHALLENGE_DEPOSIT;
+	pub const ChallengeWindow: BlockNumber = constants::blobchallenge::CHALLENGE_WINDOW;
+	pub const ResponsePeriod: BlockNumber = constants::blobchallenge::RESPONSE_PERIOD;
+}
+
+impl pallet_blobchallenge::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = pallet_blobchallenge::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type Blobs = BlobManager;
+	type ChallengeDeposit = ChallengeDeposit;
+	type ChallengeWindow = ChallengeWindow;
+	type ResponsePeriod = ResponsePeriod;
+	type OnBlobUnavailable = StorageProvider;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BlobBenchmarkHelper;
+}
+
+parameter_types! {
+	pub const StorageProviderPalletId: PalletId = PalletId(*b"py/stprv");
+	pub const MinProviderBond: Balance = constants::storageprovider::MIN_BOND;
+	pub const MaxProviders: u32 = constants::storageprovider::MAX_PROVIDERS;
+	pub const ReplicationFactor: u32 = constants::storageprovider::REPLICATION_FACTOR;
+	pub const MaxAssignments: u32 = constants::storageprovider::MAX_ASSIGNMENTS;
+	pub const StoragePeriod: BlockNumber = constants::storageprovider::STORAGE_PERIOD;
+	pub const StorageChallengePeriod: BlockNumber = constants::storageprovider::CHALLENGE_PERIOD;
+	pub const ProofReward: Balance = constants::storageprovider::PROOF_REWARD;
+	pub const ProviderSlashAmount: Balance = constants::storageprovider::SLASH_AMOUNT;
+	pub const ChallengerReward: Perbill = constants::storageprovider::CHALLENGER_REWARD;
+}
+
+impl pallet_storageprovider::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = pallet_storageprovider::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type Blobs = BlobManager;
+	type Randomness = RandomnessCollectiveFlip;
+	type PalletId = StorageProviderPalletId;
+	type MinBond = MinProviderBond;
+	type MaxProviders = MaxProviders;
+	type ReplicationFactor = ReplicationFactor;
+	type MaxAssignments = MaxAssignments;
+	type MaxPendingBlobs = MaxBlobsPerBlock;
+	type StoragePeriod = StoragePeriod;
+	type ChallengePeriod = StorageChallengePeriod;
+	type ProofReward = ProofReward;
+	type SlashAmount = ProviderSlashAmount;
+	type ChallengerReward = ChallengerReward;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BlobBenchmarkHelper;
+}
+
+// Randomness of the storage providers, mixed from the hashes of the last 81 blocks.
+//
+// It's known a block in advance and the author of a block can still bias the next draws by
+// choosing its contents or skipping its slot, but no single parent hash decides them anymore. A VRF
+// output like BABE's would close that, but Aura has none; the draws only pick which providers get
+// new blobs and which assigned chunk they prove, and providers have to keep every assigned blob
+// either way.
+impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
+
+/// Stores the blobs challenged and assigned in benchmarks through the blob manager.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BlobBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BlobBenchmarkHelper {
+	fn store_blob(blob: Vec<u8>) -> (BlockNumber, u32) {
+		use pallet_blobmanager::BlobProvider;
+
+		let uploader: AccountId = frame_benchmarking::account("uploader", 0, 0);
+		pallet_blobmanager::Uploader::<Runtime>::put(&uploader);
+		BlobManager::upload_blob(RuntimeOrigin::signed(uploader), blob)
+			.expect("upload_blob() fail");
+
+		let block_number = System::block_number();
+		(block_number, BlobManager::blob_count(block_number) - 1)
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_blobchallenge::BenchmarkHelper<BlockNumber> for BlobBenchmarkHelper {
+	fn store_blob(blob: Vec<u8>) -> (BlockNumber, u32) {
+		Self::store_blob(blob)
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_storageprovider::BenchmarkHelper<BlockNumber> for BlobBenchmarkHelper {
+	fn store_blob(blob: Vec<u8>) -> (BlockNumber, u32) {
+		Self::store_blob(blob)
+	}
+}
+
+/// Provides MMR leaves committing to the blobs of the parent block.
+pub struct BlobMmrLeafProvider;
+
+impl mmr::LeafDataProvider for BlobMmrLeafProvider {
+	type LeafData = pallet_blobmanager::BlobMmrLeaf<BlockNumber, Hash>;
+
+	fn leaf_data() -> Self::LeafData {
+		use pallet_blobmanager::WeightInfo as _;
+
+		let (block_number, block_hash) =
+			<pallet_mmr::ParentNumberAndHash<Runtime> as mmr::LeafDataProvider>::leaf_data();
+
+		// Computing the blob root isn't covered by the weight of the MMR pallet's hook.
+		let blobs = pallet_blobmanager::BlobHashes::<Runtime>::decode_len(block_number)
+			.unwrap_or_default() as u32;
+		System::register_extra_weight_unchecked(
+			<Runtime as pallet_blobmanager::Config>::WeightInfo::blob_root(blobs),
+			frame_support::dispatch::DispatchClass::Mandatory,
+		);
+
+		pallet_blobmanager::BlobMmrLeaf {
+			block_number,
+			block_hash,
+			blob_root: BlobManager::blob_root(block_number),
+		}
+	}
 }
 
+/// Deposits every new MMR root into the header digest, so it can be read from the header.
+pub struct DepositMmrRoot;
+
+impl mmr::OnNewRoot<mmr::Hash> for DepositMmrRoot {
+	fn on_new_root(root: &mmr::Hash) {
+		System::deposit_log(DigestItem::Consensus(constants::mmr::ROOT_ENGINE_ID, root.encode()));
+	}
+}
+
+impl pallet_mmr::Config for Runtime {
+	const INDEXING_PREFIX: &'static [u8] = mmr::INDEXING_PREFIX;
+	type Hashing = BlakeTwo256;
+	type LeafData = BlobMmrLeafProvider;
+	type OnNewRoot = DepositMmrRoot;
+	type BlockHashProvider = pallet_mmr::DefaultBlockHashProvider<Runtime>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// MMR helper types.
+mod mmr {
+	use super::Runtime;
+	pub use pallet_mmr::primitives::*;
+
+	pub type Leaf = <<Runtime as pallet_mmr::Config>::LeafData as LeafDataProvider>::LeafData;
+	pub type Hashing = <Runtime as pallet_mmr::Config>::Hashing;
+	pub type Hash = <Hashing as sp_runtime::traits::Hash>::Output;
+}
+
+impl pallet_custom_origins::Config for Runtime {}
+
 impl pallet_preimage::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
@@ -333,28 +630,75 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 	type Id = u16;
 	type RuntimeOrigin = <RuntimeOrigin as OriginTrait>::PalletsOrigin;
 	fn tracks() -> &'static [(Self::Id, pallet_referenda::TrackInfo<Balance, BlockNumber>)] {
-		static DATA: [(u16, pallet_referenda::TrackInfo<Balance, BlockNumber>); 1] = [(
-			0u16, // Id
-			pallet_referenda::TrackInfo {
-				name: "root",
-				max_deciding: 1,
-				decision_deposit: 10,
-				prepare_period: 10,
-				decision_period: 15,
-				confirm_period: 5,
-				min_enactment_period: 5,
-				min_approval: pallet_referenda::Curve::LinearDecreasing {
-					length: Perbill::from_percent(100),
-					floor: Perbill::from_percent(50),
-					ceil: Perbill::from_percent(100),
+		static DATA: [(u16, pallet_referenda::TrackInfo<Balance, BlockNumber>); 3] = [
+			(
+				0u16, // Id
+				pallet_referenda::TrackInfo {
+					name: "root",
+					max_deciding: 1,
+					decision_deposit: 10,
+					prepare_period: 10,
+					decision_period: 15,
+					confirm_period: 5,
+					min_enactment_period: 5,
+					min_approval: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(50),
+						ceil: Perbill::from_percent(100),
+					},
+					min_support: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(0),
+						ceil: Perbill::from_percent(100),
+					},
+				},
+			),
+			(
+				1u16, // Id
+				pallet_referenda::TrackInfo {
+					name: "blob_admin",
+					max_deciding: constants::referenda::blob_admin_track::MAX_DECIDING,
+					decision_deposit: constants::referenda::blob_admin_track::DECISION_DEPOSIT,
+					prepare_period: constants::referenda::blob_admin_track::PREPARE_PERIOD,
+					decision_period: constants::referenda::blob_admin_track::DECISION_PERIOD,
+					confirm_period: constants::referenda::blob_admin_track::CONFIRM_PERIOD,
+					min_enactment_period: constants::referenda::blob_admin_track::MIN_ENACTMENT_PERIOD,
+					min_approval: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(50),
+						ceil: Perbill::from_percent(100),
+					},
+					min_support: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(0),
+						ceil: Perbill::from_percent(50),
+					},
 				},
-				min_support: pallet_referenda::Curve::LinearDecreasing {
-					length: Perbill::from_percent(100),
-					floor: Perbill::from_percent(0),
-					ceil: Perbill::from_percent(100),
+			),
+			(
+				2u16, // Id
+				pallet_referenda::TrackInfo {
+					name: "blob_emergency",
+					max_deciding: constants::referenda::blob_emergency_track::MAX_DECIDING,
+					decision_deposit: constants::referenda::blob_emergency_track::DECISION_DEPOSIT,
+					prepare_period: constants::referenda::blob_emergency_track::PREPARE_PERIOD,
+					decision_period: constants::referenda::blob_emergency_track::DECISION_PERIOD,
+					confirm_period: constants::referenda::blob_emergency_track::CONFIRM_PERIOD,
+					min_enactment_period:
+						constants::referenda::blob_emergency_track::MIN_ENACTMENT_PERIOD,
+					min_approval: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(67),
+						ceil: Perbill::from_percent(100),
+					},
+					min_support: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(0),
+						ceil: Perbill::from_percent(25),
+					},
 				},
-			},
-		)];
+			),
+		];
 		&DATA[..]
 	}
 	fn track_for(origin: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
@@ -363,6 +707,11 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 				frame_system::RawOrigin::Root => Ok(0),
 				_ => Err(()),
 			}
+		} else if let Ok(custom_origin) = pallet_custom_origins::Origin::try_from(origin.clone()) {
+			match custom_origin {
+				pallet_custom_origins::Origin::BlobAdmin => Ok(1),
+				pallet_custom_origins::Origin::BlobEmergency => Ok(2),
+			}
 		} else {
 			Err(())
 		}
@@ -458,6 +807,21 @@ mod runtime {
 
 	#[runtime::pallet_index(14)]
 	pub type Referenda = pallet_referenda;
+
+	#[runtime::pallet_index(15)]
+	pub type Origins = pallet_custom_origins;
+
+	#[runtime::pallet_index(16)]
+	pub type Mmr = pallet_mmr;
+
+	#[runtime::pallet_index(17)]
+	pub type BlobChallenge = pallet_blobchallenge;
+
+	#[runtime::pallet_index(18)]
+	pub type StorageProvider = pallet_storageprovider;
+
+	#[runtime::pallet_index(19)]
+	pub type RandomnessCollectiveFlip = pallet_insecure_randomness_collective_flip;
 }
 
 /// The address format for describing accounts.
@@ -476,6 +840,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_blobmanager::CheckBlobUploads<Runtime>,
 );
 
 /// All migrations of the runtime, aside from the ones declared in the pallets.
@@ -514,6 +879,9 @@ mod benches {
 		[pallet_scheduler, Scheduler]
 		[pallet_conviction_voting, ConvictionVoting]
 		[pallet_referenda, Referenda]
+		[pallet_mmr, Mmr]
+		[pallet_blobchallenge, BlobChallenge]
+		[pallet_storageprovider, StorageProvider]
 	);
 }
 
@@ -685,6 +1053,87 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_blobmanager_runtime_api::BlobManagerApi<Block, BlockNumber> for Runtime {
+		fn blob_meta(
+			block_number: BlockNumber,
+			index: u32,
+		) -> Option<pallet_blobmanager::BlobMeta<BlockNumber>> {
+			BlobManager::blob_meta(block_number, index)
+		}
+
+		fn blob_metas(
+			block_number: BlockNumber,
+		) -> Vec<(u32, pallet_blobmanager::BlobMeta<BlockNumber>)> {
+			BlobManager::blob_metas(block_number)
+		}
+	}
+
+	impl pallet_blobmanager_runtime_api::BlobChannelApi<Block, AccountId, BlockNumber, Hash>
+		for Runtime
+	{
+		fn channel(
+			channel: pallet_blobmanager::ChannelId,
+		) -> Option<pallet_blobmanager::Channel<AccountId, Hash>> {
+			pallet_blobmanager::Channels::<Runtime>::get(channel)
+		}
+
+		fn channel_messages(
+			channel: pallet_blobmanager::ChannelId,
+			from: u64,
+			count: u32,
+		) -> Vec<(u64, pallet_blobmanager::ChannelMessage<BlockNumber, Hash>)> {
+			BlobManager::channel_messages(channel, from, count)
+		}
+	}
+
+	impl mmr::MmrApi<Block, mmr::Hash, BlockNumber> for Runtime {
+		fn mmr_root() -> Result<mmr::Hash, mmr::Error> {
+			Ok(pallet_mmr::RootHash::<Runtime>::get())
+		}
+
+		fn mmr_leaf_count() -> Result<mmr::LeafIndex, mmr::Error> {
+			Ok(pallet_mmr::NumberOfLeaves::<Runtime>::get())
+		}
+
+		fn generate_proof(
+			block_numbers: Vec<BlockNumber>,
+			best_known_block_number: Option<BlockNumber>,
+		) -> Result<(Vec<mmr::EncodableOpaqueLeaf>, mmr::LeafProof<mmr::Hash>), mmr::Error> {
+			Mmr::generate_proof(block_numbers, best_known_block_number).map(|(leaves, proof)| {
+				(
+					leaves
+						.into_iter()
+						.map(|leaf| mmr::EncodableOpaqueLeaf::from_leaf(&leaf))
+						.collect(),
+					proof,
+				)
+			})
+		}
+
+		fn verify_proof(
+			leaves: Vec<mmr::EncodableOpaqueLeaf>,
+			proof: mmr::LeafProof<mmr::Hash>,
+		) -> Result<(), mmr::Error> {
+			let leaves = leaves
+				.into_iter()
+				.map(|leaf| leaf.into_opaque_leaf().try_decode().ok_or(mmr::Error::Verify))
+				.collect::<Result<Vec<mmr::Leaf>, mmr::Error>>()?;
+			Mmr::verify_leaves(leaves, proof)
+		}
+
+		fn verify_proof_stateless(
+			root: mmr::Hash,
+			leaves: Vec<mmr::EncodableOpaqueLeaf>,
+			proof: mmr::LeafProof<mmr::Hash>,
+		) -> Result<(), mmr::Error> {
+			let nodes = leaves
+				.into_iter()
+				.map(|leaf| mmr::DataOrHash::Data(leaf.into_opaque_leaf()))
+				.collect();
+			pallet_mmr::verify_leaves_proof::<mmr::Hashing, _>(root, nodes, proof)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
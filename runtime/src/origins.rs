@@ -0,0 +1,45 @@
+//! Custom origins for governance interventions.
+
+#[frame_support::pallet]
+pub mod pallet_custom_origins {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+	#[pallet::origin]
+	pub enum Origin {
+		/// Origin able to administer blob uploads, e.g. to change the uploader.
+		BlobAdmin,
+		/// Origin able to pause blob uploads in an emergency.
+		BlobEmergency,
+	}
+
+	macro_rules! decl_unit_ensures {
+		($name:ident) => {
+			#[doc = concat!("Ensures the origin is [`Origin::", stringify!($name), "`].")]
+			pub struct $name;
+			impl<O: Into<Result<Origin, O>> + From<Origin>> EnsureOrigin<O> for $name {
+				type Success = ();
+
+				fn try_origin(o: O) -> Result<Self::Success, O> {
+					o.into().and_then(|o| match o {
+						Origin::$name => Ok(()),
+						r => Err(O::from(r)),
+					})
+				}
+
+				#[cfg(feature = "runtime-benchmarks")]
+				fn try_successful_origin() -> Result<O, ()> {
+					Ok(O::from(Origin::$name))
+				}
+			}
+		};
+	}
+	decl_unit_ensures!(BlobAdmin);
+	decl_unit_ensures!(BlobEmergency);
+}